@@ -1,6 +1,6 @@
 use iracing_broadcast::{
-    BroadcastMessage, CameraState, ChatCommandMode, Client, PitCommandMode, ReplayPositionMode,
-    ReplaySearchMode, TelemetryCommandMode, VideoCaptureMode,
+    BroadcastMessage, CameraState, ChatCommandMode, Client, FfbForce, PitCommandMode,
+    ReplayPositionMode, ReplaySearchMode, ReplaySpeed, TelemetryCommandMode, VideoCaptureMode,
 };
 
 pub fn main() {
@@ -16,14 +16,18 @@ pub fn main() {
 
 fn demo_camera_messages(broadcast: &Client) {
     let _ = broadcast.send_message(BroadcastMessage::CameraSwitchPosition(0, 0, 0));
-    let _ = broadcast.send_message(BroadcastMessage::CameraSwitchNumber("064", 1, 1));
+    let _ = broadcast.send_message(BroadcastMessage::camera_switch_number("064", 1, 1));
     let scenic_camera = CameraState::IS_SCENIC_ACTIVE | CameraState::UI_HIDDEN;
     let _ = broadcast.send_message(BroadcastMessage::CameraSetState(scenic_camera));
 }
 
 fn demo_replay_messages(broadcast: &Client) {
-    let _ = broadcast.send_message(BroadcastMessage::ReplaySetPlaySpeed(1, false));
-    let _ = broadcast.send_message(BroadcastMessage::ReplaySetPlaySpeed(4, true));
+    let normal = ReplaySpeed::forward(1).expect("valid replay speed");
+    let _ = broadcast.send_message(BroadcastMessage::ReplaySetPlaySpeed(normal));
+    let quarter_speed = ReplaySpeed::new(4, true).expect("valid replay speed");
+    let _ = broadcast.send_message(BroadcastMessage::ReplaySetPlaySpeed(quarter_speed));
+    let rewind = ReplaySpeed::rewind(2).expect("valid replay speed");
+    let _ = broadcast.send_message(BroadcastMessage::ReplaySetPlaySpeed(rewind));
     let _ = broadcast.send_message(BroadcastMessage::ReplaySetPlayPosition(
         ReplayPositionMode::Begin,
         0,
@@ -31,7 +35,9 @@ fn demo_replay_messages(broadcast: &Client) {
     let _ = broadcast.send_message(BroadcastMessage::ReplaySearch(
         ReplaySearchMode::NextIncident,
     ));
-    let _ = broadcast.send_message(BroadcastMessage::ReplaySetState);
+    // Erases the replay tape; it neither pauses nor returns to live.
+    let _ = broadcast.send_message(BroadcastMessage::replay_erase_tape());
+    let _ = broadcast.send_message(BroadcastMessage::ReplaySearch(ReplaySearchMode::ToEnd));
     let _ = broadcast.send_message(BroadcastMessage::ReplaySearchSessionTime(0, 15_000));
     let _ = broadcast.send_message(BroadcastMessage::ReloadAllTextures);
     let _ = broadcast.send_message(BroadcastMessage::ReloadTextures(12));
@@ -54,10 +60,10 @@ fn demo_pit_messages(broadcast: &Client) {
         PitCommandMode::Clear,
         PitCommandMode::Tearoff,
         PitCommandMode::Fuel(65),
-        PitCommandMode::LF(26),
-        PitCommandMode::RF(26),
-        PitCommandMode::LR(26),
-        PitCommandMode::RR(26),
+        PitCommandMode::LF(179),
+        PitCommandMode::RF(179),
+        PitCommandMode::LR(179),
+        PitCommandMode::RR(179),
         PitCommandMode::ClearTires,
         PitCommandMode::FastRepair,
         PitCommandMode::ClearTearoff,
@@ -74,7 +80,9 @@ fn demo_telemetry_and_ffb(broadcast: &Client) {
     let _ = broadcast.send_message(BroadcastMessage::TelemetryCommand(
         TelemetryCommandMode::Restart,
     ));
-    let _ = broadcast.send_message(BroadcastMessage::FFBCommand(32_768));
+    let _ = broadcast.send_message(BroadcastMessage::FFBCommand(FfbForce::from_newton_meters(
+        12.5,
+    )));
 }
 
 fn demo_video_capture(broadcast: &Client) {
@@ -0,0 +1,38 @@
+//! Steps the force-feedback MaxForce down from a weak setting and back up,
+//! for checking a wheel's response.
+//!
+//! MaxForce is the torque that maps to full wheel output, so lower values
+//! give stronger forces. The sweep starts high, where the wheel is light,
+//! never goes below the wheel's own peak torque, and ends light again.
+//! Set `WHEEL_MAX_NM` to your wheel's peak torque before running.
+//!
+//! ```text
+//! cargo run --example ffb_sweep
+//! ```
+
+use std::{thread, time::Duration};
+
+use iracing_broadcast::{BroadcastMessage, Client, FfbForce};
+
+/// Peak torque of the wheel under test, in Nm.
+const WHEEL_MAX_NM: u16 = 10;
+/// MaxForce the sweep starts and ends at, where forces are light.
+const START_NM: u16 = 4 * WHEEL_MAX_NM;
+const STEP_NM: usize = 5;
+const HOLD: Duration = Duration::from_millis(500);
+
+pub fn main() {
+    let broadcast = Client::new().expect("Could not create broadcast client");
+
+    let down = (WHEEL_MAX_NM..=START_NM).rev().step_by(STEP_NM);
+    let up = (WHEEL_MAX_NM..=START_NM).step_by(STEP_NM).skip(1);
+    for newton_meters in down.chain(up) {
+        println!("FFB MaxForce at {newton_meters} Nm");
+        broadcast
+            .send_message(BroadcastMessage::FFBCommand(FfbForce::from_newton_meters(
+                newton_meters.into(),
+            )))
+            .expect("Could not set FFB force");
+        thread::sleep(HOLD);
+    }
+}
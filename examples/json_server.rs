@@ -0,0 +1,57 @@
+//! A local WebSocket server that relays JSON commands from a browser UI to
+//! the sim, one command per text frame.
+//!
+//! ```text
+//! cargo run --example json_server --features serve
+//! ```
+//!
+//! Then, from a browser console:
+//!
+//! ```text
+//! const ws = new WebSocket("ws://127.0.0.1:8182");
+//! ws.onmessage = (event) => console.log(event.data);
+//! ws.onopen = () => ws.send('{"PitCommand":{"Fuel":65}}');
+//! ```
+
+use std::{
+    net::{TcpListener, TcpStream},
+    thread,
+};
+
+use iracing_broadcast::{Client, serve};
+use tungstenite::Message;
+
+const ADDRESS: &str = "127.0.0.1:8182";
+
+pub fn main() {
+    let client = Client::new().expect("Could not create broadcast client");
+    let listener = TcpListener::bind(ADDRESS).expect("Could not bind server address");
+    println!("Listening on ws://{ADDRESS}");
+
+    for stream in listener.incoming().flatten() {
+        let client = client.clone();
+        thread::spawn(move || {
+            if let Err(error) = relay(&client, stream) {
+                eprintln!("Connection closed: {error}");
+            }
+        });
+    }
+}
+
+fn relay(client: &Client, stream: TcpStream) -> tungstenite::Result<()> {
+    let mut socket = tungstenite::accept(stream).map_err(|error| match error {
+        tungstenite::HandshakeError::Failure(error) => error,
+        tungstenite::HandshakeError::Interrupted(_) => unreachable!("the stream is blocking"),
+    })?;
+
+    loop {
+        match socket.read()? {
+            Message::Text(command) => {
+                let reply = serve::handle(client, &command);
+                socket.send(Message::Text(reply.into()))?;
+            }
+            Message::Close(_) => return Ok(()),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,2186 @@
+//! The [`BroadcastMessage`] enum and its wire encoding.
+//!
+//! Nothing here needs `std`, so messages can be built and encoded on
+//! targets that never run the sim and shipped to a Windows machine that
+//! does.
+
+use alloc::{format, string::String, vec::Vec};
+use core::{fmt, ops::RangeInclusive, time::Duration};
+
+use crate::{
+    BroadcastError, BroadcastMessageType, CameraFocusMode, CameraGroup, CameraState, CarNumber,
+    ChatCommandMode, FfbForce, PitCommandMode, ReloadTexturesMode, ReplayPositionMode,
+    ReplaySearchMode, ReplaySpeed, Result, TelemetryCommandMode, VideoCaptureMode,
+    util::{checked_param, pack, saturating_param, split_words, try_pad_car_number},
+};
+
+/// Messages that can be sent to the iRacing simulation.
+///
+/// Each variant maps to the documented window message contract in the iRacing
+/// SDK. Primitive parameters are passed through as-is and packed into the
+/// `WPARAM`/`LPARAM` pairs expected by the simulator.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::BroadcastMessage;
+///
+/// let _ = BroadcastMessage::CameraSwitchPosition(0, 0, 0);
+/// let _ = BroadcastMessage::CameraSwitchNumber("001".parse()?, 0, 0);
+/// let _ = BroadcastMessage::camera_switch_number("064", 1, 1);
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BroadcastMessage {
+    /// Switch to a specific camera group and camera index for a position.
+    ///
+    /// Only non-negative positions fit here; use
+    /// [`BroadcastMessage::CameraSwitchFocus`] for the SDK's negative
+    /// sentinels or an arbitrary signed position word.
+    CameraSwitchPosition(u8, u8, u8),
+    /// Switch to a specific camera group and camera index for a car number.
+    ///
+    /// Leading zeros in the car number are significant (`"064"` and `"64"`
+    /// are different cars).
+    CameraSwitchNumber(CarNumber, u8, u8),
+    /// Switch to a camera group and camera index, letting the sim pick the
+    /// focus car according to the [`CameraFocusMode`].
+    CameraSwitchFocus(CameraFocusMode, u8, u8),
+    /// Apply a new [`CameraState`] bitfield.
+    CameraSetState(CameraState),
+    /// Set the replay play speed, direction, and slow-motion mode.
+    ReplaySetPlaySpeed(ReplaySpeed),
+    /// Jump to a replay frame relative to the given [`ReplayPositionMode`].
+    ///
+    /// The frame number is split across the low and high words of `LPARAM`.
+    /// The sim reads it as a signed 32-bit offset, so relative seeks
+    /// backwards store the two's complement; see
+    /// [`BroadcastMessage::replay_seek_from_end`].
+    ReplaySetPlayPosition(ReplayPositionMode, u32),
+    /// Perform a replay search according to the provided mode.
+    ReplaySearch(ReplaySearchMode),
+    /// Erase the replay tape.
+    ///
+    /// Despite its name, this is not a play/pause toggle: the SDK's
+    /// `irsdk_ReplayStateMode` has the single mode `EraseTape` (`0`), sent
+    /// in `var1`, so the message needs no parameters and encodes to
+    /// `(ReplaySetState, 0, 0, 0)`. There is no "set to live" state either.
+    /// Use [`BroadcastMessage::replay_toggle`] or
+    /// [`BroadcastMessage::replay_pause`] to pause playback, and
+    /// [`ReplaySearchMode::ToEnd`] to return to live.
+    ReplaySetState,
+    /// Reload all textures.
+    ReloadAllTextures,
+    /// Reload textures for a specific car index.
+    ///
+    /// The simulator only addresses cars by index here; see
+    /// [`BroadcastMessage::reload_textures_by_number`] to start from a car
+    /// number.
+    ReloadTextures(u8),
+    /// Send a chat command.
+    ChatCommand(ChatCommandMode),
+    /// Send a chat macro by number.
+    ///
+    /// iRacing defines macros `1..=15`
+    /// ([`BroadcastMessage::CHAT_MACRO_RANGE`]); other numbers are passed
+    /// through unchecked for forward compatibility and are ignored by the
+    /// sim. Use [`BroadcastMessage::chat_macro`] to validate.
+    ChatCommandMacro(u8),
+    /// Issue a pit command.
+    PitCommand(PitCommandMode),
+    /// Control telemetry recording.
+    TelemetryCommand(TelemetryCommandMode),
+    /// Set the force-feedback MaxForce, the torque that maps to full wheel
+    /// output; lower values mean stronger forces. See [`FfbForce`].
+    ///
+    /// The force is sent as a 16.16 fixed-point integer (`value * 65536`)
+    /// split across the low and high words of `LPARAM`.
+    FFBCommand(FfbForce),
+    /// Search a replay to a session number and session time in milliseconds.
+    ///
+    /// The time is split across the low and high words of `LPARAM`. See
+    /// [`BroadcastMessage::replay_seek_session_time`] to build this from a
+    /// [`Duration`].
+    ReplaySearchSessionTime(u8, u32),
+    /// Control video capture.
+    VideoCapture(VideoCaptureMode),
+}
+
+impl BroadcastMessage {
+    /// Chat macro numbers defined by the simulator.
+    pub const CHAT_MACRO_RANGE: RangeInclusive<u8> = 1..=15;
+
+    /// Highest lap [`BroadcastMessage::replay_to_lap`] searches to. Each lap
+    /// is one more message, and the longest oval races run 500 laps.
+    pub const MAX_REPLAY_LAP: u16 = 500;
+
+    /// Build a [`BroadcastMessage::ChatCommandMacro`], rejecting macro
+    /// numbers outside [`BroadcastMessage::CHAT_MACRO_RANGE`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::BroadcastMessage;
+    ///
+    /// assert_eq!(BroadcastMessage::chat_macro(3)?, BroadcastMessage::ChatCommandMacro(3));
+    /// assert!(BroadcastMessage::chat_macro(16).is_err());
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn chat_macro(macro_number: u8) -> Result<Self> {
+        if !Self::CHAT_MACRO_RANGE.contains(&macro_number) {
+            return Err(BroadcastError::invalid_parameter(
+                "chat macro",
+                format!(
+                    "{macro_number} is outside {}..={}",
+                    Self::CHAT_MACRO_RANGE.start(),
+                    Self::CHAT_MACRO_RANGE.end()
+                ),
+            ));
+        }
+
+        Ok(BroadcastMessage::ChatCommandMacro(macro_number))
+    }
+
+    /// Like [`BroadcastMessage::chat_macro`], for macro numbers held in a
+    /// wider integer.
+    pub fn checked_chat_macro(macro_number: u32) -> Result<Self> {
+        checked_param("chat macro", macro_number, Self::CHAT_MACRO_RANGE)
+            .map(BroadcastMessage::ChatCommandMacro)
+    }
+
+    /// Build a [`BroadcastMessage::ChatCommandMacro`], clamping the macro
+    /// number into [`BroadcastMessage::CHAT_MACRO_RANGE`].
+    pub fn saturating_chat_macro(macro_number: u32) -> Self {
+        BroadcastMessage::ChatCommandMacro(saturating_param(macro_number, Self::CHAT_MACRO_RANGE))
+    }
+
+    /// Build a [`BroadcastMessage::CameraSwitchPosition`], rejecting a
+    /// position, group or camera above the field's maximum of 255.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::BroadcastMessage;
+    ///
+    /// assert!(BroadcastMessage::checked_camera_switch_position(1, 300, 1).is_err());
+    /// assert_eq!(
+    ///     BroadcastMessage::saturating_camera_switch_position(1, 300, 1),
+    ///     BroadcastMessage::CameraSwitchPosition(1, 255, 1)
+    /// );
+    /// ```
+    pub fn checked_camera_switch_position(position: u32, group: u32, camera: u32) -> Result<Self> {
+        Ok(BroadcastMessage::CameraSwitchPosition(
+            checked_param("camera position", position, 0..=u8::MAX)?,
+            checked_param("camera group", group, 0..=u8::MAX)?,
+            checked_param("camera", camera, 0..=u8::MAX)?,
+        ))
+    }
+
+    /// Build a [`BroadcastMessage::CameraSwitchPosition`], clamping the
+    /// position, group and camera to the field's maximum of 255.
+    pub fn saturating_camera_switch_position(position: u32, group: u32, camera: u32) -> Self {
+        BroadcastMessage::CameraSwitchPosition(
+            saturating_param(position, 0..=u8::MAX),
+            saturating_param(group, 0..=u8::MAX),
+            saturating_param(camera, 0..=u8::MAX),
+        )
+    }
+
+    /// Build a [`BroadcastMessage::CameraSwitchPosition`] for a 1-based
+    /// position, as shown on the timing screen.
+    ///
+    /// The sim numbers positions from 1 as well, so the position is sent
+    /// unchanged; `1` is the car in first. Position `0` belongs to no car and
+    /// is rejected with [`BroadcastError::InvalidParameter`], as its meaning
+    /// is left to the sim. The negative position words are the
+    /// [`CameraFocusMode`] sentinels, which
+    /// [`BroadcastMessage::CameraSwitchFocus`] sends instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, CameraGroup};
+    ///
+    /// assert_eq!(
+    ///     BroadcastMessage::camera_grid_position(1, CameraGroup::new(11), 0)?,
+    ///     BroadcastMessage::CameraSwitchPosition(1, 11, 0)
+    /// );
+    /// assert!(BroadcastMessage::camera_grid_position(0, 11, 0).is_err());
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn camera_grid_position(
+        position: u8,
+        group: impl Into<CameraGroup>,
+        camera: u8,
+    ) -> Result<Self> {
+        let position = checked_param("grid position", position.into(), 1..=u8::MAX)?;
+        Ok(BroadcastMessage::CameraSwitchPosition(
+            position,
+            group.into().get(),
+            camera,
+        ))
+    }
+
+    /// Build a [`BroadcastMessage::CameraSwitchFocus`], rejecting a group or
+    /// camera above the field's maximum of 255.
+    pub fn checked_camera_switch_focus(
+        mode: CameraFocusMode,
+        group: u32,
+        camera: u32,
+    ) -> Result<Self> {
+        Ok(BroadcastMessage::CameraSwitchFocus(
+            mode,
+            checked_param("camera group", group, 0..=u8::MAX)?,
+            checked_param("camera", camera, 0..=u8::MAX)?,
+        ))
+    }
+
+    /// Build a [`BroadcastMessage::CameraSwitchFocus`], clamping the group
+    /// and camera to the field's maximum of 255.
+    pub fn saturating_camera_switch_focus(mode: CameraFocusMode, group: u32, camera: u32) -> Self {
+        BroadcastMessage::CameraSwitchFocus(
+            mode,
+            saturating_param(group, 0..=u8::MAX),
+            saturating_param(camera, 0..=u8::MAX),
+        )
+    }
+
+    /// Build a [`BroadcastMessage::ReloadTextures`], rejecting car indices
+    /// above the field's maximum of 255.
+    pub fn checked_reload_textures(car_index: u32) -> Result<Self> {
+        checked_param("car index", car_index, 0..=u8::MAX).map(BroadcastMessage::ReloadTextures)
+    }
+
+    /// Build a [`BroadcastMessage::ReloadTextures`], clamping the car index
+    /// to the field's maximum of 255.
+    pub fn saturating_reload_textures(car_index: u32) -> Self {
+        BroadcastMessage::ReloadTextures(saturating_param(car_index, 0..=u8::MAX))
+    }
+
+    /// One [`BroadcastMessage::ReloadTextures`] per car index, in order, e.g.
+    /// to reload every car whose livery just changed.
+    ///
+    /// Car indices are `u8`, so every index is one the field can carry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::BroadcastMessage;
+    ///
+    /// assert_eq!(
+    ///     BroadcastMessage::reload_textures_range(3..=4),
+    ///     [BroadcastMessage::ReloadTextures(3), BroadcastMessage::ReloadTextures(4)]
+    /// );
+    /// ```
+    pub fn reload_textures_range(indices: impl IntoIterator<Item = u8>) -> Vec<Self> {
+        indices
+            .into_iter()
+            .map(BroadcastMessage::ReloadTextures)
+            .collect()
+    }
+
+    /// The `PitCommand` messages that deselect every pit service, in the
+    /// order they should be sent: a general clear, then tires, tearoff, fast
+    /// repair and fuel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, Client, MessageSink};
+    ///
+    /// let client = Client::offline();
+    /// client
+    ///     .send_all(BroadcastMessage::clear_all_pit_service())
+    ///     .expect("offline sends succeed");
+    /// ```
+    pub fn clear_all_pit_service() -> [Self; 5] {
+        [
+            PitCommandMode::Clear,
+            PitCommandMode::ClearTires,
+            PitCommandMode::ClearTearoff,
+            PitCommandMode::ClearFastRepair,
+            PitCommandMode::ClearFuel,
+        ]
+        .map(BroadcastMessage::PitCommand)
+    }
+
+    /// Build a [`BroadcastMessage::CameraSetState`] that sets or clears
+    /// `flag` while keeping every other flag of `current`.
+    ///
+    /// The simulator does not report camera state back over the broadcast
+    /// channel, so `current` must come from telemetry (`CamCameraState`) or
+    /// the last state this application sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, CameraState};
+    ///
+    /// let current = CameraState::CAM_TOOL_ACTIVE;
+    /// assert_eq!(
+    ///     BroadcastMessage::camera_set_flag(CameraState::UI_HIDDEN, true, current),
+    ///     BroadcastMessage::CameraSetState(CameraState::CAM_TOOL_ACTIVE | CameraState::UI_HIDDEN)
+    /// );
+    /// ```
+    pub fn camera_set_flag(flag: CameraState, on: bool, current: CameraState) -> Self {
+        BroadcastMessage::CameraSetState(current.to_builder().flag(flag, on).build())
+    }
+
+    /// Build a [`BroadcastMessage::CameraSwitchNumber`] from anything that
+    /// converts to a [`CarNumber`], such as a string read from config or a
+    /// timing feed, and a [`CameraGroup`] or raw group number.
+    ///
+    /// Malformed car numbers become car `0`; see
+    /// [`BroadcastMessage::try_camera_switch_number`] to reject them.
+    pub fn camera_switch_number(
+        car_number: impl Into<CarNumber>,
+        group: impl Into<CameraGroup>,
+        camera: u8,
+    ) -> Self {
+        BroadcastMessage::CameraSwitchNumber(car_number.into(), group.into().get(), camera)
+    }
+
+    /// Like [`BroadcastMessage::camera_switch_number`], but rejects car
+    /// numbers that [`try_pad_car_number`](crate::try_pad_car_number) would
+    /// refuse instead of sending them as car `0`.
+    pub fn try_camera_switch_number(
+        car_number: impl AsRef<str>,
+        group: impl Into<CameraGroup>,
+        camera: u8,
+    ) -> Result<Self> {
+        let car_number = CarNumber::new(car_number.as_ref())?;
+        Ok(Self::camera_switch_number(car_number, group, camera))
+    }
+
+    /// Build a [`BroadcastMessage::ReloadTextures`] for the car showing
+    /// `car_number`.
+    ///
+    /// Unlike camera switches, the reload message has no car-number form, so
+    /// the number is validated like
+    /// [`try_pad_car_number`](crate::try_pad_car_number) and then resolved to
+    /// a car index by `car_index_of`, typically a lookup into the session's
+    /// driver list. Returns [`BroadcastError::InvalidCarNumber`] if the number
+    /// is malformed or the lookup finds no car.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::BroadcastMessage;
+    ///
+    /// let drivers = [("7", 3), ("064", 12)];
+    /// let car_index_of = |number: &str| {
+    ///     drivers.iter().find(|(n, _)| *n == number).map(|&(_, index)| index)
+    /// };
+    ///
+    /// assert_eq!(
+    ///     BroadcastMessage::reload_textures_by_number("064", car_index_of)?,
+    ///     BroadcastMessage::ReloadTextures(12)
+    /// );
+    /// assert!(BroadcastMessage::reload_textures_by_number("64", car_index_of).is_err());
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn reload_textures_by_number<F>(car_number: &str, car_index_of: F) -> Result<Self>
+    where
+        F: FnOnce(&str) -> Option<u8>,
+    {
+        try_pad_car_number(car_number)?;
+        car_index_of(car_number)
+            .map(BroadcastMessage::ReloadTextures)
+            .ok_or_else(|| {
+                BroadcastError::invalid_car_number(car_number, "no car with this number")
+            })
+    }
+
+    /// Build a [`BroadcastMessage::ReplaySearchSessionTime`] from a
+    /// [`Duration`] into the session, truncated to whole milliseconds.
+    ///
+    /// Returns [`BroadcastError::InvalidParameter`] if the time does not fit
+    /// the 32-bit millisecond field (about 49.7 days).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use iracing_broadcast::BroadcastMessage;
+    ///
+    /// assert_eq!(
+    ///     BroadcastMessage::replay_seek_session_time(2, Duration::from_secs(90))?,
+    ///     BroadcastMessage::ReplaySearchSessionTime(2, 90_000)
+    /// );
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn replay_seek_session_time(session_number: u8, time: Duration) -> Result<Self> {
+        let millis = u32::try_from(time.as_millis()).map_err(|_| {
+            BroadcastError::invalid_parameter(
+                "session time",
+                format!("{time:?} exceeds the maximum of {} ms", u32::MAX),
+            )
+        })?;
+
+        Ok(BroadcastMessage::ReplaySearchSessionTime(
+            session_number,
+            millis,
+        ))
+    }
+
+    /// Like [`BroadcastMessage::replay_seek_session_time`], with the time
+    /// written as a clock: `"83.4"`, `"1:23.456"` or `"01:02:03"`.
+    ///
+    /// Hours and minutes are optional, seconds may carry a fraction, and
+    /// digits past the millisecond are truncated. Minutes and seconds after
+    /// the first field must be below 60. Surrounding whitespace is ignored.
+    ///
+    /// Returns [`BroadcastError::InvalidParameter`] describing the problem if
+    /// the time is malformed or too long.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::BroadcastMessage;
+    ///
+    /// assert_eq!(
+    ///     BroadcastMessage::replay_seek_session_time_str(2, "1:23.456")?,
+    ///     BroadcastMessage::ReplaySearchSessionTime(2, 83_456)
+    /// );
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn replay_seek_session_time_str(session_number: u8, time: &str) -> Result<Self> {
+        let time = parse_session_time(time)?;
+        Self::replay_seek_session_time(session_number, time)
+    }
+
+    /// Jump to `frames_before_end` frames before the last replay frame.
+    ///
+    /// Offsets from [`ReplayPositionMode::End`] count backwards as negative
+    /// frame numbers, stored as their two's complement. Offsets beyond
+    /// `i32::MAX` saturate; the sim clamps at the first frame anyway.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, ReplayPositionMode};
+    ///
+    /// assert_eq!(
+    ///     BroadcastMessage::replay_seek_from_end(60),
+    ///     BroadcastMessage::ReplaySetPlayPosition(ReplayPositionMode::End, -60i32 as u32)
+    /// );
+    /// ```
+    pub fn replay_seek_from_end(frames_before_end: u32) -> Self {
+        let offset = -i32::try_from(frames_before_end).unwrap_or(i32::MAX);
+        BroadcastMessage::ReplaySetPlayPosition(ReplayPositionMode::End, offset as u32)
+    }
+
+    /// Jump `offset` into the replay relative to `mode`, converted to whole
+    /// frames at `fps` frames per second (truncated).
+    ///
+    /// Offsets from [`ReplayPositionMode::End`] count backwards like
+    /// [`BroadcastMessage::replay_seek_from_end`]; the others count forwards.
+    /// The sim records replays at 60 fps, but its frame counter is what
+    /// matters, so pass the rate the replay's frame numbers advance at.
+    ///
+    /// Returns [`BroadcastError::InvalidParameter`] if `fps` is zero or the
+    /// frame count does not fit the signed 32-bit frame field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use iracing_broadcast::{BroadcastMessage, ReplayPositionMode};
+    ///
+    /// assert_eq!(
+    ///     BroadcastMessage::replay_seek_duration(
+    ///         ReplayPositionMode::Current,
+    ///         Duration::from_secs(30),
+    ///         60
+    ///     )?,
+    ///     BroadcastMessage::ReplaySetPlayPosition(ReplayPositionMode::Current, 1800)
+    /// );
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn replay_seek_duration(
+        mode: ReplayPositionMode,
+        offset: Duration,
+        fps: u32,
+    ) -> Result<Self> {
+        if fps == 0 {
+            return Err(BroadcastError::invalid_parameter(
+                "frame rate",
+                "must be at least 1 fps",
+            ));
+        }
+
+        let frames = offset.as_nanos() * u128::from(fps) / 1_000_000_000;
+        let frames = i32::try_from(frames).map_err(|_| {
+            BroadcastError::invalid_parameter(
+                "replay offset",
+                format!(
+                    "{offset:?} at {fps} fps exceeds the maximum of {} frames",
+                    i32::MAX
+                ),
+            )
+        })?;
+        let frames = match mode {
+            ReplayPositionMode::End => -frames,
+            ReplayPositionMode::Begin | ReplayPositionMode::Current => frames,
+        };
+
+        Ok(BroadcastMessage::ReplaySetPlayPosition(mode, frames as u32))
+    }
+
+    /// Play the replay forward at normal speed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, ReplaySpeed};
+    ///
+    /// assert_eq!(
+    ///     BroadcastMessage::replay_play(),
+    ///     BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::new(1, false)?)
+    /// );
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn replay_play() -> Self {
+        BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::normal())
+    }
+
+    /// Freeze the replay on the current frame.
+    pub fn replay_pause() -> Self {
+        BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::paused())
+    }
+
+    /// Pause the replay if it is playing at `current` speed, or play it at
+    /// normal speed if it is paused.
+    ///
+    /// The sim has no toggle message and does not report the play speed
+    /// over the broadcast channel, so `current` must come from telemetry
+    /// (`ReplayPlaySpeed`) or the last speed this application sent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, ReplaySpeed};
+    ///
+    /// assert_eq!(
+    ///     BroadcastMessage::replay_toggle(ReplaySpeed::paused()),
+    ///     BroadcastMessage::replay_play()
+    /// );
+    /// assert_eq!(
+    ///     BroadcastMessage::replay_toggle(ReplaySpeed::forward(4)?),
+    ///     BroadcastMessage::replay_pause()
+    /// );
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn replay_toggle(current: ReplaySpeed) -> Self {
+        if current.speed() == 0 {
+            Self::replay_play()
+        } else {
+            Self::replay_pause()
+        }
+    }
+
+    /// Erase the replay tape; the same as
+    /// [`BroadcastMessage::ReplaySetState`], under a name that says what it
+    /// does.
+    pub fn replay_erase_tape() -> Self {
+        BroadcastMessage::ReplaySetState
+    }
+
+    /// Play forward at `1/divisor` of normal speed.
+    ///
+    /// The slow-motion flag in `var2` changes how the sim reads `var1`: with
+    /// the flag clear `var1` multiplies normal speed, with it set `var1`
+    /// divides it. So `(2, 0)` is double speed while `(2, 1)` is half speed,
+    /// and `(1, 1)` plays at normal speed.
+    ///
+    /// Returns [`BroadcastError::InvalidParameter`] if `divisor` is `0` or
+    /// exceeds [`ReplaySpeed::MAX`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, ReplaySpeed};
+    ///
+    /// assert_eq!(
+    ///     BroadcastMessage::replay_slow_motion(4)?,
+    ///     BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::new(4, true)?)
+    /// );
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn replay_slow_motion(divisor: u8) -> Result<Self> {
+        if divisor == 0 {
+            return Err(BroadcastError::invalid_parameter(
+                "slow-motion divisor",
+                "must be at least 1",
+            ));
+        }
+        let divisor = i8::try_from(divisor).unwrap_or(i8::MAX);
+        ReplaySpeed::new(divisor, true).map(BroadcastMessage::ReplaySetPlaySpeed)
+    }
+
+    /// Play forward at `multiplier` times normal speed.
+    ///
+    /// Returns [`BroadcastError::InvalidParameter`] if `multiplier` exceeds
+    /// [`ReplaySpeed::MAX`]. A multiplier of `0` pauses, like
+    /// [`BroadcastMessage::replay_pause`].
+    pub fn replay_fast_forward(multiplier: u8) -> Result<Self> {
+        ReplaySpeed::forward(multiplier).map(BroadcastMessage::ReplaySetPlaySpeed)
+    }
+
+    /// The messages that show the car with `car_number` at the start of lap
+    /// `lap`, in the order they should be sent.
+    ///
+    /// The SDK has no message that searches to a lap, and replay frames are
+    /// not tied to laps in any way a client can compute. So the sequence
+    /// focuses the car, keeping the camera group and camera, pauses, rewinds
+    /// to the start of the replay and then searches for the next lap `lap`
+    /// times. Lap searches follow the focused car, so lap `0` is the start
+    /// of the replay and lap `n` is the car's `n`th crossing of the line
+    /// after it. That matches the car's own lap count only when the replay
+    /// starts before its first lap. Laps past the end of the replay stop on
+    /// the last frame. Send the messages in order and stop at the first
+    /// failure; `ReplayScrubber::jump_to_lap` sends the same sequence
+    /// without the focus, for whichever car is already focused.
+    ///
+    /// The sim handles the searches one at a time as they arrive, so a long
+    /// sequence can take a moment to land; see
+    /// `ReplayScrubber::with_step_interval` to pace the steps.
+    ///
+    /// Returns [`BroadcastError::InvalidCarNumber`] if `car_number` is
+    /// malformed and [`BroadcastError::InvalidParameter`] if `lap` is above
+    /// [`BroadcastMessage::MAX_REPLAY_LAP`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, Client, ReplaySearchMode};
+    ///
+    /// assert!(BroadcastMessage::replay_to_lap("064", 501).is_err());
+    /// let messages = BroadcastMessage::replay_to_lap("064", 2)?;
+    /// assert_eq!(
+    ///     messages,
+    ///     [
+    ///         BroadcastMessage::camera_switch_number("064", 0, 0),
+    ///         BroadcastMessage::replay_pause(),
+    ///         BroadcastMessage::ReplaySearch(ReplaySearchMode::ToStart),
+    ///         BroadcastMessage::ReplaySearch(ReplaySearchMode::NextLap),
+    ///         BroadcastMessage::ReplaySearch(ReplaySearchMode::NextLap),
+    ///     ]
+    /// );
+    ///
+    /// let client = Client::offline();
+    /// for message in messages {
+    ///     client.send_message(message)?;
+    /// }
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn replay_to_lap(car_number: &str, lap: u16) -> Result<Vec<Self>> {
+        let focus = Self::try_camera_switch_number(car_number, 0, 0)?;
+        let (rewind, steps) = Self::replay_lap_search(lap)?;
+        Ok(core::iter::once(focus).chain(rewind).chain(steps).collect())
+    }
+
+    /// The part of [`BroadcastMessage::replay_to_lap`] after the focus: the
+    /// pause and rewind to the start, then the `lap` steps forward.
+    pub(crate) fn replay_lap_search(
+        lap: u16,
+    ) -> Result<([Self; 2], impl Iterator<Item = Self> + use<>)> {
+        if lap > Self::MAX_REPLAY_LAP {
+            return Err(BroadcastError::invalid_parameter(
+                "replay lap",
+                format!("{lap} is outside 0..={}", Self::MAX_REPLAY_LAP),
+            ));
+        }
+
+        let rewind = [
+            Self::replay_pause(),
+            BroadcastMessage::ReplaySearch(ReplaySearchMode::ToStart),
+        ];
+        let steps = core::iter::repeat_n(
+            BroadcastMessage::ReplaySearch(ReplaySearchMode::NextLap),
+            usize::from(lap),
+        );
+        Ok((rewind, steps))
+    }
+
+    /// Set the force-feedback MaxForce to `percent_of_wheel` percent of
+    /// `wheel_max_newton_meters`, the peak torque of the caller's wheel;
+    /// values above `100` are clamped.
+    ///
+    /// This sets MaxForce, not force-feedback strength. MaxForce is the
+    /// torque that maps to full wheel output, so a **lower** percentage
+    /// gives **stronger** forces: at `100` the wheel's full torque matches
+    /// the sim's, and below that forces are amplified and clip sooner. `0`
+    /// is maximum gain, not "off"; the SDK has no message that turns force
+    /// feedback off. See [`FfbForce`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, FfbForce};
+    ///
+    /// // Half of a 20 Nm direct-drive base: forces twice as strong.
+    /// assert_eq!(
+    ///     BroadcastMessage::ffb_max_force_for_wheel(50, 20.0),
+    ///     BroadcastMessage::FFBCommand(FfbForce::from_newton_meters(10.0))
+    /// );
+    /// ```
+    pub fn ffb_max_force_for_wheel(percent_of_wheel: u8, wheel_max_newton_meters: f32) -> Self {
+        BroadcastMessage::FFBCommand(FfbForce::from_percent(
+            percent_of_wheel.into(),
+            wheel_max_newton_meters,
+        ))
+    }
+
+    /// Unpack a raw `WPARAM`/`LPARAM` pair into the message type and its
+    /// three parameter words.
+    ///
+    /// This is the inverse of [`pack`](crate::pack), which
+    /// `Client::send_message` uses: the message type lives in the low word of
+    /// `wparam` and `var1` in its high word, while `var2` and `var3` are the
+    /// low and high words of `lparam`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, BroadcastMessageType};
+    ///
+    /// let (message_type, var1, var2, var3) = BroadcastMessage::decode(0x0001_0009, 0x0041)?;
+    /// assert!(matches!(message_type, BroadcastMessageType::PitCommand));
+    /// assert_eq!((var1, var2, var3), (1, 0x41, 0));
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn decode(wparam: usize, lparam: isize) -> Result<(BroadcastMessageType, u16, u16, u16)> {
+        let message_type = BroadcastMessageType::try_from((wparam & 0xFFFF) as u32)?;
+
+        let var1 = (wparam >> 16) as u16;
+        let var2 = lparam as u16;
+        let var3 = (lparam >> 16) as u16;
+
+        Ok((message_type, var1, var2, var3))
+    }
+}
+
+impl fmt::Display for BroadcastMessage {
+    /// Operator-facing description of the message, e.g.
+    /// `Switch to car #064, group 1, camera 1`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BroadcastMessage::CameraSwitchPosition(position, group, camera) => write!(
+                f,
+                "Switch to position {position}, group {group}, camera {camera}"
+            ),
+            BroadcastMessage::CameraSwitchNumber(car_number, group, camera) => write!(
+                f,
+                "Switch to car #{car_number}, group {group}, camera {camera}"
+            ),
+            BroadcastMessage::CameraSwitchFocus(focus, group, camera) => {
+                write!(f, "Focus on {focus}, group {group}, camera {camera}")
+            }
+            BroadcastMessage::CameraSetState(state) => write!(f, "Set camera state: {state}"),
+            BroadcastMessage::ReplaySetPlaySpeed(speed) => write!(f, "Replay speed: {speed}"),
+            BroadcastMessage::ReplaySetPlayPosition(mode, frame_number) => {
+                write!(f, "Replay to frame {frame_number} from {mode}")
+            }
+            BroadcastMessage::ReplaySearch(mode) => write!(f, "{mode}"),
+            BroadcastMessage::ReplaySetState => f.write_str("Erase replay tape"),
+            BroadcastMessage::ReloadAllTextures => f.write_str("Reload all textures"),
+            BroadcastMessage::ReloadTextures(car_index) => {
+                write!(f, "Reload textures for car index {car_index}")
+            }
+            BroadcastMessage::ChatCommand(mode) => write!(f, "{mode}"),
+            BroadcastMessage::ChatCommandMacro(macro_number) => {
+                write!(f, "Chat macro {macro_number}")
+            }
+            BroadcastMessage::PitCommand(mode) => write!(f, "{mode}"),
+            BroadcastMessage::TelemetryCommand(mode) => write!(f, "{mode}"),
+            BroadcastMessage::FFBCommand(force) => write!(f, "Set FFB max force: {force}"),
+            BroadcastMessage::ReplaySearchSessionTime(session_number, session_time_ms) => {
+                write!(f, "Replay to session {session_number} at ")?;
+                write_session_time(f, *session_time_ms)
+            }
+            BroadcastMessage::VideoCapture(mode) => write!(f, "{mode}"),
+        }
+    }
+}
+
+/// Writes milliseconds as `M:SS.mmm`, or `H:MM:SS.mmm` past an hour.
+fn write_session_time(f: &mut fmt::Formatter<'_>, ms: u32) -> fmt::Result {
+    let (hours, minutes) = (ms / 3_600_000, ms / 60_000 % 60);
+    let (seconds, millis) = (ms / 1_000 % 60, ms % 1_000);
+    if hours > 0 {
+        write!(f, "{hours}:{minutes:02}:{seconds:02}.{millis:03}")
+    } else {
+        write!(f, "{minutes}:{seconds:02}.{millis:03}")
+    }
+}
+
+/// Parses `[[H:]M:]S[.fff]` into a duration; the inverse of
+/// [`write_session_time`].
+fn parse_session_time(text: &str) -> Result<Duration> {
+    let invalid = |reason: String| {
+        BroadcastError::invalid_parameter("session time", format!("{text:?}: {reason}"))
+    };
+    let number = |field: &str, name: &str| {
+        if field.is_empty() || !field.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid(format!("{name} must be a whole number")));
+        }
+        field
+            .parse::<u64>()
+            .map_err(|_| invalid(format!("{name} is too large")))
+    };
+
+    let mut fields: Vec<&str> = text.trim().split(':').collect();
+    if fields.len() > 3 {
+        return Err(invalid(
+            "expected at most hours, minutes and seconds".into(),
+        ));
+    }
+
+    let seconds_field = fields.pop().unwrap_or_default();
+    let (whole, fraction) = seconds_field.split_once('.').unwrap_or((seconds_field, ""));
+    let seconds = number(whole, "seconds")?;
+    let millis = match fraction {
+        "" if seconds_field.ends_with('.') => {
+            return Err(invalid("missing digits after the decimal point".into()));
+        }
+        "" => 0,
+        fraction if !fraction.bytes().all(|b| b.is_ascii_digit()) => {
+            return Err(invalid("the fraction of a second must be digits".into()));
+        }
+        fraction => {
+            let digits = &fraction[..fraction.len().min(3)];
+            number(digits, "the fraction of a second")? * 10u64.pow(3 - digits.len() as u32)
+        }
+    };
+
+    let (hours, minutes) = match fields[..] {
+        [] => (0, 0),
+        [minutes] => (0, number(minutes, "minutes")?),
+        [hours, minutes] => (number(hours, "hours")?, number(minutes, "minutes")?),
+        _ => unreachable!("at most two fields precede the seconds"),
+    };
+    if !fields.is_empty() && seconds >= 60 {
+        return Err(invalid(format!("seconds must be below 60, got {seconds}")));
+    }
+    if fields.len() == 2 && minutes >= 60 {
+        return Err(invalid(format!("minutes must be below 60, got {minutes}")));
+    }
+
+    hours
+        .checked_mul(3_600)
+        .and_then(|total| total.checked_add(minutes.checked_mul(60)?))
+        .and_then(|total| total.checked_add(seconds))
+        .and_then(|total| total.checked_mul(1_000))
+        .and_then(|total| total.checked_add(millis))
+        .map(Duration::from_millis)
+        .ok_or_else(|| invalid("time is too large".into()))
+}
+
+impl From<PitCommandMode> for BroadcastMessage {
+    fn from(mode: PitCommandMode) -> Self {
+        BroadcastMessage::PitCommand(mode)
+    }
+}
+
+impl From<ChatCommandMode> for BroadcastMessage {
+    fn from(mode: ChatCommandMode) -> Self {
+        BroadcastMessage::ChatCommand(mode)
+    }
+}
+
+impl From<TelemetryCommandMode> for BroadcastMessage {
+    fn from(mode: TelemetryCommandMode) -> Self {
+        BroadcastMessage::TelemetryCommand(mode)
+    }
+}
+
+impl From<VideoCaptureMode> for BroadcastMessage {
+    fn from(mode: VideoCaptureMode) -> Self {
+        BroadcastMessage::VideoCapture(mode)
+    }
+}
+
+impl From<ReplaySearchMode> for BroadcastMessage {
+    fn from(mode: ReplaySearchMode) -> Self {
+        BroadcastMessage::ReplaySearch(mode)
+    }
+}
+
+impl BroadcastMessage {
+    /// Packs the message into its type and parameter words.
+    ///
+    /// This is what a `Client` sends, and it never
+    /// allocates or needs `std`, so a front-end without a client can encode
+    /// messages and ship the words to one. [`pack`](crate::pack) turns them
+    /// into the `WPARAM`/`LPARAM` pair and [`BroadcastMessage::decode`] reads
+    /// them back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, BroadcastMessageType, PitCommandMode};
+    ///
+    /// let message = BroadcastMessage::PitCommand(PitCommandMode::Fuel(65));
+    /// assert_eq!(
+    ///     message.encode(),
+    ///     (BroadcastMessageType::PitCommand, 2, 65, 0)
+    /// );
+    /// ```
+    pub fn encode(&self) -> (BroadcastMessageType, u16, u16, u16) {
+        match *self {
+            BroadcastMessage::CameraSwitchPosition(position, group, camera) => (
+                BroadcastMessageType::CameraSwitchPosition,
+                position.into(),
+                group.into(),
+                camera.into(),
+            ),
+            BroadcastMessage::CameraSwitchNumber(car_number, group, camera) => (
+                BroadcastMessageType::CameraSwitchNumber,
+                car_number.encode(),
+                group.into(),
+                camera.into(),
+            ),
+            BroadcastMessage::CameraSwitchFocus(focus, group, camera) => (
+                BroadcastMessageType::CameraSwitchPosition,
+                focus.encode(),
+                group.into(),
+                camera.into(),
+            ),
+            BroadcastMessage::CameraSetState(camera_state) => (
+                BroadcastMessageType::CameraSetState,
+                camera_state.bits() as u16,
+                0,
+                0,
+            ),
+            BroadcastMessage::ReplaySetPlaySpeed(speed) => {
+                let (var1, var2) = speed.encode();
+                (BroadcastMessageType::ReplaySetPlaySpeed, var1, var2, 0)
+            }
+            BroadcastMessage::ReplaySetPlayPosition(mode, frame_number) => {
+                let (low, high) = split_words(frame_number);
+                (
+                    BroadcastMessageType::ReplaySetPlayPosition,
+                    mode.into(),
+                    low,
+                    high,
+                )
+            }
+            BroadcastMessage::ReplaySearch(mode) => {
+                (BroadcastMessageType::ReplaySearch, mode.into(), 0, 0)
+            }
+            BroadcastMessage::ReplaySetState => (BroadcastMessageType::ReplaySetState, 0, 0, 0),
+            BroadcastMessage::ReloadAllTextures => (
+                BroadcastMessageType::ReloadTextures,
+                ReloadTexturesMode::All.into(),
+                0,
+                0,
+            ),
+            BroadcastMessage::ReloadTextures(car_index) => (
+                BroadcastMessageType::ReloadTextures,
+                ReloadTexturesMode::CarIndex.into(),
+                car_index.into(),
+                0,
+            ),
+            BroadcastMessage::ChatCommand(mode) => {
+                (BroadcastMessageType::ChatCommand, mode.into(), 0, 0)
+            }
+            BroadcastMessage::ChatCommandMacro(macro_number) => (
+                BroadcastMessageType::ChatCommand,
+                ChatCommandMode::Macro.into(),
+                macro_number.into(),
+                0,
+            ),
+            BroadcastMessage::PitCommand(pit_command_mode) => {
+                let (var1, var2) = pit_command_mode.encode();
+                (BroadcastMessageType::PitCommand, var1, var2, 0)
+            }
+            BroadcastMessage::TelemetryCommand(mode) => {
+                (BroadcastMessageType::TelemetryCommand, mode.into(), 0, 0)
+            }
+            BroadcastMessage::FFBCommand(force) => {
+                let (low, high) = split_words(force.fixed_point());
+                (BroadcastMessageType::FFBCommand, 0, low, high)
+            }
+            BroadcastMessage::ReplaySearchSessionTime(session_number, session_time_ms) => {
+                let (low, high) = split_words(session_time_ms);
+                (
+                    BroadcastMessageType::ReplaySearchSessionTime,
+                    session_number.into(),
+                    low,
+                    high,
+                )
+            }
+            BroadcastMessage::VideoCapture(mode) => {
+                (BroadcastMessageType::VideoCapture, mode.into(), 0, 0)
+            }
+        }
+    }
+
+    /// The numeric message type and parameter words, as the sim receives
+    /// them, for showing or logging what a send puts on the wire.
+    ///
+    /// Like [`BroadcastMessage::encode`], with the type as the SDK's `u32`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, PitCommandMode};
+    ///
+    /// let message = BroadcastMessage::PitCommand(PitCommandMode::Fuel(65));
+    /// assert_eq!(message.packed(), (9, 2, 65, 0));
+    /// assert_eq!(message.packed_winparams(), (0x0002_0009, 0x0041));
+    /// ```
+    pub fn packed(&self) -> (u32, u16, u16, u16) {
+        let (message_type, var1, var2, var3) = self.encode();
+        (message_type.as_u32(), var1, var2, var3)
+    }
+
+    /// The `(WPARAM, LPARAM)` pair a `Client` sends for
+    /// this message; see [`pack`](crate::pack).
+    pub fn packed_winparams(&self) -> (usize, isize) {
+        let (message_type, var1, var2, var3) = self.encode();
+        pack(message_type as u16, var1, var2, var3)
+    }
+}
+
+/// A message as a fixed-size, C-compatible struct, for passing encoded
+/// messages between processes without a serialization format.
+///
+/// The fields are [`BroadcastMessage::encode`]'s words, widened to the
+/// sim's `u32` message type and padded to 12 bytes with no implicit
+/// padding, so the struct can be copied through shared memory or a pipe as
+/// raw bytes. Both ends must share a byte order; [`WireMessage::to_bytes`]
+/// and [`WireMessage::from_bytes`] use the native one.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::{BroadcastMessage, PitCommandMode, WireMessage};
+///
+/// let message = BroadcastMessage::PitCommand(PitCommandMode::Fuel(65));
+/// let bytes = message.to_wire().to_bytes();
+///
+/// assert_eq!(
+///     BroadcastMessage::from_wire(WireMessage::from_bytes(bytes))?,
+///     message
+/// );
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct WireMessage {
+    /// The [`BroadcastMessageType`] discriminant.
+    pub type_: u32,
+    /// The high word of `WPARAM`.
+    pub var1: u16,
+    /// The low word of `LPARAM`.
+    pub var2: u16,
+    /// The high word of `LPARAM`.
+    pub var3: u16,
+    _pad: u16,
+}
+
+impl WireMessage {
+    /// Size of the struct, and of [`WireMessage::to_bytes`], in bytes.
+    pub const SIZE: usize = core::mem::size_of::<Self>();
+
+    /// A wire message from raw words, e.g. read from another process.
+    pub const fn new(type_: u32, var1: u16, var2: u16, var3: u16) -> Self {
+        WireMessage {
+            type_,
+            var1,
+            var2,
+            var3,
+            _pad: 0,
+        }
+    }
+
+    /// The struct's bytes in native byte order.
+    pub fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[0..4].copy_from_slice(&self.type_.to_ne_bytes());
+        bytes[4..6].copy_from_slice(&self.var1.to_ne_bytes());
+        bytes[6..8].copy_from_slice(&self.var2.to_ne_bytes());
+        bytes[8..10].copy_from_slice(&self.var3.to_ne_bytes());
+        bytes
+    }
+
+    /// Read a struct written by [`WireMessage::to_bytes`]. The padding is
+    /// ignored.
+    pub fn from_bytes(bytes: [u8; Self::SIZE]) -> Self {
+        let word = |at: usize| u16::from_ne_bytes([bytes[at], bytes[at + 1]]);
+        Self::new(
+            u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            word(4),
+            word(6),
+            word(8),
+        )
+    }
+}
+
+impl BroadcastMessage {
+    /// Encode into a [`WireMessage`].
+    pub fn to_wire(&self) -> WireMessage {
+        let (message_type, var1, var2, var3) = self.encode();
+        WireMessage::new(message_type.as_u32(), var1, var2, var3)
+    }
+
+    /// Rebuild a message from a [`WireMessage`], the inverse of
+    /// [`BroadcastMessage::to_wire`].
+    ///
+    /// Several messages share an encoding, so the result is the canonical
+    /// one: focus on a plain race position comes back as
+    /// [`BroadcastMessage::CameraSwitchPosition`], and a chat macro as
+    /// [`BroadcastMessage::ChatCommandMacro`]. Either way it encodes to the
+    /// same words.
+    ///
+    /// Returns [`BroadcastError::UnknownMessageType`] for an unknown type and
+    /// [`BroadcastError::InvalidParameter`] for words no message encodes to.
+    pub fn from_wire(wire: WireMessage) -> Result<Self> {
+        let WireMessage {
+            type_,
+            var1,
+            var2,
+            var3,
+            ..
+        } = wire;
+        let byte = |parameter: &str, word: u16| checked_param(parameter, word.into(), 0..=u8::MAX);
+        let long = u32::from(var2) | u32::from(var3) << 16;
+
+        Ok(match BroadcastMessageType::try_from(type_)? {
+            BroadcastMessageType::CameraSwitchPosition => {
+                let (group, camera) = (byte("camera group", var2)?, byte("camera", var3)?);
+                match CameraFocusMode::decode(var1) {
+                    CameraFocusMode::Position(position) => {
+                        BroadcastMessage::CameraSwitchPosition(position, group, camera)
+                    }
+                    mode => BroadcastMessage::CameraSwitchFocus(mode, group, camera),
+                }
+            }
+            BroadcastMessageType::CameraSwitchNumber => BroadcastMessage::CameraSwitchNumber(
+                CarNumber::from_padded(var1)?,
+                byte("camera group", var2)?,
+                byte("camera", var3)?,
+            ),
+            BroadcastMessageType::CameraSetState => {
+                BroadcastMessage::CameraSetState(CameraState::from_bits_retain(var1.into()))
+            }
+            BroadcastMessageType::ReplaySetPlaySpeed => {
+                BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::decode(var1, var2)?)
+            }
+            BroadcastMessageType::ReplaySetPlayPosition => {
+                BroadcastMessage::ReplaySetPlayPosition(var1.try_into()?, long)
+            }
+            BroadcastMessageType::ReplaySearch => BroadcastMessage::ReplaySearch(var1.try_into()?),
+            BroadcastMessageType::ReplaySetState => {
+                // `irsdk_ReplayState_EraseTape` is the only mode.
+                checked_param("replay state mode", var1.into(), 0..=0u16)?;
+                BroadcastMessage::ReplaySetState
+            }
+            BroadcastMessageType::ReloadTextures => match ReloadTexturesMode::try_from(var1)? {
+                ReloadTexturesMode::All => BroadcastMessage::ReloadAllTextures,
+                ReloadTexturesMode::CarIndex => {
+                    BroadcastMessage::ReloadTextures(byte("car index", var2)?)
+                }
+            },
+            BroadcastMessageType::ChatCommand => match ChatCommandMode::try_from(var1)? {
+                ChatCommandMode::Macro => {
+                    BroadcastMessage::ChatCommandMacro(byte("chat macro", var2)?)
+                }
+                mode => BroadcastMessage::ChatCommand(mode),
+            },
+            BroadcastMessageType::PitCommand => {
+                BroadcastMessage::PitCommand(PitCommandMode::decode(var1, var2)?)
+            }
+            BroadcastMessageType::TelemetryCommand => {
+                BroadcastMessage::TelemetryCommand(var1.try_into()?)
+            }
+            BroadcastMessageType::FFBCommand => {
+                BroadcastMessage::FFBCommand(FfbForce::from_fixed_point(long))
+            }
+            BroadcastMessageType::ReplaySearchSessionTime => {
+                BroadcastMessage::ReplaySearchSessionTime(byte("session number", var1)?, long)
+            }
+            BroadcastMessageType::VideoCapture => BroadcastMessage::VideoCapture(var1.try_into()?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{pack, pad_car_number};
+
+    #[test]
+    fn test_replay_seek_session_time() {
+        let message =
+            BroadcastMessage::replay_seek_session_time(1, Duration::from_secs(90)).unwrap();
+        assert_eq!(
+            message,
+            BroadcastMessage::ReplaySearchSessionTime(1, 90_000)
+        );
+
+        let (_, var1, var2, var3) = message.encode();
+        assert_eq!((var1, var2, var3), (1, 0x5F90, 0x0001));
+
+        assert!(matches!(
+            BroadcastMessage::replay_seek_session_time(
+                1,
+                Duration::from_millis(u32::MAX as u64 + 1)
+            ),
+            Err(BroadcastError::InvalidParameter { .. })
+        ));
+    }
+
+    #[test]
+    fn test_replay_seek_session_time_str() {
+        let parse = |time| match BroadcastMessage::replay_seek_session_time_str(1, time) {
+            Ok(BroadcastMessage::ReplaySearchSessionTime(1, millis)) => Ok(millis),
+            Ok(other) => panic!("unexpected message {other:?}"),
+            Err(error) => Err(error),
+        };
+
+        assert_eq!(parse("83.4").unwrap(), 83_400);
+        assert_eq!(parse("45").unwrap(), 45_000);
+        assert_eq!(parse("1:23.456").unwrap(), 83_456);
+        assert_eq!(parse("01:02:03").unwrap(), 3_723_000);
+        assert_eq!(parse("1:02:03.004").unwrap(), 3_723_004);
+        assert_eq!(parse(" 0:05.1239 ").unwrap(), 5_123);
+        assert_eq!(parse("7.00000000000000000000001").unwrap(), 7_000);
+
+        for malformed in [
+            "", "1:", ":30", "1:2:3:4", "1:60", "1:60:00", "1.5:00", "12.", "-3", "1:2x",
+        ] {
+            let error = parse(malformed).unwrap_err();
+            assert!(
+                matches!(&error, BroadcastError::InvalidParameter { reason, .. } if reason.contains(&format!("{malformed:?}"))),
+                "{malformed:?} gave {error:?}"
+            );
+        }
+        assert!(parse("1193:02:47.296").is_err());
+    }
+
+    #[test]
+    fn test_replay_speed_constructors() {
+        let words = |message: BroadcastMessage| {
+            let (message_type, var1, var2, var3) = message.encode();
+            assert!(matches!(
+                message_type,
+                BroadcastMessageType::ReplaySetPlaySpeed
+            ));
+            (var1, var2, var3)
+        };
+
+        assert_eq!(words(BroadcastMessage::replay_play()), (1, 0, 0));
+        assert_eq!(words(BroadcastMessage::replay_pause()), (0, 0, 0));
+        assert_eq!(
+            words(BroadcastMessage::replay_slow_motion(2).unwrap()),
+            (2, 1, 0)
+        );
+        assert_eq!(
+            words(BroadcastMessage::replay_fast_forward(8).unwrap()),
+            (8, 0, 0)
+        );
+
+        for invalid in [
+            BroadcastMessage::replay_slow_motion(0),
+            BroadcastMessage::replay_slow_motion(ReplaySpeed::MAX + 1),
+            BroadcastMessage::replay_slow_motion(u8::MAX),
+            BroadcastMessage::replay_fast_forward(ReplaySpeed::MAX + 1),
+        ] {
+            assert!(matches!(
+                invalid,
+                Err(BroadcastError::InvalidParameter { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_replay_seek_duration() {
+        let thirty_seconds = Duration::from_secs(30);
+        assert_eq!(
+            BroadcastMessage::replay_seek_duration(ReplayPositionMode::Begin, thirty_seconds, 60)
+                .unwrap(),
+            BroadcastMessage::ReplaySetPlayPosition(ReplayPositionMode::Begin, 1800)
+        );
+
+        let from_end =
+            BroadcastMessage::replay_seek_duration(ReplayPositionMode::End, thirty_seconds, 60)
+                .unwrap();
+        assert_eq!(from_end, BroadcastMessage::replay_seek_from_end(1800));
+        let (_, _, var2, var3) = from_end.encode();
+        assert_eq!((u32::from(var3) << 16 | u32::from(var2)) as i32, -1800);
+
+        // Partial frames are truncated.
+        let just_short = Duration::from_secs(1) - Duration::from_millis(1);
+        assert_eq!(
+            BroadcastMessage::replay_seek_duration(ReplayPositionMode::Current, just_short, 60)
+                .unwrap(),
+            BroadcastMessage::ReplaySetPlayPosition(ReplayPositionMode::Current, 59)
+        );
+    }
+
+    #[test]
+    fn test_replay_seek_duration_rejects_invalid() {
+        for (offset, fps) in [
+            (Duration::from_secs(1), 0),
+            (Duration::from_secs(i32::MAX as u64 / 60 + 1), 60),
+        ] {
+            assert!(matches!(
+                BroadcastMessage::replay_seek_duration(ReplayPositionMode::Begin, offset, fps),
+                Err(BroadcastError::InvalidParameter { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_replay_to_lap_encoding() {
+        let encoded: Vec<_> = BroadcastMessage::replay_to_lap("064", 2)
+            .unwrap()
+            .iter()
+            .map(|message| message.encode())
+            .collect();
+
+        let search =
+            |mode: ReplaySearchMode| (BroadcastMessageType::ReplaySearch, mode as u16, 0, 0);
+        assert_eq!(
+            encoded,
+            [
+                (
+                    BroadcastMessageType::CameraSwitchNumber,
+                    pad_car_number("064"),
+                    0,
+                    0
+                ),
+                (BroadcastMessageType::ReplaySetPlaySpeed, 0, 0, 0),
+                search(ReplaySearchMode::ToStart),
+                search(ReplaySearchMode::NextLap),
+                search(ReplaySearchMode::NextLap),
+            ]
+        );
+
+        assert_eq!(BroadcastMessage::replay_to_lap("7", 0).unwrap().len(), 3);
+        assert_eq!(
+            BroadcastMessage::replay_to_lap("7", BroadcastMessage::MAX_REPLAY_LAP)
+                .unwrap()
+                .len(),
+            3 + usize::from(BroadcastMessage::MAX_REPLAY_LAP)
+        );
+        assert!(matches!(
+            BroadcastMessage::replay_to_lap("7", BroadcastMessage::MAX_REPLAY_LAP + 1),
+            Err(BroadcastError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            BroadcastMessage::replay_to_lap("7a", 1),
+            Err(BroadcastError::InvalidCarNumber { .. })
+        ));
+    }
+
+    #[test]
+    fn test_camera_set_flag_preserves_other_flags() {
+        let current = CameraState::IS_SCENIC_ACTIVE | CameraState::CAM_TOOL_ACTIVE;
+
+        assert_eq!(
+            BroadcastMessage::camera_set_flag(CameraState::UI_HIDDEN, true, current),
+            BroadcastMessage::CameraSetState(current | CameraState::UI_HIDDEN)
+        );
+        assert_eq!(
+            BroadcastMessage::camera_set_flag(CameraState::CAM_TOOL_ACTIVE, false, current),
+            BroadcastMessage::CameraSetState(CameraState::IS_SCENIC_ACTIVE)
+        );
+        assert_eq!(
+            BroadcastMessage::camera_set_flag(CameraState::IS_SCENIC_ACTIVE, true, current),
+            BroadcastMessage::CameraSetState(current)
+        );
+    }
+
+    #[test]
+    fn test_clear_all_pit_service_sequence() {
+        let words: Vec<_> = BroadcastMessage::clear_all_pit_service()
+            .into_iter()
+            .map(|message| {
+                let (message_type, var1, var2, var3) = message.encode();
+                assert_eq!(message_type, BroadcastMessageType::PitCommand);
+                (var1, var2, var3)
+            })
+            .collect();
+
+        // Clear, ClearTires, ClearTearoff, ClearFastRepair, ClearFuel
+        assert_eq!(
+            words,
+            [(0, 0, 0), (7, 0, 0), (9, 0, 0), (10, 0, 0), (11, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn test_chat_macro_range() {
+        assert_eq!(
+            BroadcastMessage::chat_macro(1).unwrap(),
+            BroadcastMessage::ChatCommandMacro(1)
+        );
+        assert_eq!(
+            BroadcastMessage::chat_macro(15).unwrap(),
+            BroadcastMessage::ChatCommandMacro(15)
+        );
+        for macro_number in [0, 16, u8::MAX] {
+            assert!(matches!(
+                BroadcastMessage::chat_macro(macro_number),
+                Err(BroadcastError::InvalidParameter { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_chat_reply_has_no_target() {
+        let reply = BroadcastMessage::ChatCommand(ChatCommandMode::Reply);
+        assert_eq!(reply.encode(), (BroadcastMessageType::ChatCommand, 2, 0, 0));
+
+        // A sub-command word on a reply carries nothing and is dropped.
+        assert_eq!(
+            BroadcastMessage::from_wire(WireMessage::new(8, 2, 12, 0)).unwrap(),
+            reply
+        );
+    }
+
+    #[test]
+    fn test_mode_conversions() {
+        assert_eq!(
+            BroadcastMessage::from(PitCommandMode::Tearoff),
+            BroadcastMessage::PitCommand(PitCommandMode::Tearoff)
+        );
+        assert_eq!(
+            BroadcastMessage::from(ChatCommandMode::Reply),
+            BroadcastMessage::ChatCommand(ChatCommandMode::Reply)
+        );
+        assert_eq!(
+            BroadcastMessage::from(TelemetryCommandMode::Restart),
+            BroadcastMessage::TelemetryCommand(TelemetryCommandMode::Restart)
+        );
+        assert_eq!(
+            BroadcastMessage::from(VideoCaptureMode::ScreenShot),
+            BroadcastMessage::VideoCapture(VideoCaptureMode::ScreenShot)
+        );
+        assert_eq!(
+            BroadcastMessage::from(ReplaySearchMode::NextIncident),
+            BroadcastMessage::ReplaySearch(ReplaySearchMode::NextIncident)
+        );
+    }
+
+    #[test]
+    fn test_ffb_command_fixed_point() {
+        let (message_type, var1, var2, var3) =
+            BroadcastMessage::FFBCommand(FfbForce::from_newton_meters(1.0)).encode();
+        assert!(matches!(message_type, BroadcastMessageType::FFBCommand));
+        assert_eq!(var1, 0);
+        assert_eq!((var2, var3), (0x0000, 0x0001));
+
+        let lparam = var2 as u32 | ((var3 as u32) << 16);
+        assert_eq!(lparam, 0x0001_0000);
+
+        let (_, _, var2, var3) =
+            BroadcastMessage::FFBCommand(FfbForce::from_newton_meters(2.5)).encode();
+        assert_eq!(var2 as u32 | ((var3 as u32) << 16), 0x0002_8000);
+    }
+
+    #[test]
+    fn test_ffb_max_force_for_wheel() {
+        // 10 Nm as 16.16 fixed point is 0x000a_0000.
+        assert_eq!(
+            BroadcastMessage::ffb_max_force_for_wheel(50, 20.0).encode(),
+            (BroadcastMessageType::FFBCommand, 0, 0x0000, 0x000a)
+        );
+        assert_eq!(
+            BroadcastMessage::ffb_max_force_for_wheel(100, 20.0),
+            BroadcastMessage::FFBCommand(FfbForce::from_newton_meters(20.0))
+        );
+        assert_eq!(
+            BroadcastMessage::ffb_max_force_for_wheel(u8::MAX, 20.0),
+            BroadcastMessage::ffb_max_force_for_wheel(100, 20.0)
+        );
+        // Maximum gain, the strongest setting.
+        assert_eq!(
+            BroadcastMessage::ffb_max_force_for_wheel(0, 20.0).encode(),
+            (BroadcastMessageType::FFBCommand, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_reload_all_textures() {
+        let (message_type, var1, var2, var3) = BroadcastMessage::ReloadAllTextures.encode();
+        assert!(matches!(message_type, BroadcastMessageType::ReloadTextures));
+        assert_eq!((var1, var2, var3), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_reload_textures_by_car_index() {
+        let (message_type, var1, var2, var3) = BroadcastMessage::ReloadTextures(12).encode();
+        assert!(matches!(message_type, BroadcastMessageType::ReloadTextures));
+        assert_eq!((var1, var2, var3), (1, 12, 0));
+    }
+
+    #[test]
+    fn test_reload_textures_range() {
+        let encoded: Vec<_> = BroadcastMessage::reload_textures_range(5..8)
+            .iter()
+            .map(|message| {
+                let (message_type, var1, var2, var3) = message.encode();
+                assert!(matches!(message_type, BroadcastMessageType::ReloadTextures));
+                (var1, var2, var3)
+            })
+            .collect();
+        assert_eq!(encoded, [(1, 5, 0), (1, 6, 0), (1, 7, 0)]);
+        assert!(BroadcastMessage::reload_textures_range([]).is_empty());
+    }
+
+    #[test]
+    fn test_reload_textures_by_number() {
+        let car_index_of = |number: &str| match number {
+            "007" => Some(4),
+            "7" => Some(9),
+            _ => None,
+        };
+
+        let message = BroadcastMessage::reload_textures_by_number("007", car_index_of).unwrap();
+        assert_eq!(message, BroadcastMessage::ReloadTextures(4));
+        let (_, var1, var2, var3) = message.encode();
+        assert_eq!((var1, var2, var3), (1, 4, 0));
+
+        assert_eq!(
+            BroadcastMessage::reload_textures_by_number("7", car_index_of).unwrap(),
+            BroadcastMessage::ReloadTextures(9)
+        );
+        for car_number in ["07", "1a", ""] {
+            assert!(matches!(
+                BroadcastMessage::reload_textures_by_number(car_number, car_index_of),
+                Err(BroadcastError::InvalidCarNumber { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_replay_search_session_time_split() {
+        let (message_type, var1, var2, var3) =
+            BroadcastMessage::ReplaySearchSessionTime(2, 15_000).encode();
+        assert!(matches!(
+            message_type,
+            BroadcastMessageType::ReplaySearchSessionTime
+        ));
+        assert_eq!((var1, var2, var3), (2, 15_000, 0));
+
+        // One hour: 3_600_000 ms == 0x0036_EE80
+        let (_, var1, var2, var3) =
+            BroadcastMessage::ReplaySearchSessionTime(2, 3_600_000).encode();
+        assert_eq!((var1, var2, var3), (2, 0xEE80, 0x0036));
+        assert_eq!(var2 as u32 | ((var3 as u32) << 16), 3_600_000);
+    }
+
+    #[test]
+    fn test_replay_set_play_position_split() {
+        // 200_000 frames == 0x0003_0D40
+        let (message_type, var1, var2, var3) =
+            BroadcastMessage::ReplaySetPlayPosition(ReplayPositionMode::Current, 200_000).encode();
+        assert!(matches!(
+            message_type,
+            BroadcastMessageType::ReplaySetPlayPosition
+        ));
+        assert_eq!((var1, var2, var3), (1, 0x0D40, 0x0003));
+    }
+
+    #[test]
+    fn test_replay_seek_from_end() {
+        let (message_type, var1, var2, var3) =
+            BroadcastMessage::replay_seek_from_end(3600).encode();
+        assert!(matches!(
+            message_type,
+            BroadcastMessageType::ReplaySetPlayPosition
+        ));
+        // -3600 == 0xFFFF_F1F0
+        assert_eq!((var1, var2, var3), (2, 0xF1F0, 0xFFFF));
+
+        let (_, _, var2, var3) = BroadcastMessage::replay_seek_from_end(0).encode();
+        assert_eq!((var2, var3), (0, 0));
+
+        let (_, _, var2, var3) = BroadcastMessage::replay_seek_from_end(u32::MAX).encode();
+        assert_eq!((var2, var3), (0x0001, 0x8000));
+    }
+
+    #[test]
+    fn test_camera_switch_number_from_runtime_string() {
+        let car_number = format!("{:03}", 64);
+        let message = BroadcastMessage::camera_switch_number(car_number, 1, 2);
+
+        let (message_type, var1, var2, var3) = message.encode();
+        assert!(matches!(
+            message_type,
+            BroadcastMessageType::CameraSwitchNumber
+        ));
+        assert_eq!((var1, var2, var3), (3064, 1, 2));
+    }
+
+    #[test]
+    fn test_camera_switch_focus_sentinels() {
+        let cases = [
+            (CameraFocusMode::Incident, 0xFFFD),
+            (CameraFocusMode::Leader, 0xFFFE),
+            (CameraFocusMode::Exciting, 0xFFFF),
+            (CameraFocusMode::Position(5), 5),
+            (CameraFocusMode::Raw(-1), 0xFFFF),
+        ];
+
+        for (focus, expected) in cases {
+            let (message_type, var1, var2, var3) =
+                BroadcastMessage::CameraSwitchFocus(focus, 3, 1).encode();
+            assert!(matches!(
+                message_type,
+                BroadcastMessageType::CameraSwitchPosition
+            ));
+            assert_eq!((var1, var2, var3), (expected, 3, 1));
+        }
+    }
+
+    #[test]
+    fn test_camera_switch_raw_position() {
+        let cases = [
+            (CameraFocusMode::Raw(0), 0),
+            (CameraFocusMode::Raw(12), 12),
+            (CameraFocusMode::Raw(-1), CameraFocusMode::Exciting.encode()),
+            (CameraFocusMode::Raw(i16::MIN), 0x8000),
+        ];
+
+        for (focus, expected) in cases {
+            assert_eq!(focus.encode(), expected);
+            let (_, var1, _, _) = BroadcastMessage::CameraSwitchFocus(focus, 1, 0).encode();
+            assert_eq!(var1, expected);
+        }
+        assert_eq!(
+            CameraFocusMode::Position(12).encode(),
+            CameraFocusMode::Raw(12).encode()
+        );
+    }
+
+    #[test]
+    fn test_debug_formatting() {
+        let messages = [
+            BroadcastMessage::camera_switch_number("064", 1, 1),
+            BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Leader, 0, 0),
+            BroadcastMessage::CameraSetState(CameraState::UI_HIDDEN),
+            BroadcastMessage::ReplaySetPlayPosition(ReplayPositionMode::End, 0),
+            BroadcastMessage::ReplaySearch(ReplaySearchMode::NextIncident),
+            BroadcastMessage::ChatCommand(ChatCommandMode::Begin),
+            BroadcastMessage::PitCommand(PitCommandMode::Fuel(65)),
+            BroadcastMessage::TelemetryCommand(TelemetryCommandMode::Restart),
+            BroadcastMessage::VideoCapture(VideoCaptureMode::ScreenShot),
+        ];
+
+        for message in messages {
+            assert!(!format!("{:?}", message).is_empty());
+        }
+
+        let formatted = format!(
+            "{:?}",
+            BroadcastMessage::PitCommand(PitCommandMode::Fuel(65))
+        );
+        assert_eq!(formatted, "PitCommand(Fuel(65))");
+        assert_eq!(
+            format!("{:?}", BroadcastMessageType::ReplaySearch),
+            "ReplaySearch"
+        );
+    }
+
+    #[test]
+    fn test_message_equality() {
+        let fuel = BroadcastMessage::PitCommand(PitCommandMode::Fuel(65));
+        assert_eq!(fuel.clone(), fuel);
+        assert_ne!(fuel, BroadcastMessage::PitCommand(PitCommandMode::Fuel(66)));
+
+        let camera = BroadcastMessage::camera_switch_number("064", 1, 1);
+        assert_eq!(camera, BroadcastMessage::camera_switch_number("064", 1, 1));
+        assert_ne!(camera, BroadcastMessage::camera_switch_number("64", 1, 1));
+
+        assert_eq!(
+            CameraState::UI_HIDDEN | CameraState::IS_SCENIC_ACTIVE,
+            CameraState::IS_SCENIC_ACTIVE | CameraState::UI_HIDDEN
+        );
+        assert_ne!(ReplaySearchMode::NextLap, ReplaySearchMode::PreviousLap);
+    }
+
+    fn pack_message(message: BroadcastMessage) -> (usize, isize) {
+        let (broadcast_type, var1, var2, var3) = message.encode();
+        pack(broadcast_type as u16, var1, var2, var3)
+    }
+
+    #[test]
+    fn test_decode_round_trip() {
+        let messages = [
+            BroadcastMessage::CameraSwitchPosition(3, 2, 1),
+            BroadcastMessage::camera_switch_number("064", 1, 1),
+            BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Incident, 4, 0),
+            BroadcastMessage::ReplaySetPlayPosition(ReplayPositionMode::Begin, 200_000),
+            BroadcastMessage::ReloadTextures(7),
+            BroadcastMessage::PitCommand(PitCommandMode::Fuel(65)),
+            BroadcastMessage::FFBCommand(FfbForce::from_newton_meters(1.0)),
+            BroadcastMessage::ReplaySearchSessionTime(1, 3_600_000),
+            BroadcastMessage::VideoCapture(VideoCaptureMode::ToggleCapture),
+        ];
+
+        for message in messages {
+            let (expected_type, var1, var2, var3) = message.encode();
+            let (wparam, lparam) = pack_message(message);
+            let (decoded_type, decoded1, decoded2, decoded3) =
+                BroadcastMessage::decode(wparam, lparam).expect("decodes");
+
+            assert_eq!(decoded_type as u32, expected_type as u32);
+            assert_eq!((decoded1, decoded2, decoded3), (var1, var2, var3));
+        }
+    }
+
+    #[test]
+    fn test_decode_unknown_message_type() {
+        let result = BroadcastMessage::decode(14, 0);
+        assert!(matches!(
+            result,
+            Err(BroadcastError::UnknownMessageType { value: 14 })
+        ));
+    }
+
+    #[test]
+    fn test_packed_params() {
+        let cases = [
+            (
+                BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Leader, 11, 0),
+                (0, 0xfffe, 11, 0),
+                (0xfffe_0000, 0xb),
+            ),
+            (
+                BroadcastMessage::replay_seek_from_end(60),
+                (4, 2, 0xffc4, 0xffff),
+                (0x2_0004, 0xffff_ffc4),
+            ),
+            (
+                BroadcastMessage::ChatCommandMacro(3),
+                (8, 0, 3, 0),
+                (0x8, 0x3),
+            ),
+            (
+                BroadcastMessage::FFBCommand(FfbForce::from_newton_meters(12.5)),
+                (11, 0, 0x8000, 0xc),
+                (0xb, 0xc_8000),
+            ),
+            (
+                BroadcastMessage::ReplaySearchSessionTime(2, 3_600_000),
+                (12, 2, 0xee80, 0x36),
+                (0x2_000c, 0x36_ee80),
+            ),
+        ];
+
+        for (message, packed, winparams) in cases {
+            assert_eq!(message.packed(), packed, "{message:?}");
+            assert_eq!(message.packed_winparams(), winparams, "{message:?}");
+        }
+    }
+
+    #[test]
+    fn test_wire_round_trip() {
+        let messages = [
+            BroadcastMessage::CameraSwitchPosition(3, 2, 1),
+            BroadcastMessage::camera_switch_number("064", 1, 1),
+            BroadcastMessage::camera_switch_number("0", 4, 2),
+            BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Incident, 4, 0),
+            BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Raw(-7), 4, 0),
+            BroadcastMessage::CameraSetState(CameraState::UI_HIDDEN | CameraState::CAM_TOOL_ACTIVE),
+            BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::new(4, true).unwrap()),
+            BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::rewind(16).unwrap()),
+            BroadcastMessage::ReplaySetPlayPosition(ReplayPositionMode::Begin, 200_000),
+            BroadcastMessage::replay_seek_from_end(60),
+            BroadcastMessage::ReplaySearch(ReplaySearchMode::NextLap),
+            BroadcastMessage::ReplaySetState,
+            BroadcastMessage::ReloadAllTextures,
+            BroadcastMessage::ReloadTextures(7),
+            BroadcastMessage::ChatCommand(ChatCommandMode::Begin),
+            BroadcastMessage::ChatCommandMacro(12),
+            BroadcastMessage::PitCommand(PitCommandMode::Fuel(65)),
+            BroadcastMessage::PitCommand(PitCommandMode::RR(179)),
+            BroadcastMessage::PitCommand(PitCommandMode::ClearFuel),
+            BroadcastMessage::TelemetryCommand(TelemetryCommandMode::Restart),
+            BroadcastMessage::FFBCommand(FfbForce::from_newton_meters(2.5)),
+            BroadcastMessage::ReplaySearchSessionTime(1, 3_600_000),
+            BroadcastMessage::VideoCapture(VideoCaptureMode::ToggleCapture),
+        ];
+
+        for message in messages {
+            let wire = message.to_wire();
+            assert_eq!(BroadcastMessage::from_wire(wire).unwrap(), message);
+            assert_eq!(WireMessage::from_bytes(wire.to_bytes()), wire);
+        }
+    }
+
+    #[test]
+    fn test_wire_layout() {
+        assert_eq!(WireMessage::SIZE, 12);
+        assert_eq!(core::mem::align_of::<WireMessage>(), 4);
+
+        let wire = BroadcastMessage::PitCommand(PitCommandMode::Fuel(65)).to_wire();
+        assert_eq!(wire, WireMessage::new(9, 2, 65, 0));
+        assert_eq!(
+            wire.to_bytes(),
+            [
+                9u32.to_ne_bytes().as_slice(),
+                &2u16.to_ne_bytes(),
+                &65u16.to_ne_bytes(),
+                &[0; 4],
+            ]
+            .concat()
+            .as_slice()
+        );
+    }
+
+    #[test]
+    fn test_wire_canonical_forms() {
+        let focus = BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Position(3), 2, 1);
+        let decoded = BroadcastMessage::from_wire(focus.to_wire()).unwrap();
+        assert_eq!(decoded, BroadcastMessage::CameraSwitchPosition(3, 2, 1));
+        assert_eq!(decoded.to_wire(), focus.to_wire());
+
+        let chat = BroadcastMessage::ChatCommand(ChatCommandMode::Macro);
+        assert_eq!(
+            BroadcastMessage::from_wire(chat.to_wire()).unwrap(),
+            BroadcastMessage::ChatCommandMacro(0)
+        );
+    }
+
+    #[test]
+    fn test_from_wire_rejects_invalid_words() {
+        assert!(matches!(
+            BroadcastMessage::from_wire(WireMessage::new(14, 0, 0, 0)),
+            Err(BroadcastError::UnknownMessageType { .. })
+        ));
+
+        for wire in [
+            WireMessage::new(0, 1, 256, 1),
+            WireMessage::new(3, 17, 0, 0),
+            WireMessage::new(5, 99, 0, 0),
+            WireMessage::new(9, 2, 300, 0),
+            WireMessage::new(9, 12, 0, 0),
+        ] {
+            assert!(
+                matches!(
+                    BroadcastMessage::from_wire(wire),
+                    Err(BroadcastError::InvalidParameter { .. })
+                ),
+                "{wire:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_message_type_try_from_u32() {
+        let expected = [
+            BroadcastMessageType::CameraSwitchPosition,
+            BroadcastMessageType::CameraSwitchNumber,
+            BroadcastMessageType::CameraSetState,
+            BroadcastMessageType::ReplaySetPlaySpeed,
+            BroadcastMessageType::ReplaySetPlayPosition,
+            BroadcastMessageType::ReplaySearch,
+            BroadcastMessageType::ReplaySetState,
+            BroadcastMessageType::ReloadTextures,
+            BroadcastMessageType::ChatCommand,
+            BroadcastMessageType::PitCommand,
+            BroadcastMessageType::TelemetryCommand,
+            BroadcastMessageType::FFBCommand,
+            BroadcastMessageType::ReplaySearchSessionTime,
+            BroadcastMessageType::VideoCapture,
+        ];
+
+        for (value, expected_type) in (0u32..=13).zip(expected) {
+            let message_type = BroadcastMessageType::try_from(value).expect("known type");
+            assert_eq!(message_type as u32, expected_type as u32);
+        }
+
+        assert!(matches!(
+            BroadcastMessageType::try_from(14),
+            Err(BroadcastError::UnknownMessageType { value: 14 })
+        ));
+    }
+
+    #[test]
+    fn test_try_camera_switch_number() {
+        assert_eq!(
+            BroadcastMessage::try_camera_switch_number("064", 1, 1).unwrap(),
+            BroadcastMessage::camera_switch_number("064", 1, 1)
+        );
+        assert!(matches!(
+            BroadcastMessage::try_camera_switch_number("ABC", 1, 1),
+            Err(BroadcastError::InvalidCarNumber { .. })
+        ));
+    }
+
+    #[test]
+    fn test_replay_set_play_speed_rewind() {
+        let speed = ReplaySpeed::rewind(4).unwrap();
+        let (message_type, var1, var2, var3) = BroadcastMessage::ReplaySetPlaySpeed(speed).encode();
+        assert!(matches!(
+            message_type,
+            BroadcastMessageType::ReplaySetPlaySpeed
+        ));
+        assert_eq!((var1, var2, var3), (-4i16 as u16, 0, 0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_externally_tagged() {
+        let fuel = BroadcastMessage::PitCommand(PitCommandMode::Fuel(65));
+        assert_eq!(
+            serde_json::to_string(&fuel).unwrap(),
+            r#"{"PitCommand":{"Fuel":65}}"#
+        );
+
+        let state = BroadcastMessage::CameraSetState(CameraState::UI_HIDDEN);
+        assert_eq!(
+            serde_json::to_string(&state).unwrap(),
+            r#"{"CameraSetState":"UI_HIDDEN"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<BroadcastMessage>(r#"{"CameraSetState":"UI_HIDDEN"}"#).unwrap(),
+            state
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let messages = [
+            BroadcastMessage::CameraSwitchPosition(1, 2, 3),
+            BroadcastMessage::camera_switch_number("064", 1, 1),
+            BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Leader, 0, 0),
+            BroadcastMessage::CameraSetState(
+                CameraState::IS_SCENIC_ACTIVE | CameraState::UI_HIDDEN,
+            ),
+            BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::rewind(2).unwrap()),
+            BroadcastMessage::ReplaySetPlayPosition(ReplayPositionMode::End, 200_000),
+            BroadcastMessage::ReplaySearch(ReplaySearchMode::NextIncident),
+            BroadcastMessage::ReplaySetState,
+            BroadcastMessage::ChatCommand(ChatCommandMode::Reply),
+            BroadcastMessage::PitCommand(PitCommandMode::LF(179)),
+            BroadcastMessage::TelemetryCommand(TelemetryCommandMode::Start),
+            BroadcastMessage::FFBCommand(FfbForce::from_newton_meters(12.5)),
+            BroadcastMessage::ReplaySearchSessionTime(1, 3_600_000),
+            BroadcastMessage::VideoCapture(VideoCaptureMode::ShowTimer),
+        ];
+
+        for message in messages {
+            let json = serde_json::to_string(&message).unwrap();
+            let decoded: BroadcastMessage = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, message, "{json}");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_rejects_invalid_replay_speed() {
+        let json = r#"{"ReplaySetPlaySpeed":{"speed":-40,"slow_motion":false}}"#;
+        assert!(serde_json::from_str::<BroadcastMessage>(json).is_err());
+    }
+
+    #[test]
+    fn test_display_descriptions() {
+        assert_eq!(
+            BroadcastMessage::camera_switch_number("064", 1, 1).to_string(),
+            "Switch to car #064, group 1, camera 1"
+        );
+        assert_eq!(
+            BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Leader, 2, 0).to_string(),
+            "Focus on leader, group 2, camera 0"
+        );
+        assert_eq!(
+            BroadcastMessage::PitCommand(PitCommandMode::Fuel(65)).to_string(),
+            "Fuel: 65L"
+        );
+        assert_eq!(
+            BroadcastMessage::ReplaySearchSessionTime(2, 83_456).to_string(),
+            "Replay to session 2 at 1:23.456"
+        );
+        assert_eq!(
+            BroadcastMessage::ReplaySearchSessionTime(0, 3_723_004).to_string(),
+            "Replay to session 0 at 1:02:03.004"
+        );
+        assert_eq!(
+            BroadcastMessage::ReplaySetPlayPosition(ReplayPositionMode::Begin, 600).to_string(),
+            "Replay to frame 600 from session start"
+        );
+        assert_eq!(
+            BroadcastMessage::ReplaySetState.to_string(),
+            "Erase replay tape"
+        );
+    }
+
+    #[test]
+    fn test_messages_as_set_keys() {
+        let messages = [
+            BroadcastMessage::camera_switch_number("064", 1, 0),
+            BroadcastMessage::camera_switch_number("64", 1, 0),
+            BroadcastMessage::FFBCommand(FfbForce::from_newton_meters(12.5)),
+            BroadcastMessage::PitCommand(PitCommandMode::Fuel(40)),
+            BroadcastMessage::ReplaySetState,
+        ];
+        let mut seen = std::collections::HashSet::new();
+        for message in messages {
+            assert!(seen.insert(message), "{message:?}");
+        }
+
+        // Copies hash and compare equal to the original.
+        let copy = messages[0];
+        assert!(!seen.insert(copy));
+        assert!(seen.contains(&BroadcastMessage::camera_switch_number("064", 1, 0)));
+        assert_eq!(seen.len(), messages.len());
+    }
+
+    #[test]
+    fn test_replay_set_state_erases_tape() {
+        assert_eq!(
+            BroadcastMessage::replay_erase_tape(),
+            BroadcastMessage::ReplaySetState
+        );
+        // irsdk_ReplayState_EraseTape is 0 and the other words are unused.
+        assert!(matches!(
+            BroadcastMessage::replay_erase_tape().encode(),
+            (BroadcastMessageType::ReplaySetState, 0, 0, 0)
+        ));
+        assert_eq!(
+            BroadcastMessage::ReplaySetState.to_wire(),
+            WireMessage::new(6, 0, 0, 0)
+        );
+        assert_eq!(
+            BroadcastMessage::ReplaySetState.packed_winparams(),
+            (0x6, 0)
+        );
+
+        assert_eq!(
+            BroadcastMessage::from_wire(WireMessage::new(6, 0, 0, 0)).unwrap(),
+            BroadcastMessage::ReplaySetState
+        );
+        for mode in [1, 2, u16::MAX] {
+            assert!(matches!(
+                BroadcastMessage::from_wire(WireMessage::new(6, mode, 0, 0)),
+                Err(BroadcastError::InvalidParameter { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_replay_toggle() {
+        assert_eq!(
+            BroadcastMessage::replay_toggle(ReplaySpeed::paused()),
+            BroadcastMessage::replay_play()
+        );
+        for playing in [
+            ReplaySpeed::normal(),
+            ReplaySpeed::rewind(2).unwrap(),
+            ReplaySpeed::new(4, true).unwrap(),
+        ] {
+            assert_eq!(
+                BroadcastMessage::replay_toggle(playing),
+                BroadcastMessage::replay_pause(),
+                "{playing}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_checked_and_saturating_chat_macro() {
+        for (macro_number, checked, saturated) in [
+            (0, None, 1),
+            (1, Some(1), 1),
+            (15, Some(15), 15),
+            (16, None, 15),
+            (u32::MAX, None, 15),
+        ] {
+            assert_eq!(
+                BroadcastMessage::checked_chat_macro(macro_number).ok(),
+                checked.map(BroadcastMessage::ChatCommandMacro),
+                "{macro_number}"
+            );
+            assert_eq!(
+                BroadcastMessage::saturating_chat_macro(macro_number),
+                BroadcastMessage::ChatCommandMacro(saturated)
+            );
+        }
+    }
+
+    #[test]
+    fn test_camera_grid_position() {
+        assert_eq!(
+            BroadcastMessage::camera_grid_position(1, 11, 2).unwrap(),
+            BroadcastMessage::CameraSwitchPosition(1, 11, 2)
+        );
+        assert_eq!(
+            BroadcastMessage::camera_grid_position(1, 11, 2)
+                .unwrap()
+                .encode(),
+            (BroadcastMessageType::CameraSwitchPosition, 1, 11, 2)
+        );
+        assert_eq!(
+            BroadcastMessage::camera_grid_position(u8::MAX, 1, 1).unwrap(),
+            BroadcastMessage::CameraSwitchPosition(u8::MAX, 1, 1)
+        );
+        assert!(matches!(
+            BroadcastMessage::camera_grid_position(0, 11, 2),
+            Err(BroadcastError::InvalidParameter { .. })
+        ));
+    }
+
+    #[test]
+    fn test_checked_and_saturating_u8_params() {
+        let boundaries = [
+            (0, Some(0), 0),
+            (254, Some(254), 254),
+            (255, Some(255), 255),
+            (256, None, 255),
+            (u32::MAX, None, 255),
+        ];
+        let focus = CameraFocusMode::Leader;
+
+        for (value, fits, saturated) in boundaries {
+            for (checked, expected) in [
+                (
+                    BroadcastMessage::checked_camera_switch_position(value, 1, 1),
+                    fits.map(|value| BroadcastMessage::CameraSwitchPosition(value, 1, 1)),
+                ),
+                (
+                    BroadcastMessage::checked_camera_switch_position(1, value, 1),
+                    fits.map(|value| BroadcastMessage::CameraSwitchPosition(1, value, 1)),
+                ),
+                (
+                    BroadcastMessage::checked_camera_switch_focus(focus, 1, value),
+                    fits.map(|value| BroadcastMessage::CameraSwitchFocus(focus, 1, value)),
+                ),
+                (
+                    BroadcastMessage::checked_reload_textures(value),
+                    fits.map(BroadcastMessage::ReloadTextures),
+                ),
+            ] {
+                match expected {
+                    Some(expected) => assert_eq!(checked.unwrap(), expected, "{value}"),
+                    None => assert!(
+                        matches!(checked, Err(BroadcastError::InvalidParameter { .. })),
+                        "{value}"
+                    ),
+                }
+            }
+
+            assert_eq!(
+                BroadcastMessage::saturating_camera_switch_position(value, value, 1),
+                BroadcastMessage::CameraSwitchPosition(saturated, saturated, 1)
+            );
+            assert_eq!(
+                BroadcastMessage::saturating_camera_switch_focus(focus, value, value),
+                BroadcastMessage::CameraSwitchFocus(focus, saturated, saturated)
+            );
+            assert_eq!(
+                BroadcastMessage::saturating_reload_textures(value),
+                BroadcastMessage::ReloadTextures(saturated)
+            );
+        }
+    }
+}
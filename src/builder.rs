@@ -0,0 +1,246 @@
+//! Configuring a [`Client`] in one place.
+
+use std::{sync::Arc, time::Duration};
+
+use crate::{BroadcastMessage, Client, Delivery, RateLimitMode, Result, client::Observer};
+
+/// Where a [`Client`]'s sends go.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    /// Every top-level window on the desktop, like the SDK does.
+    #[default]
+    Broadcast,
+    /// Only the simulator window owned by this process id; see
+    /// [`Client::send_message_to_pid`].
+    ///
+    /// For a single known window, use `Client::send_message_to` on Windows.
+    Process(u32),
+}
+
+/// Fluent configuration for a [`Client`].
+///
+/// Every option has a matching setter on [`Client`]; the builder only
+/// gathers them so a client can be configured up front.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use iracing_broadcast::{BroadcastMessage, Client, Target};
+///
+/// let client = Client::builder()
+///     .offline(true)
+///     .dry_run(true)
+///     .target(Target::Process(4242))
+///     .rate_limit(Duration::from_millis(20))
+///     .observer(|message, _| println!("sending {message}"))
+///     .build()?;
+///
+/// client.send_message(BroadcastMessage::ReplaySetState)?;
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ClientBuilder {
+    offline: bool,
+    dry_run: bool,
+    auto_refresh: bool,
+    delivery: Delivery,
+    target: Target,
+    rate_limit: Option<(Duration, RateLimitMode)>,
+    observers: Vec<ObserverEntry>,
+}
+
+/// An observer waiting to be added by [`ClientBuilder::build`].
+#[derive(Clone)]
+struct ObserverEntry(Arc<Observer>);
+
+impl std::fmt::Debug for ObserverEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Observer")
+    }
+}
+
+impl ClientBuilder {
+    /// A builder for a live client with no options set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build an [offline](Client::offline) client instead of registering
+    /// the broadcast message.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// See [`Client::set_dry_run`].
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// See [`Client::set_auto_refresh`].
+    pub fn auto_refresh(mut self, auto_refresh: bool) -> Self {
+        self.auto_refresh = auto_refresh;
+        self
+    }
+
+    /// See [`Client::set_delivery`].
+    pub fn delivery(mut self, delivery: Delivery) -> Self {
+        self.delivery = delivery;
+        self
+    }
+
+    /// See [`Client::set_target`].
+    pub fn target(mut self, target: Target) -> Self {
+        self.target = target;
+        self
+    }
+
+    /// Wait at least `min_interval` between sends; see
+    /// [`Client::set_rate_limit`]. Early sends wait unless
+    /// [`ClientBuilder::rate_limit_mode`] says otherwise.
+    pub fn rate_limit(mut self, min_interval: Duration) -> Self {
+        let mode = self.rate_limit.map(|(_, mode)| mode).unwrap_or_default();
+        self.rate_limit = Some((min_interval, mode));
+        self
+    }
+
+    /// What to do with a send that comes too soon under
+    /// [`ClientBuilder::rate_limit`].
+    pub fn rate_limit_mode(mut self, mode: RateLimitMode) -> Self {
+        let min_interval = self
+            .rate_limit
+            .map_or(Duration::ZERO, |(interval, _)| interval);
+        self.rate_limit = Some((min_interval, mode));
+        self
+    }
+
+    /// See [`Client::with_observer`]. Observers run in the order they are
+    /// added.
+    pub fn observer<F>(mut self, observer: F) -> Self
+    where
+        F: Fn(&BroadcastMessage, (usize, isize)) + Send + Sync + 'static,
+    {
+        self.observers.push(ObserverEntry(Arc::new(observer)));
+        self
+    }
+
+    /// Create the client.
+    ///
+    /// Fails like [`Client::new`] unless the client is
+    /// [offline](ClientBuilder::offline).
+    pub fn build(self) -> Result<Client> {
+        let mut client = if self.offline {
+            Client::offline()
+        } else {
+            Client::new()?
+        };
+
+        client.set_dry_run(self.dry_run);
+        client.set_auto_refresh(self.auto_refresh);
+        client.set_delivery(self.delivery);
+        client.set_target(self.target);
+        if let Some((min_interval, mode)) = self.rate_limit {
+            client.set_rate_limit(min_interval, mode);
+        }
+        for ObserverEntry(observer) in self.observers {
+            client = client.with_shared_observer(observer);
+        }
+
+        Ok(client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::{BroadcastError, PitCommandMode};
+
+    fn offline() -> ClientBuilder {
+        ClientBuilder::new().offline(true)
+    }
+
+    #[test]
+    fn test_defaults() {
+        let client = offline().build().unwrap();
+
+        assert!(client.is_offline());
+        assert!(!client.is_dry_run());
+        assert!(!client.is_auto_refresh());
+        assert_eq!(client.delivery(), Delivery::Notify);
+        assert_eq!(client.target(), Target::Broadcast);
+        assert_eq!(client.rate_limit(), None);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_live_client_unsupported_off_windows() {
+        assert!(matches!(
+            ClientBuilder::new().build(),
+            Err(BroadcastError::UnsupportedPlatform { .. })
+        ));
+    }
+
+    #[test]
+    fn test_dry_run_validates() {
+        let client = offline().dry_run(true).build().unwrap();
+
+        assert!(client.is_dry_run());
+        assert!(
+            client
+                .send_message(BroadcastMessage::ChatCommandMacro(40))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_delivery() {
+        let client = offline().delivery(Delivery::Post).build().unwrap();
+        assert_eq!(client.delivery(), Delivery::Post);
+    }
+
+    #[test]
+    fn test_target() {
+        let client = offline().target(Target::Process(4242)).build().unwrap();
+
+        assert_eq!(client.target(), Target::Process(4242));
+        assert!(client.send_message(PitCommandMode::Tearoff).is_ok());
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let interval = Duration::from_secs(60);
+        let client = offline()
+            .rate_limit(interval)
+            .rate_limit_mode(RateLimitMode::Reject)
+            .build()
+            .unwrap();
+
+        assert_eq!(client.rate_limit(), Some((interval, RateLimitMode::Reject)));
+        client.send_message(PitCommandMode::Tearoff).unwrap();
+        assert!(matches!(
+            client.send_message(PitCommandMode::Tearoff),
+            Err(BroadcastError::RateLimited { .. })
+        ));
+
+        let waiting = offline().rate_limit(interval).build().unwrap();
+        assert_eq!(waiting.rate_limit(), Some((interval, RateLimitMode::Wait)));
+    }
+
+    #[test]
+    fn test_observers_in_order() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let (first, second) = (Arc::clone(&seen), Arc::clone(&seen));
+        let client = offline()
+            .observer(move |_, _| first.lock().unwrap().push(1))
+            .observer(move |_, _| second.lock().unwrap().push(2))
+            .build()
+            .unwrap();
+
+        client.send_message(PitCommandMode::Tearoff).unwrap();
+        assert_eq!(*seen.lock().unwrap(), [1, 2]);
+    }
+}
@@ -0,0 +1,461 @@
+//! Stateful camera switching for director UIs.
+
+use std::collections::HashMap;
+
+use crate::{
+    BroadcastError, BroadcastMessage, CameraFocusMode, CameraGroup, CarNumber, Client, MessageSink,
+    Result,
+};
+
+/// What the camera is pointed at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Target {
+    Position(u8),
+    Car(CarNumber),
+    Focus(CameraFocusMode),
+}
+
+/// Tracks the current camera target, group and camera, and sends the
+/// matching `CameraSwitch*` message whenever one of them changes.
+///
+/// Group and camera numbers are the sim's `GroupNum` and `CameraNum` from
+/// the session info, both starting at `1`. The controller starts on group
+/// `1`, camera `1`, following the most exciting car, but sends nothing until
+/// the first call. The sim does not report camera changes made elsewhere,
+/// so the tracked state is only what this controller last sent.
+///
+/// The sim silently ignores a switch to a group or camera the session does
+/// not have, so the controller rejects numbers above its limits instead.
+/// A session lists a few dozen groups at most, and even long tracks have
+/// only a few dozen cameras per group, so both limits default to
+/// [`CameraController::DEFAULT_LIMIT`]. Set the session's exact counts from
+/// its `CameraInfo` with [`CameraController::set_limits`].
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::{BroadcastMessage, CameraController, RecordingSink};
+///
+/// let mut cameras = CameraController::new(RecordingSink::new());
+/// cameras.switch_to_car("064")?;
+/// cameras.set_group(4)?;
+/// cameras.next_camera()?;
+///
+/// assert_eq!(
+///     cameras.sink().messages().last(),
+///     Some(&BroadcastMessage::camera_switch_number("064", 4, 2))
+/// );
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct CameraController<S = Client> {
+    sink: S,
+    target: Target,
+    group: u8,
+    camera: u8,
+    max_group: u8,
+    max_camera: u8,
+    roster: HashMap<String, String>,
+}
+
+impl<S: MessageSink> CameraController<S> {
+    /// The default highest group and camera number.
+    pub const DEFAULT_LIMIT: u8 = 64;
+
+    /// Switch cameras through `sink`, usually a [`Client`].
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink,
+            target: Target::Focus(CameraFocusMode::Exciting),
+            group: 1,
+            camera: 1,
+            max_group: Self::DEFAULT_LIMIT,
+            max_camera: Self::DEFAULT_LIMIT,
+            roster: HashMap::new(),
+        }
+    }
+
+    /// Like [`CameraController::new`], with a roster mapping driver names
+    /// to car numbers for [`CameraController::switch_to_driver`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use iracing_broadcast::{BroadcastMessage, CameraController, RecordingSink};
+    ///
+    /// let roster = HashMap::from([("Jane Doe".to_owned(), "064".to_owned())]);
+    /// let mut cameras = CameraController::with_roster(RecordingSink::new(), roster);
+    /// cameras.switch_to_driver("Jane Doe")?;
+    ///
+    /// assert_eq!(
+    ///     cameras.sink().messages(),
+    ///     [BroadcastMessage::camera_switch_number("064", 1, 1)]
+    /// );
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn with_roster(sink: S, roster: HashMap<String, String>) -> Self {
+        Self {
+            roster,
+            ..Self::new(sink)
+        }
+    }
+
+    /// Replace the roster, e.g. when timing data reports a driver change.
+    pub fn set_roster(&mut self, roster: HashMap<String, String>) {
+        self.roster = roster;
+    }
+
+    /// The driver names and car numbers
+    /// [`CameraController::switch_to_driver`] looks up.
+    pub fn roster(&self) -> &HashMap<String, String> {
+        &self.roster
+    }
+
+    /// Follow the car showing `car_number`, keeping the group and camera.
+    ///
+    /// Returns [`BroadcastError::InvalidCarNumber`](crate::BroadcastError::InvalidCarNumber)
+    /// without sending or changing state if the number is malformed.
+    pub fn switch_to_car(&mut self, car_number: &str) -> Result<()> {
+        self.retarget(Target::Car(CarNumber::new(car_number)?))
+    }
+
+    /// Follow the car driven by `name` in the roster, keeping the group and
+    /// camera.
+    ///
+    /// Returns [`BroadcastError::InvalidParameter`] without sending or
+    /// changing state if the name is not in the roster, and fails like
+    /// [`CameraController::switch_to_car`] if its car number is malformed.
+    pub fn switch_to_driver(&mut self, name: &str) -> Result<()> {
+        let car_number = self.roster.get(name).map(String::as_str).ok_or_else(|| {
+            BroadcastError::invalid_parameter(
+                "driver name",
+                format!("{name:?} is not in the roster"),
+            )
+        })?;
+        let target = Target::Car(CarNumber::new(car_number)?);
+        self.retarget(target)
+    }
+
+    /// Follow the car running in race position `position`, keeping the
+    /// group and camera.
+    pub fn switch_to_position(&mut self, position: u8) -> Result<()> {
+        self.retarget(Target::Position(position))
+    }
+
+    /// Let the sim pick the car according to `mode`, keeping the group and
+    /// camera.
+    pub fn switch_to_focus(&mut self, mode: CameraFocusMode) -> Result<()> {
+        self.retarget(Target::Focus(mode))
+    }
+
+    /// Change to camera group `group`, starting at its first camera.
+    ///
+    /// Returns [`BroadcastError::InvalidParameter`] without sending or
+    /// changing state if `group` is `0` or above the group limit.
+    pub fn set_group(&mut self, group: impl Into<CameraGroup>) -> Result<()> {
+        let group = checked_index("camera group", group.into().get(), self.max_group)?;
+        self.switch(self.target, group, 1)
+    }
+
+    /// Change to camera `camera` in the current group.
+    ///
+    /// Returns [`BroadcastError::InvalidParameter`] without sending or
+    /// changing state if `camera` is `0` or above the camera limit.
+    pub fn set_camera(&mut self, camera: u8) -> Result<()> {
+        let camera = checked_index("camera", camera, self.max_camera)?;
+        self.switch(self.target, self.group, camera)
+    }
+
+    /// Change to the next camera in the current group, wrapping to `1`
+    /// after the camera limit.
+    ///
+    /// Unless the limit is the group's exact camera count, the sim ignores
+    /// numbers past the group's last camera.
+    pub fn next_camera(&mut self) -> Result<()> {
+        let camera = if self.camera >= self.max_camera {
+            1
+        } else {
+            self.camera + 1
+        };
+        self.switch(self.target, self.group, camera)
+    }
+
+    /// Reject groups above `max_group` and cameras above `max_camera` from
+    /// now on, e.g. the group count and the current group's camera count
+    /// from the session's `CameraInfo`. Limits below `1` are raised to `1`.
+    ///
+    /// The current group and camera are kept even if they exceed the new
+    /// limits.
+    pub fn set_limits(&mut self, max_group: u8, max_camera: u8) {
+        self.max_group = max_group.max(1);
+        self.max_camera = max_camera.max(1);
+    }
+
+    /// The highest accepted group and camera numbers; see
+    /// [`CameraController::set_limits`].
+    pub fn limits(&self) -> (u8, u8) {
+        (self.max_group, self.max_camera)
+    }
+
+    /// The current camera group.
+    pub fn group(&self) -> u8 {
+        self.group
+    }
+
+    /// The current camera within [`CameraController::group`].
+    pub fn camera(&self) -> u8 {
+        self.camera
+    }
+
+    /// The sink messages are sent through.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Unwrap the sink.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+
+    fn retarget(&mut self, target: Target) -> Result<()> {
+        self.switch(target, self.group, self.camera)
+    }
+
+    /// Send the switch to `target`, `group` and `camera`, and track them
+    /// only once the send succeeded.
+    fn switch(&mut self, target: Target, group: u8, camera: u8) -> Result<()> {
+        let message = match &target {
+            Target::Position(position) => {
+                BroadcastMessage::CameraSwitchPosition(*position, group, camera)
+            }
+            Target::Car(car_number) => {
+                BroadcastMessage::CameraSwitchNumber(*car_number, group, camera)
+            }
+            Target::Focus(mode) => BroadcastMessage::CameraSwitchFocus(*mode, group, camera),
+        };
+        self.sink.send(message)?;
+
+        (self.target, self.group, self.camera) = (target, group, camera);
+        Ok(())
+    }
+}
+
+/// Check a 1-based group or camera number against its limit.
+fn checked_index(parameter: &str, value: u8, max: u8) -> Result<u8> {
+    if (1..=max).contains(&value) {
+        Ok(value)
+    } else {
+        Err(BroadcastError::invalid_parameter(
+            parameter,
+            format!("{value} is outside 1..={max}"),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RecordingSink, sink::FailingSink};
+
+    fn last(cameras: &CameraController<RecordingSink>) -> BroadcastMessage {
+        cameras.sink().messages().pop().unwrap()
+    }
+
+    #[test]
+    fn test_switch_to_car() {
+        let mut cameras = CameraController::new(RecordingSink::new());
+        cameras.switch_to_car("007").unwrap();
+
+        assert_eq!(
+            last(&cameras),
+            BroadcastMessage::camera_switch_number("007", 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_switch_to_car_rejects_bad_number() {
+        let mut cameras = CameraController::new(RecordingSink::new());
+
+        assert!(matches!(
+            cameras.switch_to_car("7a"),
+            Err(BroadcastError::InvalidCarNumber { .. })
+        ));
+        assert!(cameras.sink().messages().is_empty());
+
+        cameras.set_group(2).unwrap();
+        assert_eq!(
+            last(&cameras),
+            BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Exciting, 2, 1)
+        );
+    }
+
+    fn roster() -> HashMap<String, String> {
+        HashMap::from([
+            ("Jane Doe".to_owned(), "064".to_owned()),
+            ("John Roe".to_owned(), "7a".to_owned()),
+        ])
+    }
+
+    #[test]
+    fn test_switch_to_driver() {
+        let mut cameras = CameraController::with_roster(RecordingSink::new(), roster());
+        cameras.set_group(4).unwrap();
+        cameras.switch_to_driver("Jane Doe").unwrap();
+
+        assert_eq!(
+            last(&cameras),
+            BroadcastMessage::camera_switch_number("064", 4, 1)
+        );
+    }
+
+    #[test]
+    fn test_switch_to_unknown_driver() {
+        let mut cameras = CameraController::with_roster(RecordingSink::new(), roster());
+
+        assert!(matches!(
+            cameras.switch_to_driver("jane doe"),
+            Err(BroadcastError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            cameras.switch_to_driver("John Roe"),
+            Err(BroadcastError::InvalidCarNumber { .. })
+        ));
+        assert!(cameras.sink().messages().is_empty());
+
+        cameras.set_roster(HashMap::new());
+        assert!(cameras.switch_to_driver("Jane Doe").is_err());
+    }
+
+    #[test]
+    fn test_switch_to_position() {
+        let mut cameras = CameraController::new(RecordingSink::new());
+        cameras.switch_to_position(3).unwrap();
+
+        assert_eq!(
+            last(&cameras),
+            BroadcastMessage::CameraSwitchPosition(3, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_switch_to_focus() {
+        let mut cameras = CameraController::new(RecordingSink::new());
+        cameras.switch_to_focus(CameraFocusMode::Leader).unwrap();
+
+        assert_eq!(
+            last(&cameras),
+            BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Leader, 1, 1)
+        );
+    }
+
+    #[test]
+    fn test_set_group_resets_camera() {
+        let mut cameras = CameraController::new(RecordingSink::new());
+        cameras.switch_to_position(5).unwrap();
+        cameras.next_camera().unwrap();
+        cameras.set_group(9).unwrap();
+
+        assert_eq!((cameras.group(), cameras.camera()), (9, 1));
+        assert_eq!(
+            last(&cameras),
+            BroadcastMessage::CameraSwitchPosition(5, 9, 1)
+        );
+    }
+
+    #[test]
+    fn test_next_camera() {
+        let mut cameras = CameraController::new(RecordingSink::new());
+        cameras.switch_to_position(1).unwrap();
+        cameras.next_camera().unwrap();
+        cameras.next_camera().unwrap();
+
+        assert_eq!(
+            cameras.sink().messages(),
+            vec![
+                BroadcastMessage::CameraSwitchPosition(1, 1, 1),
+                BroadcastMessage::CameraSwitchPosition(1, 1, 2),
+                BroadcastMessage::CameraSwitchPosition(1, 1, 3),
+            ]
+        );
+
+        cameras.camera = u8::MAX;
+        cameras.next_camera().unwrap();
+        assert_eq!(cameras.camera(), 1);
+    }
+
+    #[test]
+    fn test_default_limits() {
+        let mut cameras = CameraController::new(RecordingSink::new());
+        let max = CameraController::<RecordingSink>::DEFAULT_LIMIT;
+        assert_eq!(cameras.limits(), (max, max));
+
+        cameras.set_group(max).unwrap();
+        cameras.set_camera(max).unwrap();
+        assert_eq!((cameras.group(), cameras.camera()), (max, max));
+
+        for result in [
+            cameras.set_group(max + 1),
+            cameras.set_group(0),
+            cameras.set_camera(max + 1),
+            cameras.set_camera(0),
+        ] {
+            assert!(matches!(
+                result,
+                Err(BroadcastError::InvalidParameter { .. })
+            ));
+        }
+        assert_eq!((cameras.group(), cameras.camera()), (max, max));
+        assert_eq!(cameras.sink().messages().len(), 2);
+
+        cameras.next_camera().unwrap();
+        assert_eq!(cameras.camera(), 1);
+    }
+
+    #[test]
+    fn test_session_limits() {
+        let mut cameras = CameraController::new(RecordingSink::new());
+        cameras.set_limits(10, 3);
+        cameras.switch_to_position(1).unwrap();
+
+        assert!(cameras.set_group(11).is_err());
+        cameras.set_group(10).unwrap();
+        cameras.next_camera().unwrap();
+        cameras.next_camera().unwrap();
+        cameras.next_camera().unwrap();
+        assert!(cameras.set_camera(4).is_err());
+
+        assert_eq!(
+            cameras.sink().messages(),
+            vec![
+                BroadcastMessage::CameraSwitchPosition(1, 1, 1),
+                BroadcastMessage::CameraSwitchPosition(1, 10, 1),
+                BroadcastMessage::CameraSwitchPosition(1, 10, 2),
+                BroadcastMessage::CameraSwitchPosition(1, 10, 3),
+                BroadcastMessage::CameraSwitchPosition(1, 10, 1),
+            ]
+        );
+
+        cameras.set_limits(0, 0);
+        assert_eq!(cameras.limits(), (1, 1));
+    }
+
+    #[test]
+    fn test_failed_send_keeps_state() {
+        // Accept the first switch, then reject everything.
+        let mut cameras = CameraController::new(FailingSink::new(|attempt, _| attempt > 0));
+        cameras.switch_to_car("064").unwrap();
+        cameras.set_limits(10, 10);
+
+        assert!(cameras.set_group(4).is_err());
+        assert!(cameras.set_camera(3).is_err());
+        assert!(cameras.next_camera().is_err());
+        assert!(cameras.switch_to_position(2).is_err());
+        assert_eq!((cameras.group(), cameras.camera()), (1, 1));
+        assert_eq!(cameras.target, Target::Car(CarNumber::new("064").unwrap()));
+        assert_eq!(
+            cameras.sink().messages(),
+            [BroadcastMessage::camera_switch_number("064", 1, 1)]
+        );
+    }
+}
@@ -0,0 +1,267 @@
+//! Car numbers as the sim displays them.
+
+use alloc::string::{String, ToString};
+use core::{fmt, str::FromStr};
+
+use crate::{
+    BroadcastError, Result,
+    util::{pad_car_number, try_pad_car_number},
+};
+
+/// A car number of one to three digits, leading zeros included.
+///
+/// `"064"` and `"64"` are different cars in iRacing, so the number is kept
+/// as its digits rather than its value. It is stored inline and is `Copy`,
+/// so messages can be built from borrowed or owned data without tying them
+/// to a lifetime.
+///
+/// [`CarNumber::new`] (or [`str::parse`]) validates; the `From` conversions
+/// never fail and turn anything that is not a valid car number into car
+/// `0`, the number [`pad_car_number`](crate::pad_car_number) sends for it.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::CarNumber;
+///
+/// let car: CarNumber = "064".parse()?;
+/// assert_eq!(car.as_str(), "064");
+/// assert_eq!(car.encode(), 3064);
+/// assert_eq!(CarNumber::from(64u16), "64");
+/// assert_eq!(CarNumber::from_padded(3064)?, car);
+/// assert!(CarNumber::new("64a").is_err());
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CarNumber {
+    digits: [u8; CarNumber::MAX_DIGITS],
+    len: u8,
+}
+
+impl CarNumber {
+    /// Longest car number iRacing accepts, including leading zeros.
+    pub const MAX_DIGITS: usize = 3;
+
+    /// Car `0`.
+    pub const ZERO: Self = CarNumber {
+        digits: [b'0', 0, 0],
+        len: 1,
+    };
+
+    /// Parse a car number of one to three ASCII digits.
+    ///
+    /// Returns [`BroadcastError::InvalidCarNumber`] for anything else, like
+    /// [`try_pad_car_number`](crate::try_pad_car_number).
+    pub fn new(car_number: &str) -> Result<Self> {
+        try_pad_car_number(car_number)?;
+
+        let mut digits = [0; Self::MAX_DIGITS];
+        digits[..car_number.len()].copy_from_slice(car_number.as_bytes());
+        Ok(CarNumber {
+            digits,
+            len: car_number.len() as u8,
+        })
+    }
+
+    /// Decode the value [`CarNumber::encode`] produces, restoring leading
+    /// zeros.
+    ///
+    /// Returns [`BroadcastError::InvalidCarNumber`] for values no car number
+    /// encodes to, such as `2064` (two digits, but `64` has no leading zero).
+    pub fn from_padded(value: u16) -> Result<Self> {
+        if value < 1000 {
+            return Ok(Self::from(value));
+        }
+
+        let (digits, num) = (usize::from(value / 1000), value % 1000);
+        let padded = (2..=Self::MAX_DIGITS).contains(&digits) && num < 10u16.pow(digits as u32 - 1);
+        if !padded {
+            return Err(BroadcastError::invalid_car_number(
+                value.to_string(),
+                "not a padded car number",
+            ));
+        }
+        Self::new(&alloc::format!("{num:0digits$}"))
+    }
+
+    /// The digits, e.g. `"064"`.
+    pub fn as_str(&self) -> &str {
+        // Only ASCII digits are ever stored.
+        core::str::from_utf8(&self.digits[..usize::from(self.len)]).unwrap_or("0")
+    }
+
+    /// The `u16` the SDK expects; see [`pad_car_number`](crate::pad_car_number).
+    pub fn encode(self) -> u16 {
+        pad_car_number(self.as_str())
+    }
+}
+
+impl Default for CarNumber {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl FromStr for CarNumber {
+    type Err = BroadcastError;
+
+    fn from_str(car_number: &str) -> Result<Self> {
+        Self::new(car_number)
+    }
+}
+
+impl From<&str> for CarNumber {
+    /// Like [`CarNumber::new`], but malformed numbers become car `0`.
+    fn from(car_number: &str) -> Self {
+        Self::new(car_number).unwrap_or_default()
+    }
+}
+
+impl From<&String> for CarNumber {
+    /// Like [`CarNumber::new`], but malformed numbers become car `0`.
+    fn from(car_number: &String) -> Self {
+        Self::from(car_number.as_str())
+    }
+}
+
+impl From<String> for CarNumber {
+    /// Like [`CarNumber::new`], but malformed numbers become car `0`.
+    fn from(car_number: String) -> Self {
+        Self::from(car_number.as_str())
+    }
+}
+
+impl From<u16> for CarNumber {
+    /// The number without leading zeros; values above `999` saturate to
+    /// `999`.
+    fn from(value: u16) -> Self {
+        let value = value.min(999);
+        let digits = [value / 100, value / 10 % 10, value % 10].map(|digit| b'0' + digit as u8);
+        let len = match value {
+            0..=9 => 1,
+            10..=99 => 2,
+            _ => 3,
+        };
+
+        let mut stored = [0; Self::MAX_DIGITS];
+        stored[..len].copy_from_slice(&digits[Self::MAX_DIGITS - len..]);
+        CarNumber {
+            digits: stored,
+            len: len as u8,
+        }
+    }
+}
+
+impl AsRef<str> for CarNumber {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for CarNumber {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for CarNumber {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Debug for CarNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CarNumber").field(&self.as_str()).finish()
+    }
+}
+
+impl fmt::Display for CarNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for CarNumber {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CarNumber {
+    /// Validates like [`CarNumber::new`].
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        let car_number = String::deserialize(deserializer)?;
+        Self::new(&car_number).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_sources() {
+        let owned = format!("{:03}", 64);
+        assert_eq!(CarNumber::from(owned.as_str()), "064");
+        assert_eq!(CarNumber::from(&owned), "064");
+        assert_eq!(CarNumber::from(owned), "064");
+        assert_eq!("7".parse::<CarNumber>().unwrap(), "7");
+
+        assert_eq!(CarNumber::from(0u16), "0");
+        assert_eq!(CarNumber::from(7u16), "7");
+        assert_eq!(CarNumber::from(64u16), "64");
+        assert_eq!(CarNumber::from(999u16), "999");
+        assert_eq!(CarNumber::from(1000u16), "999");
+    }
+
+    #[test]
+    fn test_validation() {
+        for car_number in ["", "7a", "-1", "1234", " 64"] {
+            assert!(matches!(
+                CarNumber::new(car_number),
+                Err(BroadcastError::InvalidCarNumber { .. })
+            ));
+            assert_eq!(CarNumber::from(car_number), CarNumber::ZERO);
+        }
+    }
+
+    #[test]
+    fn test_padded_encoding_round_trip() {
+        for digits in 1..=3 {
+            for value in 0..10u32.pow(digits) {
+                let car_number = format!("{value:0width$}", width = digits as usize);
+                let car = CarNumber::new(&car_number).unwrap();
+                assert_eq!(car.encode(), try_pad_car_number(&car_number).unwrap());
+                assert_eq!(CarNumber::from_padded(car.encode()).unwrap(), car);
+            }
+        }
+
+        for value in [1064, 2064, 3100, 4000, u16::MAX] {
+            assert!(CarNumber::from_padded(value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_formatting() {
+        let car = CarNumber::new("007").unwrap();
+        assert_eq!(car.to_string(), "007");
+        assert_eq!(format!("{car:?}"), r#"CarNumber("007")"#);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_validates() {
+        let car = CarNumber::new("064").unwrap();
+        assert_eq!(serde_json::to_string(&car).unwrap(), r#""064""#);
+        assert_eq!(serde_json::from_str::<CarNumber>(r#""064""#).unwrap(), car);
+        assert!(serde_json::from_str::<CarNumber>(r#""64a""#).is_err());
+    }
+}
@@ -0,0 +1,185 @@
+//! Typing chat text, which the broadcast protocol cannot carry.
+
+use std::{thread, time::Duration};
+
+use crate::{BroadcastError, ChatCommandMode, Client, Result};
+
+/// How long the chat box gets to open before typing starts.
+const CHAT_OPEN_DELAY: Duration = Duration::from_millis(100);
+
+impl Client {
+    /// Say `text` in chat: open the chat box with
+    /// [`ChatCommandMode::Begin`], type the text, and press Enter.
+    ///
+    /// Broadcast messages cannot carry strings, so the text is typed as
+    /// synthesized keyboard input with `SendInput`, which comes with the
+    /// usual Win32 input caveats:
+    ///
+    /// - Keystrokes go to the foreground window, not to the sim. If another
+    ///   window has focus, or gains it while typing, it receives the text.
+    /// - Windows blocks input injected into a process running at a higher
+    ///   integrity level, so this fails if the sim runs as administrator
+    ///   and this process does not.
+    /// - The sim gets a moment to open the chat box before typing starts;
+    ///   a busy machine can lose the first characters.
+    /// - Keys the user presses meanwhile are interleaved with the text.
+    ///
+    /// For fixed phrases, the sim's chat macros
+    /// ([`BroadcastMessage::chat_macro`](crate::BroadcastMessage::chat_macro))
+    /// avoid all of this.
+    ///
+    /// Returns [`BroadcastError::InvalidParameter`] without sending if the
+    /// text is empty or contains control characters such as a line break.
+    /// An [offline](Client::offline) or [dry-run](Client::set_dry_run)
+    /// client only sends the `Begin` message and types nothing.
+    pub fn send_chat_text(&self, text: &str) -> Result<()> {
+        if text.is_empty() || text.chars().any(char::is_control) {
+            return Err(BroadcastError::invalid_parameter(
+                "chat text",
+                format!("{text:?} must be non-empty and contain no control characters"),
+            ));
+        }
+
+        self.send_message(ChatCommandMode::Begin)?;
+        if self.is_offline() || self.is_dry_run() {
+            return Ok(());
+        }
+
+        thread::sleep(CHAT_OPEN_DELAY);
+        keyboard::type_line(text)
+    }
+}
+
+#[cfg(windows)]
+mod keyboard {
+    use std::mem::size_of;
+
+    use windows::Win32::UI::Input::KeyboardAndMouse::{
+        INPUT, INPUT_0, INPUT_KEYBOARD, KEYBD_EVENT_FLAGS, KEYBDINPUT, KEYEVENTF_KEYUP,
+        KEYEVENTF_UNICODE, SendInput, VIRTUAL_KEY, VK_RETURN,
+    };
+
+    use crate::{BroadcastError, BroadcastMessageType, Result};
+
+    /// Type `text` into the foreground window and press Enter.
+    pub(super) fn type_line(text: &str) -> Result<()> {
+        let inputs = inputs(text);
+        // Safety: every INPUT is a fully initialized keyboard event, and the
+        // size passed is the size of the element type.
+        let sent = unsafe { SendInput(&inputs, size_of::<INPUT>() as i32) };
+
+        if sent as usize != inputs.len() {
+            return Err(BroadcastError::windows_api_error(
+                "SendInput",
+                Some(BroadcastMessageType::ChatCommand),
+                windows_core::Error::from_thread(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Key down and up events for every UTF-16 unit of `text`, then Enter.
+    pub(super) fn inputs(text: &str) -> Vec<INPUT> {
+        text.encode_utf16()
+            .flat_map(|unit| press(VIRTUAL_KEY(0), unit, KEYEVENTF_UNICODE))
+            .chain(press(VK_RETURN, 0, KEYBD_EVENT_FLAGS(0)))
+            .collect()
+    }
+
+    fn press(key: VIRTUAL_KEY, scan: u16, flags: KEYBD_EVENT_FLAGS) -> [INPUT; 2] {
+        let event = |flags| INPUT {
+            r#type: INPUT_KEYBOARD,
+            Anonymous: INPUT_0 {
+                ki: KEYBDINPUT {
+                    wVk: key,
+                    wScan: scan,
+                    dwFlags: flags,
+                    time: 0,
+                    dwExtraInfo: 0,
+                },
+            },
+        };
+        [event(flags), event(flags | KEYEVENTF_KEYUP)]
+    }
+}
+
+#[cfg(not(windows))]
+mod keyboard {
+    use crate::{BroadcastError, Result};
+
+    /// Keyboard input cannot be synthesized off Windows.
+    pub(super) fn type_line(_text: &str) -> Result<()> {
+        Err(BroadcastError::unsupported_platform(
+            "Chat text input",
+            "Windows",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::BroadcastMessage;
+
+    #[test]
+    fn test_rejects_invalid_text() {
+        let client = Client::offline();
+
+        for text in ["", "good\nluck", "tab\there"] {
+            assert!(matches!(
+                client.send_chat_text(text),
+                Err(BroadcastError::InvalidParameter { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_offline_only_opens_chat() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let client = Client::offline().with_observer({
+            let sent = Arc::clone(&sent);
+            move |message, _| sent.lock().unwrap().push(*message)
+        });
+
+        client.send_chat_text("good luck").unwrap();
+        assert_eq!(
+            *sent.lock().unwrap(),
+            [BroadcastMessage::ChatCommand(ChatCommandMode::Begin)]
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_unsupported_platform() {
+        assert!(matches!(
+            Client::from_message_id(1).send_chat_text("good luck"),
+            Err(BroadcastError::UnsupportedPlatform { .. })
+        ));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_input_sequence() {
+        use windows::Win32::UI::Input::KeyboardAndMouse::{
+            KEYEVENTF_KEYUP, KEYEVENTF_UNICODE, VK_RETURN,
+        };
+
+        // Three characters, a surrogate pair and Enter, each down and up.
+        let inputs = keyboard::inputs("gg \u{1F3C1}");
+        assert_eq!(inputs.len(), 12);
+
+        let keys: Vec<_> = inputs
+            .iter()
+            .map(|input| unsafe { input.Anonymous.ki })
+            .collect();
+        assert_eq!(keys[0].wScan, u16::from(b'g'));
+        assert_eq!(keys[0].dwFlags, KEYEVENTF_UNICODE);
+        assert_eq!(keys[1].dwFlags, KEYEVENTF_UNICODE | KEYEVENTF_KEYUP);
+        assert_eq!((keys[6].wScan, keys[8].wScan), (0xD83C, 0xDFC1));
+        assert_eq!(keys[10].wVk, VK_RETURN);
+        assert!(!keys[10].dwFlags.contains(KEYEVENTF_KEYUP));
+        assert!(keys[11].dwFlags.contains(KEYEVENTF_KEYUP));
+    }
+}
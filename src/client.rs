@@ -1,14 +1,22 @@
+use std::{fmt, sync::Arc, time::Duration};
+
 use crate::{
-    BroadcastError, BroadcastMessageType, CameraState, ChatCommandMode, PitCommandMode,
-    ReplayPositionMode, ReplaySearchMode, Result, TelemetryCommandMode, VideoCaptureMode,
-    util::pad_car_number,
+    BroadcastError, BroadcastMessage, BroadcastMessageType, CameraState, ChatCommandMode,
+    ClientBuilder, PitCommandMode, RateLimitMode, ReloadTexturesMode, ReplayPositionMode,
+    ReplaySearchMode, ReplaySpeed, Result, RetryPolicy, Target, TelemetryCommandMode,
+    VideoCaptureMode, rate_limit::RateLimiter, util::pack,
 };
 
 #[cfg(windows)]
 use {
+    std::sync::atomic::{AtomicU32, Ordering},
     windows::Win32::{
-        Foundation::{LPARAM, WPARAM},
-        UI::WindowsAndMessaging::{HWND_BROADCAST, RegisterWindowMessageW, SendNotifyMessageW},
+        Foundation::{ERROR_INVALID_MESSAGE, HWND, LPARAM, WIN32_ERROR, WPARAM},
+        UI::WindowsAndMessaging::{
+            FindWindowExW, FindWindowW, GetWindowThreadProcessId, HWND_BROADCAST, PostMessageW,
+            RegisterWindowMessageW, SMTO_ABORTIFHUNG, SendMessageTimeoutW, SendMessageW,
+            SendNotifyMessageW,
+        },
     },
     windows::core::PCWSTR,
 };
@@ -16,6 +24,10 @@ use {
 #[cfg(windows)]
 const BROADCAST_MESSAGE_NAME: &str = r"IRSDK_BROADCASTMSG";
 
+/// Window class registered by the iRacing simulator's main window.
+#[cfg(windows)]
+const IRACING_WINDOW_CLASS: &str = r"SimWinClass";
+
 #[cfg(windows)]
 fn wide_string(s: &str) -> Vec<u16> {
     use std::ffi::OsStr;
@@ -26,191 +38,706 @@ fn wide_string(s: &str) -> Vec<u16> {
         .collect()
 }
 
+/// A value [`Client`] can send: a [`BroadcastMessage`], anything
+/// convertible into one, such as a mode enum or a type declared with
+/// [`broadcast_message!`](crate::broadcast_message), or a reference to a
+/// message.
 pub trait BroadcastMessageProvider {
+    /// The packed message type and words; see [`BroadcastMessage::encode`].
     fn to_message(self) -> (BroadcastMessageType, u16, u16, u16);
+
+    /// Run `f` with the typed message, converting only if needed.
+    fn with_message<R>(self, f: impl FnOnce(&BroadcastMessage) -> R) -> R;
 }
 
-/// Messages that can be sent to the iRacing simulation.
-///
-/// Each variant maps to the documented window message contract in the iRacing
-/// SDK. Primitive parameters are passed through as-is and packed into the
-/// `WPARAM`/`LPARAM` pairs expected by the simulator.
+/// Anything convertible into a [`BroadcastMessage`] can be sent directly, so
+/// `client.send_message(PitCommandMode::Tearoff)` works without wrapping the
+/// mode in its variant.
+impl<T: Into<BroadcastMessage>> BroadcastMessageProvider for T {
+    fn to_message(self) -> (BroadcastMessageType, u16, u16, u16) {
+        self.into().encode()
+    }
+
+    fn with_message<R>(self, f: impl FnOnce(&BroadcastMessage) -> R) -> R {
+        f(&self.into())
+    }
+}
+
+/// Borrowed messages can be sent without giving them up, e.g. to send the
+/// same message twice or log it afterwards.
+impl BroadcastMessageProvider for &BroadcastMessage {
+    fn to_message(self) -> (BroadcastMessageType, u16, u16, u16) {
+        self.encode()
+    }
+
+    fn with_message<R>(self, f: impl FnOnce(&BroadcastMessage) -> R) -> R {
+        f(self)
+    }
+}
+
+/// Callback registered with [`Client::with_observer`].
+pub(crate) type Observer = dyn Fn(&BroadcastMessage, (usize, isize)) + Send + Sync;
+
+/// Observers shared by a [`Client`] and its clones.
+#[derive(Clone, Default)]
+struct Observers(Vec<Arc<Observer>>);
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Observers({})", self.0.len())
+    }
+}
+
+/// Validate a packed message for a dry run without delivering it.
+fn rehearse(wparam: usize, lparam: isize) -> Result<()> {
+    let (message_type, var1, var2, var3) = BroadcastMessage::decode(wparam, lparam)?;
+    validate_params(&message_type, var1, var2, var3)?;
+
+    #[cfg(feature = "tracing")]
+    tracing::info!(
+        ?message_type,
+        wparam,
+        lparam,
+        "dry run: broadcast message not sent"
+    );
+
+    Ok(())
+}
+
+/// Check parameter words against what the simulator understands for
+/// `message_type`.
+fn validate_params(
+    message_type: &BroadcastMessageType,
+    var1: u16,
+    var2: u16,
+    var3: u16,
+) -> Result<()> {
+    let in_range = |count: usize| (var1 as usize) < count;
+    let valid = match message_type {
+        BroadcastMessageType::CameraSwitchPosition
+        | BroadcastMessageType::CameraSwitchNumber
+        | BroadcastMessageType::FFBCommand
+        | BroadcastMessageType::ReplaySearchSessionTime => true,
+        BroadcastMessageType::CameraSetState => CameraState::from_bits(var1.into()).is_some(),
+        BroadcastMessageType::ReplaySetPlaySpeed => {
+            (var1 as i16).unsigned_abs() <= ReplaySpeed::MAX.into() && var2 <= 1
+        }
+        BroadcastMessageType::ReplaySetPlayPosition => in_range(ReplayPositionMode::ALL.len()),
+        BroadcastMessageType::ReplaySearch => in_range(ReplaySearchMode::ALL.len()),
+        // The SDK defines a single replay state command, erase tape.
+        BroadcastMessageType::ReplaySetState => var1 == 0,
+        BroadcastMessageType::ReloadTextures => in_range(ReloadTexturesMode::ALL.len()),
+        BroadcastMessageType::ChatCommand => {
+            in_range(ChatCommandMode::ALL.len())
+                && (var1 != u16::from(ChatCommandMode::Macro)
+                    || u8::try_from(var2)
+                        .is_ok_and(|n| BroadcastMessage::CHAT_MACRO_RANGE.contains(&n)))
+        }
+        BroadcastMessageType::PitCommand => var1 <= PitCommandMode::ClearFuel.encode().0,
+        BroadcastMessageType::TelemetryCommand => in_range(TelemetryCommandMode::ALL.len()),
+        BroadcastMessageType::VideoCapture => in_range(VideoCaptureMode::ALL.len()),
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(BroadcastError::invalid_parameter(
+            format!("{message_type:?} parameters"),
+            format!("({var1}, {var2}, {var3}) are not understood by the simulator"),
+        ))
+    }
+}
+
+/// Reject names `RegisterWindowMessageW` cannot register as given.
+fn check_message_name(name: &str) -> Result<()> {
+    if name.is_empty() || name.contains('\0') {
+        return Err(BroadcastError::invalid_parameter(
+            "broadcast message name",
+            format!("{name:?} must be non-empty and contain no NUL characters"),
+        ));
+    }
+    Ok(())
+}
+
+/// Shared send path for every [`Client`]: encodes the message and hands it
+/// to [`dispatch_raw`].
 ///
-/// # Examples
+/// With the `tracing` feature enabled, each send is wrapped in a debug span
+/// carrying the message type.
+fn dispatch<M, F>(message: M, deliver: F) -> Result<()>
+where
+    M: BroadcastMessageProvider,
+    F: FnOnce(usize, isize) -> Result<()>,
+{
+    let (broadcast_type, var1, var2, var3) = message.to_message();
+
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("send_message", message_type = ?broadcast_type).entered();
+
+    dispatch_raw(broadcast_type as u16, var1, var2, var3, deliver)
+}
+
+/// Packs the low/high words and hands the packed `(WPARAM, LPARAM)` values
+/// to `deliver`, reporting the outcome when `tracing` or `metrics` is
+/// enabled.
+fn dispatch_raw<F>(msg_type: u16, var1: u16, var2: u16, var3: u16, deliver: F) -> Result<()>
+where
+    F: FnOnce(usize, isize) -> Result<()>,
+{
+    let (wparam, lparam) = pack(msg_type, var1, var2, var3);
+
+    let result = deliver(wparam, lparam);
+
+    #[cfg(feature = "tracing")]
+    match &result {
+        Ok(()) => tracing::debug!(wparam, lparam, "broadcast message sent"),
+        Err(error) => tracing::debug!(wparam, lparam, %error, "broadcast message failed"),
+    }
+
+    #[cfg(feature = "metrics")]
+    count_send(msg_type, &result);
+
+    result
+}
+
+/// Name of the counter [`count_send`] increments.
+#[cfg(feature = "metrics")]
+const MESSAGES_COUNTER: &str = "iracing_broadcast_messages_total";
+
+/// Count one send by message type and outcome. Offline and dry-run sends
+/// count as sent; raw sends of an unknown type are labeled `unknown`.
+#[cfg(feature = "metrics")]
+fn count_send(msg_type: u16, result: &Result<()>) {
+    let message_type = BroadcastMessageType::try_from(u32::from(msg_type)).map_or_else(
+        |_| "unknown".to_owned(),
+        |message_type| format!("{message_type:?}"),
+    );
+    let outcome = if result.is_ok() { "sent" } else { "failed" };
+
+    metrics::counter!(MESSAGES_COUNTER, "message_type" => message_type, "outcome" => outcome)
+        .increment(1);
+}
+
+/// How [`Client::send_with_outcome`] sends a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendMode {
+    /// Queue the message and return at once, like [`Client::send_message`].
+    Notify,
+    /// Wait up to the timeout for the sim to process the message, like
+    /// [`Client::send_message_blocking`].
+    Blocking(Duration),
+}
+
+/// The Win32 call that delivers non-blocking sends; see
+/// [`Client::set_delivery`].
 ///
-/// ```
-/// use iracing_broadcast::BroadcastMessage;
+/// The sim processes a broadcast message the same way whichever call
+/// delivered it. The calls differ in what the sender waits for:
 ///
-/// let _ = BroadcastMessage::CameraSwitchPosition(0, 0, 0);
-/// let _ = BroadcastMessage::CameraSwitchNumber("001", 0, 0);
-/// ```
-pub enum BroadcastMessage {
-    /// Switch to a specific camera group and camera index for a position.
-    CameraSwitchPosition(u8, u8, u8),
-    /// Switch to a specific camera group and camera index for a car number.
-    CameraSwitchNumber(&'static str, u8, u8),
-    /// Apply a new [`CameraState`] bitfield.
-    CameraSetState(CameraState),
-    /// Set the replay play speed, with an optional slow-motion toggle.
-    ReplaySetPlaySpeed(u8, bool),
-    /// Jump to a replay position, with the frame number encoded in `var2`.
-    ReplaySetPlayPosition(ReplayPositionMode, u16),
-    /// Perform a replay search according to the provided mode.
-    ReplaySearch(ReplaySearchMode),
-    /// Toggle the replay state on or off.
-    ReplaySetState,
-    /// Reload all textures.
-    ReloadAllTextures,
-    /// Reload textures for a specific car index.
-    ReloadTextures(u8),
-    /// Send a chat command.
-    ChatCommand(ChatCommandMode),
-    /// Send a chat macro by number.
-    ChatCommandMacro(u8),
-    /// Issue a pit command.
-    PitCommand(PitCommandMode),
-    /// Control telemetry recording.
-    TelemetryCommand(TelemetryCommandMode),
-    /// Send a force-feedback command.
-    FFBCommand(u16),
-    /// Search a replay to a specific session time.
-    ReplaySearchSessionTime(u8, u16),
-    /// Control video capture.
-    VideoCapture(VideoCaptureMode),
+/// - [`Delivery::Notify`] (`SendNotifyMessageW`, the default, as in the SDK)
+///   returns at once for windows of other threads, but runs the window
+///   procedure before returning for windows owned by the calling thread.
+/// - [`Delivery::Post`] (`PostMessageW`) always queues the message and
+///   returns, even for the caller's own windows. A full message queue
+///   fails the send instead of delivering it late.
+/// - [`Delivery::Send`] (`SendMessageW`) waits until every receiving window
+///   has processed the message. A hung window, and with
+///   [`Target::Broadcast`] that is any top-level window on the desktop,
+///   blocks the caller indefinitely, and failures are not reported. Prefer
+///   [`Client::send_message_blocking`], which gives up after a timeout.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    /// `SendNotifyMessageW`.
+    #[default]
+    Notify,
+    /// `PostMessageW`.
+    Post,
+    /// `SendMessageW`.
+    Send,
 }
 
-impl BroadcastMessageProvider for BroadcastMessage {
-    fn to_message(self) -> (BroadcastMessageType, u16, u16, u16) {
+impl Delivery {
+    /// The Win32 function this delivery calls, as reported in
+    /// `BroadcastError::WindowsApi` errors.
+    pub fn api_name(self) -> &'static str {
         match self {
-            BroadcastMessage::CameraSwitchPosition(position, group, camera) => (
-                BroadcastMessageType::CameraSwitchPosition,
-                position.into(),
-                group.into(),
-                camera.into(),
-            ),
-            BroadcastMessage::CameraSwitchNumber(car_number, group, camera) => (
-                BroadcastMessageType::CameraSwitchNumber,
-                pad_car_number(&car_number),
-                group.into(),
-                camera.into(),
-            ),
-            BroadcastMessage::CameraSetState(camera_state) => (
-                BroadcastMessageType::CameraSetState,
-                camera_state.bits() as u16,
-                0,
-                0,
-            ),
-            BroadcastMessage::ReplaySetPlaySpeed(speed, slow_motion) => (
-                BroadcastMessageType::ReplaySetPlaySpeed,
-                speed.into(),
-                slow_motion.into(),
-                0,
-            ),
-            BroadcastMessage::ReplaySetPlayPosition(mode, frame_number) => (
-                BroadcastMessageType::ReplaySetPlayPosition,
-                mode.into(),
-                frame_number.into(),
-                0,
-            ),
-            BroadcastMessage::ReplaySearch(mode) => {
-                (BroadcastMessageType::ReplaySearch, mode.into(), 0, 0)
-            }
-            BroadcastMessage::ReplaySetState => (BroadcastMessageType::ReplaySetState, 0, 0, 0),
-            BroadcastMessage::ReloadAllTextures => (BroadcastMessageType::ReloadTextures, 0, 0, 0),
-            BroadcastMessage::ReloadTextures(car_index) => {
-                (BroadcastMessageType::ReloadTextures, car_index.into(), 0, 0)
-            }
-            BroadcastMessage::ChatCommand(mode) => {
-                (BroadcastMessageType::ChatCommand, mode.into(), 0, 0)
-            }
-            BroadcastMessage::ChatCommandMacro(macro_number) => (
-                BroadcastMessageType::ChatCommand,
-                ChatCommandMode::Macro.into(),
-                macro_number.into(),
-                0,
-            ),
-            BroadcastMessage::PitCommand(pit_command_mode) => {
-                let (var1, var2) = pit_command_mode.encode();
-                (BroadcastMessageType::PitCommand, var1, var2, 0)
-            }
-            BroadcastMessage::TelemetryCommand(mode) => {
-                (BroadcastMessageType::TelemetryCommand, mode.into(), 0, 0)
-            }
-            BroadcastMessage::FFBCommand(_value) => (
-                BroadcastMessageType::FFBCommand,
-                0,
-                0, // (value * 65536).into(),
-                0,
-            ),
-            BroadcastMessage::ReplaySearchSessionTime(session_number, session_time_ms) => (
-                BroadcastMessageType::ReplaySearchSessionTime,
-                session_number.into(),
-                session_time_ms,
-                0,
-            ),
-            BroadcastMessage::VideoCapture(mode) => {
-                (BroadcastMessageType::VideoCapture, mode.into(), 0, 0)
-            }
+            Delivery::Notify => "SendNotifyMessageW",
+            Delivery::Post => "PostMessageW",
+            Delivery::Send => "SendMessageW",
         }
     }
 }
 
+/// What is known about a message after [`Client::send_with_outcome`].
+///
+/// Broadcast messages get no reply, so a plain `Ok(())` from
+/// [`Client::send_message`] only means Windows accepted the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SendOutcome {
+    /// The sim's window processed the message.
+    Delivered,
+    /// Windows queued the message for a running sim; whether the sim acted
+    /// on it is unknown.
+    Dispatched,
+    /// No sim window was there to receive the message.
+    NoListener,
+}
+
 #[cfg(windows)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 /// Handle for sending broadcast messages to a running iRacing simulator.
 ///
 /// The client registers the well-known broadcast window message and can then
 /// dispatch typed messages via [`send_message`]. All methods are Windows-only
 /// because the simulator relies on the Win32 messaging subsystem.
 pub struct Client {
-    message_id: u32,
+    registration: Arc<Registration>,
+    offline: bool,
+    dry_run: bool,
+    auto_refresh: bool,
+    delivery: Delivery,
+    observers: Observers,
+    rate_limiter: Option<RateLimiter>,
+    target: Target,
+}
+
+/// The broadcast message name and its registered id, shared by a [`Client`]
+/// and its clones so [`Client::refresh`] updates all of them.
+#[cfg(windows)]
+#[derive(Debug)]
+struct Registration {
+    name: String,
+    id: AtomicU32,
+}
+
+#[cfg(windows)]
+impl Registration {
+    fn new(name: &str, id: u32) -> Arc<Self> {
+        Arc::new(Registration {
+            name: name.to_owned(),
+            id: AtomicU32::new(id),
+        })
+    }
+}
+
+/// Register the window message `name`, returning its id.
+#[cfg(windows)]
+fn register_message(name: &str) -> Result<u32> {
+    let message: Vec<u16> = wide_string(name);
+
+    let id = unsafe { RegisterWindowMessageW(PCWSTR::from_raw(message.as_ptr())) };
+
+    if id == 0 {
+        return Err(BroadcastError::connection_failed(format!(
+            "Failed to register broadcast window message '{name}'"
+        )));
+    }
+    Ok(id)
 }
 
 #[cfg(windows)]
 impl Client {
     /// Register the broadcast window message and create a sender handle.
     pub fn new() -> Result<Self> {
-        let message: Vec<u16> = wide_string(BROADCAST_MESSAGE_NAME);
+        Self::with_message_name(BROADCAST_MESSAGE_NAME)
+    }
+
+    /// Like [`Client::new`], registering `name` instead of the SDK's
+    /// `IRSDK_BROADCASTMSG`.
+    ///
+    /// The simulator only listens for its own name, so this is for fake
+    /// receivers in tests and for SDK forks. Processes that register the same
+    /// name get the same message id.
+    ///
+    /// Returns [`BroadcastError::InvalidParameter`] if `name` is empty or
+    /// contains a NUL character.
+    pub fn with_message_name(name: &str) -> Result<Self> {
+        check_message_name(name)?;
+        let id = register_message(name)?;
 
-        let id = unsafe { RegisterWindowMessageW(PCWSTR::from_raw(message.as_ptr())) };
+        Ok(Client {
+            registration: Registration::new(name, id),
+            offline: false,
+            dry_run: false,
+            auto_refresh: false,
+            delivery: Delivery::default(),
+            observers: Observers::default(),
+            rate_limiter: None,
+            target: Target::Broadcast,
+        })
+    }
 
-        if id == 0 {
-            return Err(BroadcastError::connection_failed(format!(
-                "Failed to register broadcast window message '{}'",
-                BROADCAST_MESSAGE_NAME
-            )));
+    /// Create a client that never reaches the simulator.
+    ///
+    /// No window message is registered, and every send encodes the message
+    /// and then returns `Ok(())` without calling into Win32. Useful for
+    /// integration harnesses that exercise the full send path.
+    pub fn offline() -> Self {
+        Client {
+            registration: Registration::new(BROADCAST_MESSAGE_NAME, 0),
+            offline: true,
+            dry_run: false,
+            auto_refresh: false,
+            delivery: Delivery::default(),
+            observers: Observers::default(),
+            rate_limiter: None,
+            target: Target::Broadcast,
         }
+    }
 
-        Ok(Client { message_id: id })
+    /// Create a client from a broadcast message id registered elsewhere.
+    ///
+    /// `id` must be the value `RegisterWindowMessageW("IRSDK_BROADCASTMSG")`
+    /// returned, e.g. in another process sharing the registration; any other
+    /// value sends a message the simulator does not listen for. Registered
+    /// ids are identical across processes for the lifetime of the session.
+    /// [`Client::refresh`] registers `IRSDK_BROADCASTMSG` itself.
+    pub fn from_message_id(id: u32) -> Self {
+        Client {
+            registration: Registration::new(BROADCAST_MESSAGE_NAME, id),
+            offline: false,
+            dry_run: false,
+            auto_refresh: false,
+            delivery: Delivery::default(),
+            observers: Observers::default(),
+            rate_limiter: None,
+            target: Target::Broadcast,
+        }
+    }
+
+    /// The registered broadcast window message id, or `0` for an
+    /// [offline](Client::offline) client.
+    pub fn message_id(&self) -> u32 {
+        self.registration.id.load(Ordering::Relaxed)
+    }
+
+    /// Register the broadcast message name again and use the id Windows
+    /// returns from now on, in this client and all its clones.
+    ///
+    /// Windows keeps registered window messages until the user logs off, so
+    /// an id should stay valid for the client's lifetime. If the atom table
+    /// was exhausted and recycled, though, an old id can stop reaching the
+    /// sim; registering again yields the current one. See
+    /// [`Client::set_auto_refresh`] to do this on a failing send.
+    ///
+    /// Returns the new id, or `0` without registering for an
+    /// [offline](Client::offline) client. Fails like [`Client::new`].
+    pub fn refresh(&self) -> Result<u32> {
+        if self.offline {
+            return Ok(0);
+        }
+
+        let id = register_message(&self.registration.name)?;
+        self.registration.id.store(id, Ordering::Relaxed);
+        Ok(id)
+    }
+
+    /// Whether this client was created with [`Client::offline`].
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Locate the running iRacing simulator's main window.
+    ///
+    /// Returns [`BroadcastError::Connection`] if no simulator window exists,
+    /// which makes this a cheap "is iRacing up?" check as well as a source of
+    /// handles for [`Client::send_message_to`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(windows)]
+    /// # fn main() -> iracing_broadcast::Result<()> {
+    /// use iracing_broadcast::{BroadcastMessage, Client, ReplaySearchMode};
+    ///
+    /// let client = Client::new()?;
+    /// let hwnd = Client::find_iracing_window()?;
+    /// client.send_message_to(hwnd, BroadcastMessage::ReplaySearch(ReplaySearchMode::ToEnd))?;
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(windows))]
+    /// # fn main() {}
+    /// ```
+    pub fn find_iracing_window() -> Result<HWND> {
+        let class_name = wide_string(IRACING_WINDOW_CLASS);
+
+        // Safety: `class_name` is a NUL-terminated UTF-16 buffer that outlives
+        // the call, and a null window name matches any title.
+        unsafe { FindWindowW(PCWSTR::from_raw(class_name.as_ptr()), PCWSTR::null()) }.map_err(
+            |_| {
+                BroadcastError::connection_failed(format!(
+                    "No iRacing simulator window ('{}') was found",
+                    IRACING_WINDOW_CLASS
+                ))
+            },
+        )
+    }
+
+    /// Whether the iRacing simulator window currently exists.
+    ///
+    /// Broadcasts succeed even when nothing is listening, so call this first
+    /// to give users real feedback when the sim is not running.
+    pub fn is_iracing_running() -> bool {
+        Self::find_iracing_window().is_ok()
+    }
+
+    /// Locate the simulator window owned by process `pid`.
+    ///
+    /// Unlike [`Client::find_iracing_window`], which returns whichever
+    /// simulator window Windows finds first, this picks one instance when
+    /// several are running. Returns [`BroadcastError::Connection`] if `pid`
+    /// owns no simulator window.
+    pub fn find_iracing_window_for_pid(pid: u32) -> Result<HWND> {
+        let class_name = wide_string(IRACING_WINDOW_CLASS);
+        let mut after = None;
+
+        // Safety: as in `find_iracing_window`; each found handle is only
+        // passed back to Win32, which validates it.
+        while let Ok(hwnd) = unsafe {
+            FindWindowExW(
+                None,
+                after,
+                PCWSTR::from_raw(class_name.as_ptr()),
+                PCWSTR::null(),
+            )
+        } {
+            let mut owner = 0;
+            unsafe { GetWindowThreadProcessId(hwnd, Some(&mut owner)) };
+            if owner == pid {
+                return Ok(hwnd);
+            }
+            after = Some(hwnd);
+        }
+
+        Err(BroadcastError::connection_failed(format!(
+            "No iRacing simulator window ('{IRACING_WINDOW_CLASS}') belongs to process {pid}"
+        )))
     }
 
     /// Send a broadcast message to the iRacing simulator.
+    ///
+    /// Accepts a [`BroadcastMessage`] or anything convertible into one, such
+    /// as a bare [`PitCommandMode`].
+    ///
+    /// The message goes to the client's [`Target`], every top-level window
+    /// by default.
     pub fn send_message<M: BroadcastMessageProvider>(&self, message: M) -> Result<()> {
-        let (broadcast_type, var1, var2, var3) = message.to_message();
-        // Pack the low/high words to match the Windows broadcast contract.
-        let wparam_value = broadcast_type as usize | ((var1 as usize) << 16);
-        let lparam_value = var2 as isize | ((var3 as isize) << 16);
+        self.dispatch(message, |wparam_value, lparam_value| {
+            self.notify_target(wparam_value, lparam_value)
+        })
+    }
+
+    /// Like [`Client::send_message`], but fails with
+    /// [`BroadcastError::Connection`] when the simulator is not running
+    /// instead of broadcasting into the void.
+    ///
+    /// [Offline](Client::offline) and [dry-run](Client::set_dry_run)
+    /// clients skip the check.
+    pub fn send_message_checked<M: BroadcastMessageProvider>(&self, message: M) -> Result<()> {
+        if !self.offline && !self.dry_run {
+            Self::find_iracing_window()?;
+        }
+
+        self.send_message(message)
+    }
+
+    /// Broadcast an arbitrary message type and parameter words.
+    ///
+    /// This is an escape hatch for message types the SDK has added before
+    /// this crate models them. Nothing is validated: the words are packed
+    /// exactly like [`Client::send_message`] packs a typed message, and the
+    /// simulator silently ignores types it does not understand. Prefer the
+    /// typed API whenever it covers the message, as this method may change
+    /// without notice.
+    pub fn send_raw(&self, msg_type: u16, var1: u16, var2: u16, var3: u16) -> Result<()> {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!("send_raw", msg_type).entered();
+
+        self.throttle()?;
+        dispatch_raw(msg_type, var1, var2, var3, |wparam_value, lparam_value| {
+            self.notify_target(wparam_value, lparam_value)
+        })
+    }
+
+    /// Send a message to a specific window instead of broadcasting it.
+    ///
+    /// Use this when the iRacing main window handle is already known, so the
+    /// message is not delivered to every top-level window on the machine.
+    /// Passing [`HWND_BROADCAST`] is equivalent to [`Client::send_message`].
+    ///
+    /// The handle is not dereferenced by this crate; a stale or invalid handle
+    /// is reported by Windows and surfaces as
+    /// [`BroadcastError::WindowsApi`].
+    pub fn send_message_to<M: BroadcastMessageProvider>(
+        &self,
+        hwnd: HWND,
+        message: M,
+    ) -> Result<()> {
+        self.dispatch(message, |wparam_value, lparam_value| {
+            self.notify(hwnd, wparam_value, lparam_value)
+        })
+    }
 
+    /// Send a message only to the simulator running as process `pid`.
+    ///
+    /// On machines running several simulator instances, this keeps the
+    /// message from reaching all of them. The window is looked up with
+    /// [`Client::find_iracing_window_for_pid`] on every send, so a
+    /// [`BroadcastError::Connection`] means that process has no simulator
+    /// window. [Offline](Client::offline) and
+    /// [dry-run](Client::set_dry_run) clients do not look for the window.
+    pub fn send_message_to_pid<M: BroadcastMessageProvider>(
+        &self,
+        pid: u32,
+        message: M,
+    ) -> Result<()> {
+        self.dispatch(message, |wparam_value, lparam_value| {
+            if let Some(result) = self.bypass(wparam_value, lparam_value) {
+                return result;
+            }
+
+            let hwnd = Self::find_iracing_window_for_pid(pid)?;
+            self.notify(hwnd, wparam_value, lparam_value)
+        })
+    }
+
+    /// Send a message to the simulator window and wait until it has been
+    /// processed.
+    ///
+    /// [`Client::send_message`] uses `SendNotifyMessageW`, which queues the
+    /// message and returns at once, so success only means Windows accepted
+    /// it. This method instead finds the simulator window and calls
+    /// `SendMessageTimeoutW` with `SMTO_ABORTIFHUNG`, blocking the calling
+    /// thread for up to `timeout` (whole milliseconds) until the sim's window
+    /// procedure has run. Keep it off UI and other latency-sensitive threads.
+    ///
+    /// Returns [`BroadcastError::Connection`] if the simulator is not running,
+    /// and [`BroadcastError::WindowsApi`] if the window is hung or does not
+    /// respond within `timeout`. [Offline](Client::offline) and
+    /// [dry-run](Client::set_dry_run) clients do not look for the window.
+    pub fn send_message_blocking<M: BroadcastMessageProvider>(
+        &self,
+        message: M,
+        timeout: Duration,
+    ) -> Result<()> {
+        let timeout_ms = u32::try_from(timeout.as_millis()).unwrap_or(u32::MAX);
+
+        self.dispatch(message, |wparam_value, lparam_value| {
+            if let Some(result) = self.bypass(wparam_value, lparam_value) {
+                return result;
+            }
+
+            let hwnd = Self::find_iracing_window()?;
+
+            // Safety: same contract as `notify`; the result pointer is
+            // optional and not requested.
+            let result = unsafe {
+                SendMessageTimeoutW(
+                    hwnd,
+                    self.message_id(),
+                    WPARAM(wparam_value),
+                    LPARAM(lparam_value),
+                    SMTO_ABORTIFHUNG,
+                    timeout_ms,
+                    None,
+                )
+            };
+
+            if result.0 == 0 {
+                return Err(BroadcastError::windows_api_error(
+                    "SendMessageTimeoutW",
+                    message_type_of(wparam_value),
+                    windows_core::Error::from_thread(),
+                ));
+            }
+            Ok(())
+        })
+    }
+
+    /// [`Client::notify`] the window(s) selected by the client's target.
+    fn notify_target(&self, wparam_value: usize, lparam_value: isize) -> Result<()> {
+        if let Some(result) = self.bypass(wparam_value, lparam_value) {
+            return result;
+        }
+
+        let hwnd = match self.target {
+            Target::Broadcast => HWND_BROADCAST,
+            Target::Process(pid) => Self::find_iracing_window_for_pid(pid)?,
+        };
+        self.notify(hwnd, wparam_value, lparam_value)
+    }
+
+    fn notify(&self, hwnd: HWND, wparam_value: usize, lparam_value: isize) -> Result<()> {
+        if let Some(result) = self.bypass(wparam_value, lparam_value) {
+            return result;
+        }
+
+        let mut result = self.post(hwnd, wparam_value, lparam_value);
+        if self.auto_refresh
+            && let Err(error) = &result
+            && WIN32_ERROR::from_error(error) == Some(ERROR_INVALID_MESSAGE)
+        {
+            self.refresh()?;
+            result = self.post(hwnd, wparam_value, lparam_value);
+        }
+
+        result.map_err(|e| {
+            BroadcastError::windows_api_error(
+                self.delivery.api_name(),
+                message_type_of(wparam_value),
+                e,
+            )
+        })
+    }
+
+    /// Hand the message to Windows with the client's [`Delivery`].
+    fn post(
+        &self,
+        hwnd: HWND,
+        wparam_value: usize,
+        lparam_value: isize,
+    ) -> windows_core::Result<()> {
         unsafe {
-            // Safety: iRacing expects these messages to be delivered to
-            // HWND_BROADCAST using the ID obtained from RegisterWindowMessageW.
-            // All parameter packing matches the documented protocol, so the
-            // Win32 API receives well-formed data.
-            SendNotifyMessageW(
-                HWND_BROADCAST,
-                self.message_id,
+            // Safety: iRacing expects these messages to use the ID obtained
+            // from RegisterWindowMessageW. All parameter packing matches the
+            // documented protocol, so the Win32 API receives well-formed data,
+            // and Windows validates the target handle itself.
+            let (id, wparam, lparam) = (
+                self.message_id(),
                 WPARAM(wparam_value),
                 LPARAM(lparam_value),
-            )
-            .map_err(|e| BroadcastError::windows_api_error("SendNotifyMessageW", e))
+            );
+            match self.delivery {
+                Delivery::Notify => SendNotifyMessageW(hwnd, id, wparam, lparam),
+                Delivery::Post => PostMessageW(Some(hwnd), id, wparam, lparam),
+                Delivery::Send => {
+                    SendMessageW(hwnd, id, Some(wparam), Some(lparam));
+                    Ok(())
+                }
+            }
         }
     }
 }
 
+/// The message type in the low word of a packed `WPARAM`, if this crate
+/// knows it; raw sends may use types it does not.
+#[cfg(windows)]
+fn message_type_of(wparam_value: usize) -> Option<BroadcastMessageType> {
+    BroadcastMessageType::try_from(wparam_value as u32 & 0xFFFF).ok()
+}
+
 // Non-windows stub
 #[cfg(not(windows))]
+#[derive(Debug, Clone)]
 pub struct Client {
-    _private: (),
+    message_id: u32,
+    offline: bool,
+    dry_run: bool,
+    auto_refresh: bool,
+    delivery: Delivery,
+    observers: Observers,
+    rate_limiter: Option<RateLimiter>,
+    target: Target,
 }
 
 #[cfg(not(windows))]
@@ -225,12 +752,462 @@ impl Client {
         ))
     }
 
-    pub fn send_message<M: BroadcastMessageProvider>(&self, _message: M) -> Result<()> {
+    /// Attempt to register a custom broadcast message name.
+    ///
+    /// Returns [`BroadcastError::InvalidParameter`] for an empty name or one
+    /// containing a NUL character, and otherwise always fails as message
+    /// events can only be sent on windows.
+    pub fn with_message_name(name: &str) -> Result<Self> {
+        check_message_name(name)?;
+        Err(BroadcastError::unsupported_platform(
+            "Broadcast Client",
+            "Windows",
+        ))
+    }
+
+    /// Create a client that never reaches the simulator.
+    ///
+    /// Unlike [`Client::new`], this succeeds on every platform: sends encode
+    /// the message and then return `Ok(())` without delivering it anywhere.
+    /// Useful for integration harnesses that exercise the full send path.
+    pub fn offline() -> Self {
+        Client {
+            message_id: 0,
+            offline: true,
+            dry_run: false,
+            auto_refresh: false,
+            delivery: Delivery::default(),
+            observers: Observers::default(),
+            rate_limiter: None,
+            target: Target::Broadcast,
+        }
+    }
+
+    /// Create a client from a broadcast message id registered elsewhere.
+    ///
+    /// `id` must be the value `RegisterWindowMessageW("IRSDK_BROADCASTMSG")`
+    /// returned on Windows. The client is not offline, so its sends still
+    /// fail with [`BroadcastError::UnsupportedPlatform`] here.
+    pub fn from_message_id(id: u32) -> Self {
+        Client {
+            message_id: id,
+            offline: false,
+            dry_run: false,
+            auto_refresh: false,
+            delivery: Delivery::default(),
+            observers: Observers::default(),
+            rate_limiter: None,
+            target: Target::Broadcast,
+        }
+    }
+
+    /// The broadcast window message id, or `0` for an
+    /// [offline](Client::offline) client.
+    pub fn message_id(&self) -> u32 {
+        self.message_id
+    }
+
+    /// Register the broadcast message name again.
+    ///
+    /// Returns `Ok(0)` for an [offline](Client::offline) client and
+    /// otherwise always fails as message ids can only be registered on
+    /// windows.
+    pub fn refresh(&self) -> Result<u32> {
+        if self.offline {
+            return Ok(0);
+        }
         Err(BroadcastError::unsupported_platform(
-            "Broadcast Client Send Message",
+            "Broadcast Client",
             "Windows",
         ))
     }
+
+    /// Whether this client was created with [`Client::offline`].
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Whether the iRacing simulator is running. Always `false` off Windows.
+    pub fn is_iracing_running() -> bool {
+        false
+    }
+
+    /// Send a broadcast message to the iRacing simulator.
+    ///
+    /// This always returns an error as message events can only be sent on
+    /// windows, unless the client is [offline](Client::offline) or in
+    /// [dry-run](Client::set_dry_run) mode.
+    pub fn send_message<M: BroadcastMessageProvider>(&self, message: M) -> Result<()> {
+        self.dispatch(message, |wparam_value, lparam_value| {
+            self.deliver("Broadcast Client Send Message", wparam_value, lparam_value)
+        })
+    }
+
+    /// Like [`Client::send_message`], but checks for a running simulator
+    /// first, which always fails off Windows.
+    ///
+    /// [Offline](Client::offline) and [dry-run](Client::set_dry_run)
+    /// clients skip the check.
+    pub fn send_message_checked<M: BroadcastMessageProvider>(&self, message: M) -> Result<()> {
+        self.dispatch(message, |wparam_value, lparam_value| {
+            self.deliver(
+                "Broadcast Client Send Message Checked",
+                wparam_value,
+                lparam_value,
+            )
+        })
+    }
+
+    /// Send a message and wait until the simulator has processed it.
+    ///
+    /// This always returns an error as message events can only be sent on
+    /// windows, unless the client is [offline](Client::offline) or in
+    /// [dry-run](Client::set_dry_run) mode.
+    pub fn send_message_blocking<M: BroadcastMessageProvider>(
+        &self,
+        message: M,
+        _timeout: Duration,
+    ) -> Result<()> {
+        self.dispatch(message, |wparam_value, lparam_value| {
+            self.deliver(
+                "Broadcast Client Send Message Blocking",
+                wparam_value,
+                lparam_value,
+            )
+        })
+    }
+
+    /// Send a message only to the simulator running as process `pid`.
+    ///
+    /// This always returns an error as message events can only be sent on
+    /// windows, unless the client is [offline](Client::offline) or in
+    /// [dry-run](Client::set_dry_run) mode.
+    pub fn send_message_to_pid<M: BroadcastMessageProvider>(
+        &self,
+        _pid: u32,
+        message: M,
+    ) -> Result<()> {
+        self.dispatch(message, |wparam_value, lparam_value| {
+            self.deliver(
+                "Broadcast Client Send Message To Pid",
+                wparam_value,
+                lparam_value,
+            )
+        })
+    }
+
+    /// Broadcast an arbitrary message type and parameter words.
+    ///
+    /// This always returns an error as message events can only be sent on
+    /// windows, unless the client is [offline](Client::offline) or in
+    /// [dry-run](Client::set_dry_run) mode.
+    pub fn send_raw(&self, msg_type: u16, var1: u16, var2: u16, var3: u16) -> Result<()> {
+        self.throttle()?;
+        dispatch_raw(msg_type, var1, var2, var3, |wparam_value, lparam_value| {
+            self.deliver("Broadcast Client Send Raw", wparam_value, lparam_value)
+        })
+    }
+
+    fn deliver(&self, feature: &str, wparam_value: usize, lparam_value: isize) -> Result<()> {
+        self.bypass(wparam_value, lparam_value)
+            .unwrap_or_else(|| Err(BroadcastError::unsupported_platform(feature, "Windows")))
+    }
+}
+
+impl Client {
+    /// Start configuring a client; see [`ClientBuilder`].
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
+    /// Choose where [`Client::send_message`] and [`Client::send_raw`] send.
+    ///
+    /// Off Windows, the target has no effect.
+    pub fn set_target(&mut self, target: Target) {
+        self.target = target;
+    }
+
+    /// Where sends go; see [`Client::set_target`].
+    pub fn target(&self) -> Target {
+        self.target
+    }
+
+    /// Rehearse sends instead of delivering them.
+    ///
+    /// A dry-run client encodes every message exactly like a live one and
+    /// checks the packed parameter words against the protocol (known modes,
+    /// chat macros in `1..=15`, replay speeds up to 16x, ...), but never
+    /// calls into Win32. Unlike an [offline](Client::offline) client, a
+    /// malformed message fails with [`BroadcastError::InvalidParameter`].
+    /// With the `tracing` feature, every rehearsed send is logged at info
+    /// level with its packed `WPARAM`/`LPARAM`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, Client};
+    ///
+    /// let mut client = Client::offline();
+    /// client.set_dry_run(true);
+    ///
+    /// assert!(client.send_message(BroadcastMessage::ChatCommandMacro(3)).is_ok());
+    /// assert!(client.send_message(BroadcastMessage::ChatCommandMacro(40)).is_err());
+    /// ```
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    /// Whether sends are only rehearsed; see [`Client::set_dry_run`].
+    pub fn is_dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    /// When a send fails because Windows rejects the message id
+    /// (`ERROR_INVALID_MESSAGE`), [refresh](Client::refresh) it once and
+    /// retry the send. Off by default.
+    ///
+    /// Registered ids normally stay valid until logoff, so this only guards
+    /// against a recycled registration in very long-running tools. Blocking
+    /// and raw sends are not retried.
+    pub fn set_auto_refresh(&mut self, auto_refresh: bool) {
+        self.auto_refresh = auto_refresh;
+    }
+
+    /// Whether stale message ids are refreshed on a failing send; see
+    /// [`Client::set_auto_refresh`].
+    pub fn is_auto_refresh(&self) -> bool {
+        self.auto_refresh
+    }
+
+    /// Choose the Win32 call behind [`Client::send_message`],
+    /// [`Client::send_raw`] and the other non-blocking sends; see
+    /// [`Delivery`] for the trade-offs. Defaults to [`Delivery::Notify`].
+    ///
+    /// [`Client::send_message_blocking`] always uses `SendMessageTimeoutW`,
+    /// and off Windows the delivery has no effect.
+    pub fn set_delivery(&mut self, delivery: Delivery) {
+        self.delivery = delivery;
+    }
+
+    /// The Win32 call non-blocking sends use; see [`Client::set_delivery`].
+    pub fn delivery(&self) -> Delivery {
+        self.delivery
+    }
+
+    /// Register a closure that sees every typed message and its packed
+    /// `(WPARAM, LPARAM)` right before delivery, on every platform.
+    ///
+    /// Observers run in registration order, also for
+    /// [offline](Client::offline) and [dry-run](Client::set_dry_run) sends,
+    /// and are shared with clones of the client. [`Client::send_raw`] is not
+    /// observed, as it has no typed message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::{Arc, Mutex};
+    /// use iracing_broadcast::{BroadcastMessage, Client, PitCommandMode};
+    ///
+    /// let seen = Arc::new(Mutex::new(Vec::new()));
+    /// let log = Arc::clone(&seen);
+    /// let client = Client::offline().with_observer(move |message, (wparam, lparam)| {
+    ///     log.lock().unwrap().push(format!("{message} ({wparam:#x}, {lparam:#x})"));
+    /// });
+    ///
+    /// client.send_message(PitCommandMode::Tearoff)?;
+    /// assert_eq!(seen.lock().unwrap()[0], "Tearoff (0x10009, 0x0)");
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn with_observer<F>(self, observer: F) -> Self
+    where
+        F: Fn(&BroadcastMessage, (usize, isize)) + Send + Sync + 'static,
+    {
+        self.with_shared_observer(Arc::new(observer))
+    }
+
+    pub(crate) fn with_shared_observer(mut self, observer: Arc<Observer>) -> Self {
+        self.observers.0.push(observer);
+        self
+    }
+
+    /// Limit how often this client and its clones send.
+    ///
+    /// Every send, including [offline](Client::offline) and
+    /// [dry-run](Client::set_dry_run) ones, must come at least `min_interval`
+    /// after the previous one. A send that comes too soon either sleeps until
+    /// the interval has passed or fails with [`BroadcastError::RateLimited`],
+    /// depending on `mode`. A zero interval removes the limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use iracing_broadcast::{BroadcastError, BroadcastMessage, Client, RateLimitMode};
+    ///
+    /// let mut client = Client::offline();
+    /// client.set_rate_limit(Duration::from_secs(60), RateLimitMode::Reject);
+    ///
+    /// client.send_message(BroadcastMessage::ReplaySetState)?;
+    /// assert!(matches!(
+    ///     client.send_message(BroadcastMessage::ReplaySetState),
+    ///     Err(BroadcastError::RateLimited { .. })
+    /// ));
+    /// # Ok::<(), BroadcastError>(())
+    /// ```
+    pub fn set_rate_limit(&mut self, min_interval: Duration, mode: RateLimitMode) {
+        self.rate_limiter = (!min_interval.is_zero()).then(|| RateLimiter::new(min_interval, mode));
+    }
+
+    /// The minimum interval and mode set with [`Client::set_rate_limit`], if
+    /// any.
+    pub fn rate_limit(&self) -> Option<(Duration, RateLimitMode)> {
+        self.rate_limiter
+            .as_ref()
+            .map(|limiter| (limiter.min_interval(), limiter.mode()))
+    }
+
+    /// Wait for or reject a send that would break the rate limit.
+    fn throttle(&self) -> Result<()> {
+        self.rate_limiter
+            .as_ref()
+            .map_or(Ok(()), RateLimiter::acquire)
+    }
+
+    /// [`dispatch`] with this client's observers notified before `deliver`.
+    fn dispatch<M, F>(&self, message: M, deliver: F) -> Result<()>
+    where
+        M: BroadcastMessageProvider,
+        F: FnOnce(usize, isize) -> Result<()>,
+    {
+        self.throttle()?;
+        message.with_message(|message| {
+            dispatch(message, |wparam_value, lparam_value| {
+                for observer in &self.observers.0 {
+                    observer(message, (wparam_value, lparam_value));
+                }
+                deliver(wparam_value, lparam_value)
+            })
+        })
+    }
+
+    /// The result of a send that must not reach Win32, or `None` to deliver
+    /// it for real.
+    fn bypass(&self, wparam_value: usize, lparam_value: isize) -> Option<Result<()>> {
+        if self.dry_run {
+            return Some(rehearse(wparam_value, lparam_value));
+        }
+
+        self.offline.then_some(Ok(()))
+    }
+
+    /// Send a message, retrying transient failures according to `policy`.
+    ///
+    /// Only [retryable](BroadcastError::is_retryable) errors are retried, so
+    /// an [`UnsupportedPlatform`](BroadcastError::UnsupportedPlatform) error
+    /// is returned immediately. If every attempt fails, the last error is
+    /// returned.
+    pub fn send_message_with_retry<M: BroadcastMessageProvider>(
+        &self,
+        message: M,
+        policy: RetryPolicy,
+    ) -> Result<()> {
+        message.with_message(|message| policy.run(|| self.send_message(message)))
+    }
+
+    /// Send a message and report how sure it is to have reached the sim.
+    ///
+    /// [`SendMode::Notify`] reports [`SendOutcome::Dispatched`] when a sim
+    /// window exists for the client's [`Target`], and
+    /// [`SendMode::Blocking`] reports [`SendOutcome::Delivered`] once the sim
+    /// processed the message. Either reports [`SendOutcome::NoListener`]
+    /// instead of failing when there is no sim window, as do
+    /// [offline](Client::offline) and [dry-run](Client::set_dry_run)
+    /// clients, which never reach a window. Other errors are returned as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, Client, SendMode, SendOutcome};
+    ///
+    /// let outcome = Client::offline()
+    ///     .send_with_outcome(BroadcastMessage::ReplaySetState, SendMode::Notify)?;
+    /// assert_eq!(outcome, SendOutcome::NoListener);
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn send_with_outcome<M: BroadcastMessageProvider>(
+        &self,
+        message: M,
+        mode: SendMode,
+    ) -> Result<SendOutcome> {
+        let sent = match mode {
+            SendMode::Notify => self.send_message(message).map(|()| SendOutcome::Dispatched),
+            SendMode::Blocking(timeout) => self
+                .send_message_blocking(message, timeout)
+                .map(|()| SendOutcome::Delivered),
+        };
+
+        match sent {
+            Ok(_) if self.offline || self.dry_run => Ok(SendOutcome::NoListener),
+            Ok(SendOutcome::Dispatched) if !self.has_sim_window() => Ok(SendOutcome::NoListener),
+            Err(BroadcastError::Connection { .. }) => Ok(SendOutcome::NoListener),
+            sent => sent,
+        }
+    }
+
+    /// Reload the textures of each car in `indices`, e.g. after their
+    /// liveries were updated on disk.
+    ///
+    /// Sends [`BroadcastMessage::reload_textures_range`] in order and stops
+    /// at the first failure, returning its error.
+    pub fn reload_textures_for(&self, indices: impl IntoIterator<Item = u8>) -> Result<()> {
+        BroadcastMessage::reload_textures_range(indices)
+            .into_iter()
+            .try_for_each(|message| self.send_message(message))
+    }
+
+    /// Whether a sim window exists for the client's target.
+    fn has_sim_window(&self) -> bool {
+        match self.target {
+            Target::Broadcast => Self::is_iracing_running(),
+            #[cfg(windows)]
+            Target::Process(pid) => Self::find_iracing_window_for_pid(pid).is_ok(),
+            #[cfg(not(windows))]
+            Target::Process(_) => false,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl Client {
+    /// Send a broadcast message without blocking the async runtime.
+    ///
+    /// The FFI call runs on tokio's blocking thread pool via
+    /// [`tokio::task::spawn_blocking`]; a clone of the client, sharing its
+    /// observers, is moved into the task. Requires the `async` feature and a running
+    /// tokio runtime.
+    pub async fn send_message_async<M>(&self, message: M) -> Result<()>
+    where
+        M: BroadcastMessageProvider + Send + 'static,
+    {
+        let client = self.clone();
+        run_blocking(move || client.send_message(message)).await
+    }
+}
+
+/// Run a blocking send on tokio's blocking pool, re-raising any panic from
+/// the task on the caller.
+#[cfg(feature = "async")]
+async fn run_blocking<F>(send: F) -> Result<()>
+where
+    F: FnOnce() -> Result<()> + Send + 'static,
+{
+    match tokio::task::spawn_blocking(send).await {
+        Ok(result) => result,
+        Err(error) if error.is_panic() => std::panic::resume_unwind(error.into_panic()),
+        Err(error) => Err(BroadcastError::connection_failed(format!(
+            "Broadcast send task did not complete: {error}"
+        ))),
+    }
 }
 
 #[cfg(test)]
@@ -251,6 +1228,626 @@ mod tests {
         assert!(broadcast.is_err());
     }
 
+    #[cfg(not(windows))]
+    #[test]
+    fn test_retry_does_not_retry_unsupported_platform() {
+        let policy = RetryPolicy::new(5, std::time::Duration::from_secs(60));
+        assert!(matches!(
+            Client::from_message_id(1).send_message_with_retry(PitCommandMode::Tearoff, policy),
+            Err(BroadcastError::UnsupportedPlatform { .. })
+        ));
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_stub_api_off_windows() {
+        assert!(!Client::is_iracing_running());
+        assert!(matches!(
+            Client::from_message_id(1).send_message_checked(PitCommandMode::Tearoff),
+            Err(BroadcastError::UnsupportedPlatform { .. })
+        ));
+        assert!(
+            Client::offline()
+                .send_message_checked(PitCommandMode::Tearoff)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_dry_run_validates_without_sending() {
+        let mut client = Client::offline();
+        client.set_dry_run(true);
+        assert!(client.is_dry_run());
+
+        client.send_message(PitCommandMode::Fuel(40)).unwrap();
+        client
+            .send_message(BroadcastMessage::ChatCommandMacro(15))
+            .unwrap();
+        for message in [
+            BroadcastMessage::ChatCommandMacro(0),
+            BroadcastMessage::ChatCommandMacro(16),
+        ] {
+            assert!(matches!(
+                client.send_message(message),
+                Err(BroadcastError::InvalidParameter { .. })
+            ));
+        }
+        assert!(matches!(
+            client.send_raw(BroadcastMessageType::ReplaySearch as u16, 10, 0, 0),
+            Err(BroadcastError::InvalidParameter { .. })
+        ));
+        assert!(matches!(
+            client.send_raw(99, 0, 0, 0),
+            Err(BroadcastError::UnknownMessageType { value: 99 })
+        ));
+
+        // Offline clients send the same malformed message without complaint.
+        assert!(
+            Client::offline()
+                .send_message(BroadcastMessage::ChatCommandMacro(16))
+                .is_ok()
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_dry_run_skips_delivery() {
+        // Delivery always fails here, so `Ok` proves it was never attempted.
+        let mut client = Client::from_message_id(1);
+        client.set_dry_run(true);
+
+        client.send_message(ReplaySearchMode::ToEnd).unwrap();
+        client
+            .send_message_checked(ReplaySearchMode::ToEnd)
+            .unwrap();
+        client.send_raw(0, 1, 2, 3).unwrap();
+    }
+
+    #[test]
+    fn test_reload_textures_for_sends_each_car() {
+        let sent = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let client = Client::offline().with_observer({
+            let sent = Arc::clone(&sent);
+            move |message, _| sent.lock().unwrap().push(*message)
+        });
+
+        client.reload_textures_for([2, 9, 40]).unwrap();
+        assert_eq!(
+            *sent.lock().unwrap(),
+            [2, 9, 40].map(BroadcastMessage::ReloadTextures)
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_throttles_sends() {
+        let interval = Duration::from_millis(50);
+        let mut client = Client::offline();
+        client.set_rate_limit(interval, RateLimitMode::Reject);
+        assert_eq!(client.rate_limit(), Some((interval, RateLimitMode::Reject)));
+
+        client
+            .send_message(BroadcastMessage::ReplaySetState)
+            .unwrap();
+        assert!(matches!(
+            client.send_raw(6, 0, 0, 0),
+            Err(BroadcastError::RateLimited { .. })
+        ));
+
+        std::thread::sleep(interval);
+        client
+            .send_message(BroadcastMessage::ReplaySetState)
+            .unwrap();
+
+        client.set_rate_limit(Duration::ZERO, RateLimitMode::Reject);
+        assert_eq!(client.rate_limit(), None);
+        client
+            .send_message(BroadcastMessage::ReplaySetState)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_rate_limit_throttles_raw_sends() {
+        let mut client = Client::offline();
+        client.set_rate_limit(Duration::from_secs(60), RateLimitMode::Reject);
+
+        client.send_raw(6, 0, 0, 0).unwrap();
+        for sent in [
+            client.send_raw(6, 0, 0, 0),
+            client.send_message(BroadcastMessage::ReplaySetState),
+        ] {
+            assert!(matches!(sent, Err(BroadcastError::RateLimited { .. })));
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_waits_between_sends() {
+        let interval = Duration::from_millis(50);
+        let mut client = Client::offline();
+        client.set_rate_limit(interval, RateLimitMode::Wait);
+
+        let start = std::time::Instant::now();
+        client
+            .send_message(BroadcastMessage::ReplaySetState)
+            .unwrap();
+        client
+            .clone()
+            .send_message(BroadcastMessage::ReplaySetState)
+            .unwrap();
+        assert!(start.elapsed() >= interval);
+    }
+
+    #[test]
+    fn test_observer_sees_each_message_in_order() {
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        let client = Client::offline().with_observer(move |message, params| {
+            log.lock().unwrap().push((*message, params));
+        });
+
+        let messages = [
+            BroadcastMessage::PitCommand(PitCommandMode::Clear),
+            BroadcastMessage::ReplaySearchSessionTime(2, 3_600_000),
+            BroadcastMessage::camera_switch_number("064", 1, 2),
+        ];
+        for message in &messages {
+            client.send_message(message).unwrap();
+        }
+        client.send_raw(0, 1, 2, 3).unwrap();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), messages.len());
+        for ((message, params), expected) in seen.iter().zip(&messages) {
+            assert_eq!(message, expected);
+            let (message_type, var1, var2, var3) = expected.to_message();
+            assert_eq!(
+                BroadcastMessage::decode(params.0, params.1).unwrap().1,
+                var1
+            );
+            assert_eq!(*params, pack(message_type as u16, var1, var2, var3));
+        }
+    }
+
+    #[test]
+    fn test_from_message_id() {
+        let client = Client::from_message_id(0xC0DE);
+        assert_eq!(client.message_id(), 0xC0DE);
+        assert!(!client.is_offline());
+        assert_eq!(Client::offline().message_id(), 0);
+    }
+
+    #[test]
+    fn test_offline_client_sends_nowhere() {
+        let client = Client::offline();
+        assert!(client.is_offline());
+        assert!(
+            client
+                .send_message(BroadcastMessage::PitCommand(PitCommandMode::Tearoff))
+                .is_ok()
+        );
+        assert!(client.send_raw(9, 1, 0, 0).is_ok());
+    }
+
+    #[test]
+    fn test_send_mode_directly() {
+        let client = Client::offline();
+        client.send_message(PitCommandMode::Tearoff).unwrap();
+
+        let (message_type, var1, var2, var3) = ReplaySearchMode::ToEnd.to_message();
+        assert!(matches!(message_type, BroadcastMessageType::ReplaySearch));
+        assert_eq!((var1, var2, var3), (ReplaySearchMode::ToEnd.into(), 0, 0));
+    }
+
+    #[test]
+    fn test_send_borrowed_message() {
+        let client = Client::offline();
+        let message = BroadcastMessage::camera_switch_number("064", 1, 2);
+
+        client.send_message(message).unwrap();
+        client.send_message(message).unwrap();
+
+        assert_eq!(message, BroadcastMessage::camera_switch_number("064", 1, 2));
+        let (message_type, var1, var2, var3) = (&message).to_message();
+        assert!(matches!(
+            message_type,
+            BroadcastMessageType::CameraSwitchNumber
+        ));
+        assert_eq!((var1, var2, var3), (3064, 1, 2));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    #[tracing_test::traced_test]
+    fn test_dispatch_emits_trace_events() {
+        let sent = dispatch(
+            BroadcastMessage::PitCommand(PitCommandMode::Tearoff),
+            |_, _| Ok(()),
+        );
+        assert!(sent.is_ok());
+        assert!(logs_contain("message_type=PitCommand"));
+        // PitCommand (9) | Tearoff (1) << 16
+        assert!(logs_contain("wparam=65545"));
+        assert!(logs_contain("broadcast message sent"));
+
+        let failed = dispatch(BroadcastMessage::ReplaySetState, |_, _| {
+            Err(BroadcastError::connection_failed("sim not running"))
+        });
+        assert!(failed.is_err());
+        assert!(logs_contain("broadcast message failed"));
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    #[tracing_test::traced_test]
+    fn test_dry_run_logs_packed_params() {
+        let mut client = Client::offline();
+        client.set_dry_run(true);
+        client.send_message(PitCommandMode::Tearoff).unwrap();
+
+        assert!(logs_contain("dry run: broadcast message not sent"));
+        assert!(logs_contain("wparam=65545"));
+        assert!(logs_contain("lparam=0"));
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_sends_increment_counter() {
+        use metrics_util::{
+            CompositeKey, MetricKind,
+            debugging::{DebugValue, DebuggingRecorder},
+        };
+
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        metrics::with_local_recorder(&recorder, || {
+            let client = Client::offline();
+            client.send_message(PitCommandMode::Tearoff).unwrap();
+            client.send_message(PitCommandMode::Clear).unwrap();
+            client
+                .send_message(BroadcastMessage::ReplaySetState)
+                .unwrap();
+            let failed = dispatch(PitCommandMode::Tearoff, |_, _| {
+                Err(BroadcastError::connection_failed("sim not running"))
+            });
+            assert!(failed.is_err());
+        });
+
+        let counts: Vec<_> = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .map(|(key, _, _, value)| {
+                let (kind, key): (MetricKind, _) = CompositeKey::into_parts(key);
+                assert_eq!(kind, MetricKind::Counter);
+                assert_eq!(key.name(), MESSAGES_COUNTER);
+                let labels: Vec<_> = key
+                    .labels()
+                    .map(|label| format!("{}={}", label.key(), label.value()))
+                    .collect();
+                let DebugValue::Counter(count) = value else {
+                    panic!("{labels:?} is not a counter");
+                };
+                (labels.join(","), count)
+            })
+            .collect();
+
+        assert!(counts.contains(&("message_type=PitCommand,outcome=sent".to_owned(), 2)));
+        assert!(counts.contains(&("message_type=ReplaySetState,outcome=sent".to_owned(), 1)));
+        assert!(counts.contains(&("message_type=PitCommand,outcome=failed".to_owned(), 1)));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_send_message_to_handle() {
+        let broadcast = Client::new().expect("Could not register broadcast client");
+        let sent = broadcast.send_message_to(
+            HWND_BROADCAST,
+            BroadcastMessage::ReplaySearch(ReplaySearchMode::ToEnd),
+        );
+        assert!(sent.is_ok());
+    }
+
+    #[test]
+    fn test_delivery_defaults_to_notify() {
+        let mut client = Client::offline();
+        assert_eq!(client.delivery(), Delivery::Notify);
+
+        client.set_delivery(Delivery::Post);
+        assert_eq!(client.delivery(), Delivery::Post);
+        assert!(client.send_message(PitCommandMode::Tearoff).is_ok());
+
+        assert_eq!(Delivery::Notify.api_name(), "SendNotifyMessageW");
+        assert_eq!(Delivery::Post.api_name(), "PostMessageW");
+        assert_eq!(Delivery::Send.api_name(), "SendMessageW");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_delivery_selects_win32_call() {
+        // No window has this handle, so each call fails and names itself.
+        let invalid = HWND(0xDEAD as _);
+        for delivery in [Delivery::Notify, Delivery::Post] {
+            let mut client = Client::new().expect("Could not register broadcast client");
+            client.set_delivery(delivery);
+
+            match client.send_message_to(invalid, BroadcastMessage::ReplaySetState) {
+                Err(BroadcastError::WindowsApi { operation, .. }) => {
+                    assert_eq!(operation, delivery.api_name())
+                }
+                other => panic!("{delivery:?} sent to an invalid window: {other:?}"),
+            }
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_find_iracing_window_not_running() {
+        // CI machines never run the simulator.
+        assert!(matches!(
+            Client::find_iracing_window(),
+            Err(BroadcastError::Connection { .. })
+        ));
+    }
+
+    #[test]
+    fn test_raw_packing_matches_typed_message() {
+        let mut typed = (0, 0);
+        dispatch(
+            BroadcastMessage::ReplaySearchSessionTime(2, 3_600_000),
+            |wparam, lparam| {
+                typed = (wparam, lparam);
+                Ok(())
+            },
+        )
+        .unwrap();
+
+        let mut raw = (0, 0);
+        dispatch_raw(12, 2, 0xEE80, 0x0036, |wparam, lparam| {
+            raw = (wparam, lparam);
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(raw, typed);
+        assert_eq!(raw, (0x0002_000C, 0x0036_EE80));
+    }
+
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_run_blocking_against_mock() {
+        use crate::{MessageSink, RecordingSink};
+        use std::sync::Arc;
+
+        let sink = Arc::new(RecordingSink::new());
+        let task_sink = Arc::clone(&sink);
+        let message = BroadcastMessage::PitCommand(PitCommandMode::Tearoff);
+        let task_message = message;
+
+        let result = run_blocking(move || task_sink.send(task_message)).await;
+        assert!(result.is_ok());
+        assert_eq!(sink.messages(), vec![message]);
+    }
+
+    #[cfg(all(feature = "async", not(windows)))]
+    #[tokio::test]
+    async fn test_send_message_async_unsupported_platform() {
+        let client = Client::from_message_id(1);
+        let result = client
+            .send_message_async(BroadcastMessage::ReplaySetState)
+            .await;
+        assert!(matches!(
+            result,
+            Err(BroadcastError::UnsupportedPlatform { .. })
+        ));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_blocking_send_without_iracing() {
+        // CI machines never run the simulator, so no window handles the
+        // message and the send fails before waiting on the timeout.
+        let broadcast = Client::new().expect("Could not register broadcast client");
+        assert!(matches!(
+            broadcast
+                .send_message_blocking(BroadcastMessage::ReplaySetState, Duration::from_millis(50)),
+            Err(BroadcastError::Connection { .. })
+        ));
+        assert!(
+            Client::offline()
+                .send_message_blocking(BroadcastMessage::ReplaySetState, Duration::ZERO)
+                .is_ok()
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_blocking_send_unsupported_platform() {
+        assert!(matches!(
+            Client::from_message_id(1)
+                .send_message_blocking(BroadcastMessage::ReplaySetState, Duration::ZERO),
+            Err(BroadcastError::UnsupportedPlatform { .. })
+        ));
+    }
+
+    #[test]
+    fn test_invalid_message_names() {
+        for name in ["", "IRSDK\0BROADCASTMSG"] {
+            assert!(matches!(
+                Client::with_message_name(name),
+                Err(BroadcastError::InvalidParameter { .. })
+            ));
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_custom_message_name() {
+        let name = "IRACING_BROADCAST_RS_TEST";
+        let custom = Client::with_message_name(name).unwrap();
+        let default = Client::new().unwrap();
+
+        assert_eq!(
+            custom.message_id(),
+            Client::with_message_name(name).unwrap().message_id()
+        );
+        assert_ne!(custom.message_id(), default.message_id());
+        // Nothing listens for the custom name, so this reaches no sim.
+        custom
+            .send_message(BroadcastMessage::ReplaySetState)
+            .unwrap();
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_refresh_registers_current_id() {
+        let name = "IRACING_BROADCAST_RS_TEST";
+        let client = Client::with_message_name(name).unwrap();
+        let clone = client.clone();
+
+        let id = client.refresh().unwrap();
+        assert_ne!(id, 0);
+        assert_eq!(id, Client::with_message_name(name).unwrap().message_id());
+        assert_eq!(clone.message_id(), id);
+
+        let stale = Client::from_message_id(1);
+        assert_eq!(
+            stale.refresh().unwrap(),
+            Client::new().unwrap().message_id()
+        );
+        assert_eq!(Client::offline().refresh().unwrap(), 0);
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_refresh_off_windows() {
+        assert_eq!(Client::offline().refresh().unwrap(), 0);
+        assert!(matches!(
+            Client::from_message_id(1).refresh(),
+            Err(BroadcastError::UnsupportedPlatform { .. })
+        ));
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_custom_message_name_unsupported_platform() {
+        assert!(matches!(
+            Client::with_message_name("IRACING_BROADCAST_RS_TEST"),
+            Err(BroadcastError::UnsupportedPlatform { .. })
+        ));
+    }
+
+    #[test]
+    fn test_send_outcome_without_window() {
+        let modes = [SendMode::Notify, SendMode::Blocking(Duration::ZERO)];
+        let mut dry_run = Client::from_message_id(1);
+        dry_run.set_dry_run(true);
+
+        for mode in modes {
+            for client in [&Client::offline(), &dry_run] {
+                assert_eq!(
+                    client
+                        .send_with_outcome(BroadcastMessage::ReplaySetState, mode)
+                        .unwrap(),
+                    SendOutcome::NoListener,
+                    "{mode:?}"
+                );
+            }
+            assert!(matches!(
+                dry_run.send_with_outcome(BroadcastMessage::ChatCommandMacro(40), mode),
+                Err(BroadcastError::InvalidParameter { .. })
+            ));
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_send_outcome_without_iracing() {
+        // CI machines never run the simulator, so no window receives the
+        // message whichever way it is sent.
+        let mut client = Client::new().unwrap();
+        for mode in [
+            SendMode::Notify,
+            SendMode::Blocking(Duration::from_millis(50)),
+        ] {
+            assert_eq!(
+                client
+                    .send_with_outcome(BroadcastMessage::ReplaySetState, mode)
+                    .unwrap(),
+                SendOutcome::NoListener,
+                "{mode:?}"
+            );
+        }
+
+        client.set_target(Target::Process(std::process::id()));
+        assert_eq!(
+            client
+                .send_with_outcome(BroadcastMessage::ReplaySetState, SendMode::Notify)
+                .unwrap(),
+            SendOutcome::NoListener
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_send_outcome_unsupported_platform() {
+        for mode in [SendMode::Notify, SendMode::Blocking(Duration::ZERO)] {
+            assert!(matches!(
+                Client::from_message_id(1)
+                    .send_with_outcome(BroadcastMessage::ReplaySetState, mode),
+                Err(BroadcastError::UnsupportedPlatform { .. })
+            ));
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_send_to_pid_without_iracing_window() {
+        // The test process owns no simulator window.
+        let pid = std::process::id();
+        assert!(matches!(
+            Client::find_iracing_window_for_pid(pid),
+            Err(BroadcastError::Connection { .. })
+        ));
+
+        let broadcast = Client::new().expect("Could not register broadcast client");
+        assert!(matches!(
+            broadcast.send_message_to_pid(pid, BroadcastMessage::ReplaySetState),
+            Err(BroadcastError::Connection { .. })
+        ));
+        assert!(
+            Client::offline()
+                .send_message_to_pid(pid, BroadcastMessage::ReplaySetState)
+                .is_ok()
+        );
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_send_to_pid_unsupported_platform() {
+        assert!(matches!(
+            Client::from_message_id(1)
+                .send_message_to_pid(std::process::id(), BroadcastMessage::ReplaySetState),
+            Err(BroadcastError::UnsupportedPlatform { .. })
+        ));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_checked_send_without_iracing() {
+        // CI machines never run the simulator.
+        assert!(!Client::is_iracing_running());
+
+        let broadcast = Client::new().expect("Could not register broadcast client");
+        assert!(matches!(
+            broadcast.send_message_checked(BroadcastMessage::ReplaySetState),
+            Err(BroadcastError::Connection { .. })
+        ));
+        assert!(
+            Client::offline()
+                .send_message_checked(BroadcastMessage::ReplaySetState)
+                .is_ok()
+        );
+    }
+
     #[cfg(windows)]
     #[test]
     fn test_message() {
@@ -0,0 +1,225 @@
+//! Buffering that drops messages made redundant by later ones.
+
+use std::collections::HashSet;
+
+use crate::{BroadcastError, BroadcastMessage, MessageSink};
+
+/// A piece of sim state that only the most recent message setting it
+/// decides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Slot {
+    /// The camera target, whether a position, car number or focus mode.
+    CameraTarget,
+    CameraGroup,
+    CameraNumber,
+    CameraState,
+    ReplaySpeed,
+    FfbForce,
+}
+
+impl Slot {
+    /// The slots `message` sets. Camera switches leave the group or camera
+    /// alone when it is 0, so they only set those slots otherwise.
+    fn of(message: &BroadcastMessage) -> [Option<Self>; 3] {
+        match *message {
+            BroadcastMessage::CameraSwitchPosition(_, group, camera)
+            | BroadcastMessage::CameraSwitchNumber(_, group, camera)
+            | BroadcastMessage::CameraSwitchFocus(_, group, camera) => [
+                Some(Slot::CameraTarget),
+                (group != 0).then_some(Slot::CameraGroup),
+                (camera != 0).then_some(Slot::CameraNumber),
+            ],
+            BroadcastMessage::CameraSetState(_) => [Some(Slot::CameraState), None, None],
+            BroadcastMessage::ReplaySetPlaySpeed(_) => [Some(Slot::ReplaySpeed), None, None],
+            BroadcastMessage::FFBCommand(_) => [Some(Slot::FfbForce), None, None],
+            _ => [None; 3],
+        }
+    }
+}
+
+/// Buffers messages for a [`MessageSink`] and drops superseded ones on flush.
+///
+/// Camera switches (in any form), camera state, replay speed and FFB force
+/// messages each replace the previous message of their kind, so only the
+/// latest of each is sent. A camera switch with group or camera 0 keeps the
+/// current one, so an earlier switch is only dropped once later ones have
+/// replaced everything it set. Everything else, such as pit, chat and replay
+/// search commands, is order-sensitive and always sent. Surviving messages
+/// keep their relative order.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::{BroadcastMessage, CoalescingSender, PitCommandMode, RecordingSink};
+///
+/// let mut sender = CoalescingSender::new(RecordingSink::new());
+/// sender.push(BroadcastMessage::CameraSwitchPosition(1, 10, 0));
+/// sender.push(BroadcastMessage::PitCommand(PitCommandMode::Tearoff));
+/// sender.push(BroadcastMessage::CameraSwitchPosition(2, 10, 0));
+/// sender.flush().expect("recording never fails");
+///
+/// assert_eq!(
+///     sender.sink().messages(),
+///     [
+///         BroadcastMessage::PitCommand(PitCommandMode::Tearoff),
+///         BroadcastMessage::CameraSwitchPosition(2, 10, 0),
+///     ]
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct CoalescingSender<S> {
+    sink: S,
+    pending: Vec<BroadcastMessage>,
+}
+
+impl<S: MessageSink> CoalescingSender<S> {
+    /// Wrap `sink` with an empty buffer.
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Buffer a message until the next [`flush`](CoalescingSender::flush).
+    pub fn push(&mut self, message: impl Into<BroadcastMessage>) {
+        self.pending.push(message.into());
+    }
+
+    /// Number of buffered messages, before coalescing.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Whether nothing is buffered.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Send the buffered messages that are still current and clear the buffer.
+    ///
+    /// Failures are reported like [`MessageSink::send_all`], with indices
+    /// into the coalesced sequence that was actually sent.
+    pub fn flush(&mut self) -> Result<(), Vec<(usize, BroadcastError)>> {
+        let mut pending = std::mem::take(&mut self.pending);
+
+        // Walking backwards, a message is current if it is unslotted or
+        // sets a slot no later message has set.
+        let mut seen = HashSet::new();
+        let mut current = Vec::with_capacity(pending.len());
+        while let Some(message) = pending.pop() {
+            let slots = Slot::of(&message);
+            let unslotted = slots.iter().all(Option::is_none);
+            let sets_unseen = slots
+                .into_iter()
+                .flatten()
+                .fold(false, |unseen, slot| seen.insert(slot) | unseen);
+            if unslotted || sets_unseen {
+                current.push(message);
+            }
+        }
+        current.reverse();
+
+        self.sink.send_all(current)
+    }
+
+    /// The wrapped sink.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Unwrap the sink, discarding anything still buffered.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CameraState, PitCommandMode, RecordingSink, ReplaySearchMode};
+
+    #[test]
+    fn test_superseded_camera_switches_dropped() {
+        let mut sender = CoalescingSender::new(RecordingSink::new());
+        sender.push(BroadcastMessage::CameraSwitchPosition(1, 10, 0));
+        sender.push(PitCommandMode::Clear);
+        sender.push(BroadcastMessage::camera_switch_number("64", 10, 0));
+        sender.push(PitCommandMode::Fuel(40));
+        sender.push(BroadcastMessage::CameraSwitchPosition(3, 10, 0));
+        sender.push(PitCommandMode::Tearoff);
+        sender.flush().unwrap();
+
+        assert_eq!(
+            sender.sink().messages(),
+            vec![
+                BroadcastMessage::PitCommand(PitCommandMode::Clear),
+                BroadcastMessage::PitCommand(PitCommandMode::Fuel(40)),
+                BroadcastMessage::CameraSwitchPosition(3, 10, 0),
+                BroadcastMessage::PitCommand(PitCommandMode::Tearoff),
+            ]
+        );
+        assert!(sender.is_empty());
+    }
+
+    #[test]
+    fn test_each_kind_keeps_its_latest() {
+        let mut sender = CoalescingSender::new(RecordingSink::new());
+        sender.push(BroadcastMessage::CameraSetState(CameraState::UI_HIDDEN));
+        sender.push(BroadcastMessage::CameraSwitchPosition(1, 10, 0));
+        sender.push(ReplaySearchMode::NextIncident);
+        sender.push(BroadcastMessage::CameraSetState(CameraState::empty()));
+        sender.push(ReplaySearchMode::NextIncident);
+        assert_eq!(sender.len(), 5);
+        sender.flush().unwrap();
+
+        assert_eq!(
+            sender.sink().messages(),
+            vec![
+                BroadcastMessage::CameraSwitchPosition(1, 10, 0),
+                BroadcastMessage::ReplaySearch(ReplaySearchMode::NextIncident),
+                BroadcastMessage::CameraSetState(CameraState::empty()),
+                BroadcastMessage::ReplaySearch(ReplaySearchMode::NextIncident),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_camera_switch_kept_for_fields_later_ones_leave_alone() {
+        let mut sender = CoalescingSender::new(RecordingSink::new());
+        sender.push(BroadcastMessage::CameraSwitchPosition(1, 4, 1));
+        sender.push(BroadcastMessage::CameraSwitchPosition(2, 0, 0));
+        sender.push(BroadcastMessage::CameraSwitchPosition(3, 5, 0));
+        sender.push(BroadcastMessage::CameraSwitchPosition(4, 6, 2));
+        sender.push(BroadcastMessage::CameraSwitchPosition(5, 0, 0));
+        sender.flush().unwrap();
+
+        assert_eq!(
+            sender.sink().messages(),
+            vec![
+                BroadcastMessage::CameraSwitchPosition(4, 6, 2),
+                BroadcastMessage::CameraSwitchPosition(5, 0, 0),
+            ]
+        );
+
+        sender.push(BroadcastMessage::CameraSwitchPosition(1, 4, 1));
+        sender.push(BroadcastMessage::camera_switch_number("64", 0, 0));
+        sender.flush().unwrap();
+
+        assert_eq!(
+            sender.sink().messages()[2..],
+            [
+                BroadcastMessage::CameraSwitchPosition(1, 4, 1),
+                BroadcastMessage::camera_switch_number("64", 0, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flush_empty_sends_nothing() {
+        let mut sender = CoalescingSender::new(RecordingSink::new());
+        sender.flush().unwrap();
+
+        assert!(sender.into_inner().messages().is_empty());
+    }
+}
@@ -0,0 +1,518 @@
+//! Parsing short text commands, as typed on a command line or in a chat-style
+//! console, into messages.
+//!
+//! A command is a verb, a subcommand and its arguments, separated by
+//! whitespace. Verbs and keywords ignore ASCII case. Arguments in brackets
+//! are optional.
+//!
+//! | Command | Message |
+//! |---|---|
+//! | `camera car <number> [group] [camera]` | [`BroadcastMessage::CameraSwitchNumber`] |
+//! | `camera position <position> [group] [camera]` | [`BroadcastMessage::camera_grid_position`] |
+//! | `camera leader\|incident\|exciting [group] [camera]` | [`BroadcastMessage::CameraSwitchFocus`] |
+//! | `pit fuel [liters]` | [`PitCommandMode::fuel_liters`] |
+//! | `pit lf\|rf\|lr\|rr [psi]` | [`PitCommandMode::lf_psi`] and friends |
+//! | `pit tearoff\|repair` | [`PitCommandMode::Tearoff`], [`PitCommandMode::FastRepair`] |
+//! | `pit clear [tires\|tearoff\|repair\|fuel]` | [`PitCommandMode::Clear`] and friends |
+//! | `replay play [speed]`, `replay rewind [speed]` | [`BroadcastMessage::ReplaySetPlaySpeed`] |
+//! | `replay slow <divisor>`, `replay pause` | [`BroadcastMessage::ReplaySetPlaySpeed`] |
+//! | `replay start\|end` | [`BroadcastMessage::ReplaySearch`] |
+//! | `replay next\|prev session\|lap\|frame\|incident` | [`BroadcastMessage::ReplaySearch`] |
+//! | `replay frame <frame>` | [`BroadcastMessage::ReplaySetPlayPosition`] |
+//! | `replay time <session> <time>` | [`BroadcastMessage::replay_seek_session_time_str`] |
+//! | `replay erase` | [`BroadcastMessage::ReplaySetState`] |
+//! | `chat begin\|reply\|cancel`, `chat macro <number>` | [`BroadcastMessage::ChatCommand`], [`BroadcastMessage::ChatCommandMacro`] |
+//! | `capture screenshot\|start\|stop\|toggle`, `capture timer show\|hide` | [`BroadcastMessage::VideoCapture`] |
+//!
+//! A camera group is a number or a name from [`CameraGroup::WELL_KNOWN`]
+//! without spaces, such as `TV1`; group and camera default to `0`. Omitted
+//! fuel and tire pressures are sent as `0`, keeping the amount already
+//! selected.
+//!
+//! # Examples
+//!
+//! ```
+//! use iracing_broadcast::{BroadcastMessage, CameraFocusMode, PitCommandMode, command};
+//!
+//! assert_eq!(
+//!     command::parse_command("pit fuel 65")?,
+//!     BroadcastMessage::PitCommand(PitCommandMode::Fuel(65))
+//! );
+//! assert_eq!(
+//!     BroadcastMessage::from_command("camera leader tv1")?,
+//!     BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Leader, 11, 0)
+//! );
+//! assert!(command::parse_command("pit refuel").is_err());
+//! # Ok::<(), command::ParseError>(())
+//! ```
+
+use alloc::string::{String, ToString};
+use core::{fmt::Display, str::FromStr, str::SplitWhitespace};
+
+use thiserror::Error;
+
+use crate::{
+    BroadcastError, BroadcastMessage, CameraFocusMode, CameraGroup, CarNumber, ChatCommandMode,
+    PitCommandMode, ReplayPositionMode, ReplaySearchMode, ReplaySpeed, VideoCaptureMode,
+};
+
+/// Why a command did not parse.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    #[error("Empty command")]
+    Empty,
+
+    #[error("Unknown command '{verb}'; expected camera, pit, replay, chat or capture")]
+    UnknownVerb { verb: String },
+
+    #[error("Unknown {verb} command '{subcommand}'")]
+    UnknownSubcommand {
+        verb: &'static str,
+        subcommand: String,
+    },
+
+    #[error("'{command}' is missing its {argument}")]
+    MissingArgument {
+        command: String,
+        argument: &'static str,
+    },
+
+    #[error("Invalid {argument} '{value}': {reason}")]
+    InvalidArgument {
+        argument: &'static str,
+        value: String,
+        reason: String,
+    },
+
+    #[error("Unexpected '{extra}' at the end of the command")]
+    TrailingInput { extra: String },
+}
+
+/// Lets command parsing use `?` in functions returning this crate's
+/// [`Result`](crate::Result), reported as an invalid `command` parameter.
+impl From<ParseError> for BroadcastError {
+    fn from(error: ParseError) -> Self {
+        BroadcastError::invalid_parameter("command", error.to_string())
+    }
+}
+
+/// Parse one command; see the [module documentation](self) for the syntax.
+pub fn parse_command(command: &str) -> Result<BroadcastMessage, ParseError> {
+    let mut words = Words {
+        command,
+        words: command.split_whitespace(),
+    };
+
+    let verb = words.next().ok_or(ParseError::Empty)?;
+    let message = match verb.to_ascii_lowercase().as_str() {
+        "camera" => camera(&mut words)?,
+        "pit" => pit(&mut words)?,
+        "replay" => replay(&mut words)?,
+        "chat" => chat(&mut words)?,
+        "capture" => capture(&mut words)?,
+        _ => return Err(ParseError::UnknownVerb { verb: verb.into() }),
+    };
+
+    match words.next() {
+        Some(extra) => Err(ParseError::TrailingInput {
+            extra: extra.into(),
+        }),
+        None => Ok(message),
+    }
+}
+
+impl BroadcastMessage {
+    /// Parse a text command such as `"pit fuel 65"`; see
+    /// [`command`](crate::command) for the syntax.
+    pub fn from_command(command: &str) -> Result<Self, ParseError> {
+        parse_command(command)
+    }
+}
+
+/// The words of a command still to be parsed.
+struct Words<'a> {
+    command: &'a str,
+    words: SplitWhitespace<'a>,
+}
+
+impl<'a> Words<'a> {
+    fn next(&mut self) -> Option<&'a str> {
+        self.words.next()
+    }
+
+    fn required(&mut self, argument: &'static str) -> Result<&'a str, ParseError> {
+        self.next().ok_or_else(|| ParseError::MissingArgument {
+            command: self.command.trim().into(),
+            argument,
+        })
+    }
+
+    fn number<T>(&mut self, argument: &'static str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let word = self.required(argument)?;
+        number(argument, word)
+    }
+
+    fn optional_number<T>(&mut self, argument: &'static str) -> Result<Option<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.next().map(|word| number(argument, word)).transpose()
+    }
+
+    /// The optional camera group and camera that end camera commands.
+    fn group_and_camera(&mut self) -> Result<(u8, u8), ParseError> {
+        let group = match self.next() {
+            None => 0,
+            Some(word) => match word.parse::<u8>() {
+                Ok(group) => group,
+                Err(_) => CameraGroup::from_name(word)
+                    .map(CameraGroup::get)
+                    .ok_or_else(|| ParseError::InvalidArgument {
+                        argument: "camera group",
+                        value: word.into(),
+                        reason: "not a group number or well-known group name".into(),
+                    })?,
+            },
+        };
+        let camera = self.optional_number("camera")?.unwrap_or(0);
+        Ok((group, camera))
+    }
+}
+
+fn number<T>(argument: &'static str, word: &str) -> Result<T, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    word.parse()
+        .map_err(|error: T::Err| ParseError::InvalidArgument {
+            argument,
+            value: word.into(),
+            reason: error.to_string(),
+        })
+}
+
+/// Report a value the message constructors rejected.
+fn invalid(argument: &'static str, value: impl Display, error: BroadcastError) -> ParseError {
+    let reason = match error {
+        BroadcastError::InvalidParameter { reason, .. }
+        | BroadcastError::InvalidCarNumber { reason, .. } => reason,
+        error => error.to_string(),
+    };
+    ParseError::InvalidArgument {
+        argument,
+        value: value.to_string(),
+        reason,
+    }
+}
+
+fn unknown(verb: &'static str, subcommand: &str) -> ParseError {
+    ParseError::UnknownSubcommand {
+        verb,
+        subcommand: subcommand.into(),
+    }
+}
+
+fn camera(words: &mut Words<'_>) -> Result<BroadcastMessage, ParseError> {
+    let subcommand = words.required("subcommand")?;
+    let focus = match subcommand.to_ascii_lowercase().as_str() {
+        "car" => {
+            let number = words.required("car number")?;
+            let car = CarNumber::new(number).map_err(|e| invalid("car number", number, e))?;
+            let (group, camera) = words.group_and_camera()?;
+            return Ok(BroadcastMessage::CameraSwitchNumber(car, group, camera));
+        }
+        "position" => {
+            let position = words.number("position")?;
+            let (group, camera) = words.group_and_camera()?;
+            return BroadcastMessage::camera_grid_position(position, group, camera)
+                .map_err(|e| invalid("position", position, e));
+        }
+        "leader" => CameraFocusMode::Leader,
+        "incident" => CameraFocusMode::Incident,
+        "exciting" => CameraFocusMode::Exciting,
+        _ => return Err(unknown("camera", subcommand)),
+    };
+
+    let (group, camera) = words.group_and_camera()?;
+    Ok(BroadcastMessage::CameraSwitchFocus(focus, group, camera))
+}
+
+fn pit(words: &mut Words<'_>) -> Result<BroadcastMessage, ParseError> {
+    let subcommand = words.required("subcommand")?;
+    let mode = match subcommand.to_ascii_lowercase().as_str() {
+        "fuel" => match words.optional_number::<f32>("fuel amount")? {
+            Some(liters) => PitCommandMode::fuel_liters(liters)
+                .map_err(|e| invalid("fuel amount", liters, e))?,
+            None => PitCommandMode::Fuel(0),
+        },
+        "lf" => tire(words, PitCommandMode::lf_psi, PitCommandMode::LF)?,
+        "rf" => tire(words, PitCommandMode::rf_psi, PitCommandMode::RF)?,
+        "lr" => tire(words, PitCommandMode::lr_psi, PitCommandMode::LR)?,
+        "rr" => tire(words, PitCommandMode::rr_psi, PitCommandMode::RR)?,
+        "tearoff" => PitCommandMode::Tearoff,
+        "repair" => PitCommandMode::FastRepair,
+        "clear" => match words.next() {
+            None => PitCommandMode::Clear,
+            Some(service) => match service.to_ascii_lowercase().as_str() {
+                "tires" => PitCommandMode::ClearTires,
+                "tearoff" => PitCommandMode::ClearTearoff,
+                "repair" => PitCommandMode::ClearFastRepair,
+                "fuel" => PitCommandMode::ClearFuel,
+                _ => return Err(unknown("pit clear", service)),
+            },
+        },
+        _ => return Err(unknown("pit", subcommand)),
+    };
+    Ok(BroadcastMessage::PitCommand(mode))
+}
+
+/// A tire change at an optional PSI pressure, or at the current one.
+fn tire(
+    words: &mut Words<'_>,
+    psi: fn(u8) -> crate::Result<PitCommandMode>,
+    current: fn(u16) -> PitCommandMode,
+) -> Result<PitCommandMode, ParseError> {
+    match words.optional_number("tire pressure")? {
+        Some(pressure) => psi(pressure).map_err(|e| invalid("tire pressure", pressure, e)),
+        None => Ok(current(0)),
+    }
+}
+
+fn replay(words: &mut Words<'_>) -> Result<BroadcastMessage, ParseError> {
+    let subcommand = words.required("subcommand")?;
+    let search = match subcommand.to_ascii_lowercase().as_str() {
+        "play" => {
+            return match words.optional_number("speed")? {
+                Some(speed) => BroadcastMessage::replay_fast_forward(speed)
+                    .map_err(|e| invalid("speed", speed, e)),
+                None => Ok(BroadcastMessage::replay_play()),
+            };
+        }
+        "rewind" => {
+            let speed = words.optional_number("speed")?.unwrap_or(1);
+            return ReplaySpeed::rewind(speed)
+                .map(BroadcastMessage::ReplaySetPlaySpeed)
+                .map_err(|e| invalid("speed", speed, e));
+        }
+        "slow" => {
+            let divisor = words.number("slow-motion divisor")?;
+            return BroadcastMessage::replay_slow_motion(divisor)
+                .map_err(|e| invalid("slow-motion divisor", divisor, e));
+        }
+        "pause" => return Ok(BroadcastMessage::replay_pause()),
+        "frame" => {
+            let frame = words.number("frame")?;
+            return Ok(BroadcastMessage::ReplaySetPlayPosition(
+                ReplayPositionMode::Begin,
+                frame,
+            ));
+        }
+        "time" => {
+            let session = words.number("session number")?;
+            let time = words.required("session time")?;
+            return BroadcastMessage::replay_seek_session_time_str(session, time)
+                .map_err(|e| invalid("session time", time, e));
+        }
+        "erase" => return Ok(BroadcastMessage::ReplaySetState),
+        "start" => ReplaySearchMode::ToStart,
+        "end" => ReplaySearchMode::ToEnd,
+        "next" | "prev" => {
+            let next = subcommand.eq_ignore_ascii_case("next");
+            let target = words.required("search target")?;
+            let (previous_mode, next_mode) = match target.to_ascii_lowercase().as_str() {
+                "session" => (
+                    ReplaySearchMode::PreviousSession,
+                    ReplaySearchMode::NextSession,
+                ),
+                "lap" => (ReplaySearchMode::PreviousLap, ReplaySearchMode::NextLap),
+                "frame" => (ReplaySearchMode::PreviousFrame, ReplaySearchMode::NextFrame),
+                "incident" => (
+                    ReplaySearchMode::PreviousIncident,
+                    ReplaySearchMode::NextIncident,
+                ),
+                _ => return Err(unknown("replay search", target)),
+            };
+            if next { next_mode } else { previous_mode }
+        }
+        _ => return Err(unknown("replay", subcommand)),
+    };
+
+    Ok(BroadcastMessage::ReplaySearch(search))
+}
+
+fn chat(words: &mut Words<'_>) -> Result<BroadcastMessage, ParseError> {
+    let subcommand = words.required("subcommand")?;
+    let mode = match subcommand.to_ascii_lowercase().as_str() {
+        "begin" => ChatCommandMode::Begin,
+        "reply" => ChatCommandMode::Reply,
+        "cancel" => ChatCommandMode::Cancel,
+        "macro" => {
+            let number = words.number("macro number")?;
+            return BroadcastMessage::chat_macro(number)
+                .map_err(|e| invalid("macro number", number, e));
+        }
+        _ => return Err(unknown("chat", subcommand)),
+    };
+    Ok(BroadcastMessage::ChatCommand(mode))
+}
+
+fn capture(words: &mut Words<'_>) -> Result<BroadcastMessage, ParseError> {
+    let subcommand = words.required("subcommand")?;
+    let mode = match subcommand.to_ascii_lowercase().as_str() {
+        "screenshot" => VideoCaptureMode::ScreenShot,
+        "start" => VideoCaptureMode::StartCapture,
+        "stop" => VideoCaptureMode::EndCapture,
+        "toggle" => VideoCaptureMode::ToggleCapture,
+        "timer" => {
+            let visibility = words.required("timer visibility")?;
+            match visibility.to_ascii_lowercase().as_str() {
+                "show" => VideoCaptureMode::ShowTimer,
+                "hide" => VideoCaptureMode::HideTimer,
+                _ => return Err(unknown("capture timer", visibility)),
+            }
+        }
+        _ => return Err(unknown("capture", subcommand)),
+    };
+    Ok(BroadcastMessage::VideoCapture(mode))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_commands() {
+        let cases = [
+            (
+                "camera car 064 tv1 2",
+                BroadcastMessage::camera_switch_number("064", 11, 2),
+            ),
+            (
+                "camera position 1",
+                BroadcastMessage::CameraSwitchPosition(1, 0, 0),
+            ),
+            (
+                "camera incident 11",
+                BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Incident, 11, 0),
+            ),
+            (
+                "pit fuel 42.6",
+                BroadcastMessage::PitCommand(PitCommandMode::Fuel(43)),
+            ),
+            (
+                "pit fuel",
+                BroadcastMessage::PitCommand(PitCommandMode::Fuel(0)),
+            ),
+            (
+                "pit rr 26",
+                BroadcastMessage::PitCommand(PitCommandMode::rr_psi(26).unwrap()),
+            ),
+            (
+                "pit lf",
+                BroadcastMessage::PitCommand(PitCommandMode::LF(0)),
+            ),
+            (
+                "pit clear tires",
+                BroadcastMessage::PitCommand(PitCommandMode::ClearTires),
+            ),
+            ("replay play", BroadcastMessage::replay_play()),
+            (
+                "replay rewind 4",
+                BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::rewind(4).unwrap()),
+            ),
+            (
+                "replay slow 2",
+                BroadcastMessage::replay_slow_motion(2).unwrap(),
+            ),
+            (
+                "replay prev incident",
+                BroadcastMessage::ReplaySearch(ReplaySearchMode::PreviousIncident),
+            ),
+            (
+                "replay time 2 1:23.456",
+                BroadcastMessage::ReplaySearchSessionTime(2, 83_456),
+            ),
+            ("replay erase", BroadcastMessage::ReplaySetState),
+            ("chat macro 3", BroadcastMessage::ChatCommandMacro(3)),
+            (
+                "chat reply",
+                BroadcastMessage::ChatCommand(ChatCommandMode::Reply),
+            ),
+            (
+                "capture timer hide",
+                BroadcastMessage::VideoCapture(VideoCaptureMode::HideTimer),
+            ),
+            (
+                "  PIT   Tearoff ",
+                BroadcastMessage::PitCommand(PitCommandMode::Tearoff),
+            ),
+        ];
+
+        for (command, expected) in cases {
+            assert_eq!(parse_command(command), Ok(expected), "{command}");
+        }
+    }
+
+    #[test]
+    fn test_malformed_commands() {
+        assert_eq!(parse_command("   "), Err(ParseError::Empty));
+        assert_eq!(
+            parse_command("fly away"),
+            Err(ParseError::UnknownVerb { verb: "fly".into() })
+        );
+        assert_eq!(
+            parse_command("pit refuel"),
+            Err(ParseError::UnknownSubcommand {
+                verb: "pit",
+                subcommand: "refuel".into(),
+            })
+        );
+        assert_eq!(
+            parse_command("camera position"),
+            Err(ParseError::MissingArgument {
+                command: "camera position".into(),
+                argument: "position",
+            })
+        );
+        assert_eq!(
+            parse_command("replay pause now"),
+            Err(ParseError::TrailingInput {
+                extra: "now".into()
+            })
+        );
+
+        for (command, argument) in [
+            ("pit fuel lots", "fuel amount"),
+            ("pit lf 0", "tire pressure"),
+            ("chat macro 16", "macro number"),
+            ("camera car 64a", "car number"),
+            ("camera position 0", "position"),
+            ("camera leader Tower", "camera group"),
+            ("replay play 40", "speed"),
+        ] {
+            assert!(
+                matches!(
+                    parse_command(command),
+                    Err(ParseError::InvalidArgument { argument: a, .. }) if a == argument
+                ),
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_converts_to_broadcast_error() {
+        let error = BroadcastError::from(parse_command("chat macro 16").unwrap_err());
+        assert_eq!(
+            error.to_string(),
+            "Invalid command: Invalid macro number '16': 16 is outside 1..=15"
+        );
+    }
+}
@@ -7,6 +7,8 @@
 //! ## Error Categories
 //!
 //! - **Connection Errors**: Issues connecting to iRacing.
+//! - **Protocol Errors**: Raw broadcast parameters that do not decode.
+//! - **Validation Errors**: Message parameters the simulator would ignore.
 //! - **Windows API Errors**: Platform-specific Windows operation failures
 //!
 //! ## Recovery and Retry
@@ -27,14 +29,27 @@
 //! let unsupported_error = BroadcastError::unsupported_platform("Message registration", "Windows");
 //! ```
 
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::time::Duration;
+
 use thiserror::Error;
 
-#[cfg(windows)]
-use windows_core as core;
+use crate::BroadcastMessageType;
 
-pub type Result<T, E = BroadcastError> = std::result::Result<T, E>;
+pub type Result<T, E = BroadcastError> = core::result::Result<T, E>;
 
-#[derive(Error, Debug)]
+/// Errors returned by this crate.
+///
+/// Errors are `Clone` so one failure can be handed to several subscribers,
+/// e.g. over channels.
+#[derive(Error, Debug, Clone)]
 #[non_exhaustive]
 pub enum BroadcastError {
     #[error("Failed to connect to iRacing: {reason}")]
@@ -46,12 +61,42 @@ pub enum BroadcastError {
         required_platform: String,
     },
 
+    #[error("Unknown broadcast message type: {value}")]
+    UnknownMessageType { value: u32 },
+
+    #[error("Invalid car number '{car_number}': {reason}")]
+    InvalidCarNumber { car_number: String, reason: String },
+
+    #[error("Invalid {parameter}: {reason}")]
+    InvalidParameter { parameter: String, reason: String },
+
+    #[error("Rate limited: next send allowed in {retry_after:?}")]
+    RateLimited { retry_after: Duration },
+
+    #[error("{message_type:?} had no visible effect: {reason}")]
+    Unverified {
+        message_type: BroadcastMessageType,
+        reason: String,
+    },
+
+    #[error("Failed to start the {name} thread: {source}")]
+    #[cfg(feature = "std")]
+    Thread {
+        name: String,
+        /// Shared so the error stays `Clone`.
+        #[source]
+        source: Arc<std::io::Error>,
+    },
+
     #[error("Windows API error: {operation}")]
     #[cfg(windows)]
     WindowsApi {
         operation: String,
+        /// The message being sent when the call failed, if the failure
+        /// happened while sending a known message type.
+        message_type: Option<BroadcastMessageType>,
         #[source]
-        source: core::Error,
+        source: windows_core::Error,
     },
 }
 
@@ -60,6 +105,13 @@ impl BroadcastError {
         match self {
             BroadcastError::Connection { .. } => true,
             BroadcastError::UnsupportedPlatform { .. } => false,
+            BroadcastError::UnknownMessageType { .. } => false,
+            BroadcastError::InvalidCarNumber { .. } => false,
+            BroadcastError::InvalidParameter { .. } => false,
+            BroadcastError::RateLimited { .. } => true,
+            BroadcastError::Unverified { .. } => true,
+            #[cfg(feature = "std")]
+            BroadcastError::Thread { .. } => false,
             #[cfg(windows)]
             BroadcastError::WindowsApi { .. } => true,
         }
@@ -77,6 +129,31 @@ impl BroadcastError {
                 "Use platform-appropriate features",
                 "Check documentation for platform requirements",
             ],
+            BroadcastError::UnknownMessageType { .. } => vec![
+                "Verify the message was produced by the iRacing broadcast protocol",
+                "Update iracing-broadcast to a version that knows this message type",
+            ],
+            BroadcastError::InvalidCarNumber { .. } => vec![
+                "Use one to three digits, keeping any leading zeros (e.g. \"7\", \"07\", \"007\")",
+                "Check the car number against the session's driver list",
+            ],
+            BroadcastError::InvalidParameter { .. } => vec![
+                "Check the parameter against the documented range",
+                "Double-check the units the simulator expects",
+            ],
+            BroadcastError::RateLimited { .. } => vec![
+                "Wait for the reported interval before sending again",
+                "Coalesce or batch rapid updates",
+            ],
+            BroadcastError::Unverified { .. } => vec![
+                "Check that the sim is showing the session the message targets",
+                "Allow a longer verification timeout",
+            ],
+            #[cfg(feature = "std")]
+            BroadcastError::Thread { .. } => vec![
+                "Check the process's thread and memory limits",
+                "Close threads or applications that are no longer needed",
+            ],
             #[cfg(windows)]
             BroadcastError::WindowsApi { .. } => vec![
                 "Check Windows API permissions",
@@ -86,6 +163,85 @@ impl BroadcastError {
         }
     }
 
+    /// [`BroadcastError::recovery_suggestions`] as one block, a `- ` bullet
+    /// per line, for toasts and dialogs.
+    pub fn recovery_text(&self) -> String {
+        self.recovery_suggestions()
+            .iter()
+            .map(|suggestion| format!("- {suggestion}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The error message, then [`BroadcastError::recovery_text`] on the
+    /// following lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::BroadcastError;
+    ///
+    /// let error = BroadcastError::invalid_parameter("chat macro", "16 is outside 1..=15");
+    /// assert_eq!(
+    ///     error.help(),
+    ///     "Invalid chat macro: 16 is outside 1..=15\n\
+    ///      - Check the parameter against the documented range\n\
+    ///      - Double-check the units the simulator expects"
+    /// );
+    /// ```
+    pub fn help(&self) -> String {
+        let recovery = self.recovery_text();
+        if recovery.is_empty() {
+            self.to_string()
+        } else {
+            format!("{self}\n{recovery}")
+        }
+    }
+
+    /// The numeric `HRESULT` of a Windows-only `WindowsApi` error, e.g.
+    /// `0x80070005` (access denied) as an `i32`, for branching on specific
+    /// Windows errors or quoting in support tickets.
+    ///
+    /// `None` for every other variant, and so always off Windows.
+    pub fn hresult(&self) -> Option<i32> {
+        match self {
+            #[cfg(windows)]
+            BroadcastError::WindowsApi { source, .. } => Some(source.code().0),
+            _ => None,
+        }
+    }
+
+    /// Name the Windows operation a Windows-only `WindowsApi` error came
+    /// from, replacing the placeholder the `From<windows_core::Error>`
+    /// conversion uses, so `?` can be used without losing context:
+    ///
+    /// ```ignore
+    /// fn find_sim() -> Result<HWND> {
+    ///     Ok(unsafe { FindWindowW(class, PCWSTR::null()) }?)
+    /// }
+    ///
+    /// let hwnd = find_sim().map_err(|e| e.with_operation("FindWindowW"))?;
+    /// ```
+    ///
+    /// Every other variant, and so every error off Windows, is returned
+    /// unchanged.
+    #[cfg_attr(not(windows), allow(unused_variables))]
+    pub fn with_operation(self, operation: impl Into<String>) -> Self {
+        match self {
+            #[cfg(windows)]
+            BroadcastError::WindowsApi {
+                message_type,
+                source,
+                ..
+            } => BroadcastError::WindowsApi {
+                operation: operation.into(),
+                message_type,
+                source,
+            },
+            other => other,
+        }
+    }
+
     /// Helper constructor for connection errors.
     pub fn connection_failed(reason: impl Into<String>) -> Self {
         BroadcastError::Connection {
@@ -93,11 +249,64 @@ impl BroadcastError {
         }
     }
 
+    /// Helper constructor for unknown message type errors.
+    pub fn unknown_message_type(value: u32) -> Self {
+        BroadcastError::UnknownMessageType { value }
+    }
+
+    /// Helper constructor for invalid car number errors.
+    pub fn invalid_car_number(car_number: impl Into<String>, reason: impl Into<String>) -> Self {
+        BroadcastError::InvalidCarNumber {
+            car_number: car_number.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Helper constructor for out-of-range message parameters.
+    pub fn invalid_parameter(parameter: impl Into<String>, reason: impl Into<String>) -> Self {
+        BroadcastError::InvalidParameter {
+            parameter: parameter.into(),
+            reason: reason.into(),
+        }
+    }
+
+    /// Helper constructor for sends rejected by a client's rate limit.
+    pub fn rate_limited(retry_after: Duration) -> Self {
+        BroadcastError::RateLimited { retry_after }
+    }
+
+    /// Helper constructor for sends whose effect never showed up in
+    /// telemetry.
+    pub fn unverified(message_type: BroadcastMessageType, reason: impl Into<String>) -> Self {
+        BroadcastError::Unverified {
+            message_type,
+            reason: reason.into(),
+        }
+    }
+
+    /// Helper constructor for a background thread that could not be
+    /// started, keeping the I/O error from the OS.
+    #[cfg(feature = "std")]
+    pub fn thread_failed(name: impl Into<String>, source: std::io::Error) -> Self {
+        BroadcastError::Thread {
+            name: name.into(),
+            source: Arc::new(source),
+        }
+    }
+
     /// Helper constructor for Windows API errors.
+    ///
+    /// Pass the type of the message being sent, if any, so logs show what
+    /// failed to send.
     #[cfg(windows)]
-    pub fn windows_api_error(operation: impl Into<String>, source: core::Error) -> Self {
+    pub fn windows_api_error(
+        operation: impl Into<String>,
+        message_type: Option<BroadcastMessageType>,
+        source: windows_core::Error,
+    ) -> Self {
         BroadcastError::WindowsApi {
             operation: operation.into(),
+            message_type,
             source,
         }
     }
@@ -115,11 +324,280 @@ impl BroadcastError {
 }
 
 #[cfg(windows)]
-impl From<core::Error> for BroadcastError {
-    fn from(err: core::Error) -> Self {
+impl From<windows_core::Error> for BroadcastError {
+    fn from(err: windows_core::Error) -> Self {
         BroadcastError::WindowsApi {
-            operation: "Unknown Windows operation".to_string(),
+            operation: "Unknown Windows operation".into(),
+            message_type: None,
             source: err,
         }
     }
 }
+
+/// Lets broadcast calls use `?` in functions returning [`std::io::Result`].
+///
+/// The original error is kept as the inner error, so it can be recovered with
+/// [`std::io::Error::into_inner`] and downcasting.
+#[cfg(feature = "std")]
+impl From<BroadcastError> for std::io::Error {
+    fn from(err: BroadcastError) -> Self {
+        use std::io::ErrorKind;
+
+        let kind = match err {
+            BroadcastError::Connection { .. } => ErrorKind::NotConnected,
+            BroadcastError::UnsupportedPlatform { .. } => ErrorKind::Unsupported,
+            BroadcastError::UnknownMessageType { .. } => ErrorKind::InvalidData,
+            BroadcastError::InvalidCarNumber { .. } | BroadcastError::InvalidParameter { .. } => {
+                ErrorKind::InvalidInput
+            }
+            BroadcastError::RateLimited { .. } => ErrorKind::WouldBlock,
+            BroadcastError::Unverified { .. } => ErrorKind::TimedOut,
+            BroadcastError::Thread { ref source, .. } => source.kind(),
+            #[cfg(windows)]
+            BroadcastError::WindowsApi { .. } => ErrorKind::Other,
+        };
+
+        std::io::Error::new(kind, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::*;
+
+    #[test]
+    fn test_io_error_kinds() {
+        let cases = [
+            (
+                BroadcastError::unsupported_platform("Broadcast Client", "Windows"),
+                io::ErrorKind::Unsupported,
+            ),
+            (
+                BroadcastError::connection_failed("iRacing not detected"),
+                io::ErrorKind::NotConnected,
+            ),
+            (
+                BroadcastError::unknown_message_type(99),
+                io::ErrorKind::InvalidData,
+            ),
+            (
+                BroadcastError::invalid_car_number("1a", "car number may only contain digits"),
+                io::ErrorKind::InvalidInput,
+            ),
+            (
+                BroadcastError::invalid_parameter("chat macro", "16 is outside 1..=15"),
+                io::ErrorKind::InvalidInput,
+            ),
+            (
+                BroadcastError::rate_limited(Duration::from_millis(20)),
+                io::ErrorKind::WouldBlock,
+            ),
+            (
+                BroadcastError::unverified(BroadcastMessageType::PitCommand, "flags unchanged"),
+                io::ErrorKind::TimedOut,
+            ),
+            (
+                BroadcastError::thread_failed("watcher", io::ErrorKind::OutOfMemory.into()),
+                io::ErrorKind::OutOfMemory,
+            ),
+        ];
+
+        for (error, kind) in cases {
+            let message = error.to_string();
+            let io_error = io::Error::from(error);
+            assert_eq!(io_error.kind(), kind);
+            assert_eq!(io_error.to_string(), message);
+            assert!(
+                io_error
+                    .into_inner()
+                    .is_some_and(|inner| inner.is::<BroadcastError>())
+            );
+        }
+    }
+
+    #[test]
+    fn test_clone_each_variant() {
+        let errors = [
+            BroadcastError::connection_failed("iRacing not detected"),
+            BroadcastError::unsupported_platform("Broadcast Client", "Windows"),
+            BroadcastError::unknown_message_type(99),
+            BroadcastError::invalid_car_number("1a", "car number may only contain digits"),
+            BroadcastError::invalid_parameter("chat macro", "16 is outside 1..=15"),
+            BroadcastError::rate_limited(Duration::from_millis(20)),
+            BroadcastError::unverified(BroadcastMessageType::PitCommand, "flags unchanged"),
+            BroadcastError::thread_failed("watcher", io::ErrorKind::OutOfMemory.into()),
+            #[cfg(windows)]
+            BroadcastError::windows_api_error(
+                "SendNotifyMessageW",
+                Some(BroadcastMessageType::PitCommand),
+                windows_core::Error::from_hresult(windows_core::HRESULT(0x8000_4005_u32 as i32)),
+            ),
+        ];
+
+        for error in errors {
+            let clone = error.clone();
+            assert_eq!(format!("{clone:?}"), format!("{error:?}"));
+            assert_eq!(clone.to_string(), error.to_string());
+            assert_eq!(clone.is_retryable(), error.is_retryable());
+        }
+    }
+
+    #[test]
+    fn test_help_for_each_variant() {
+        let cases = [
+            (
+                BroadcastError::connection_failed("iRacing not detected"),
+                "Failed to connect to iRacing: iRacing not detected\n\
+                 - Ensure iRacing is running\n\
+                 - Check Windows permissions for shared memory access\n\
+                 - Verify iRacing SDK version compatibility\n\
+                 - Try restarting iRacing",
+            ),
+            (
+                BroadcastError::unsupported_platform("Broadcast Client", "Windows"),
+                "Broadcast Client is only available on Windows\n\
+                 - Use platform-appropriate features\n\
+                 - Check documentation for platform requirements",
+            ),
+            (
+                BroadcastError::unknown_message_type(99),
+                "Unknown broadcast message type: 99\n\
+                 - Verify the message was produced by the iRacing broadcast protocol\n\
+                 - Update iracing-broadcast to a version that knows this message type",
+            ),
+            (
+                BroadcastError::invalid_car_number("1a", "car number may only contain digits"),
+                "Invalid car number '1a': car number may only contain digits\n\
+                 - Use one to three digits, keeping any leading zeros (e.g. \"7\", \"07\", \"007\")\n\
+                 - Check the car number against the session's driver list",
+            ),
+            (
+                BroadcastError::invalid_parameter("chat macro", "16 is outside 1..=15"),
+                "Invalid chat macro: 16 is outside 1..=15\n\
+                 - Check the parameter against the documented range\n\
+                 - Double-check the units the simulator expects",
+            ),
+            (
+                BroadcastError::rate_limited(Duration::from_millis(20)),
+                "Rate limited: next send allowed in 20ms\n\
+                 - Wait for the reported interval before sending again\n\
+                 - Coalesce or batch rapid updates",
+            ),
+            (
+                BroadcastError::unverified(BroadcastMessageType::PitCommand, "flags unchanged"),
+                "PitCommand had no visible effect: flags unchanged\n\
+                 - Check that the sim is showing the session the message targets\n\
+                 - Allow a longer verification timeout",
+            ),
+            (
+                BroadcastError::thread_failed("watcher", io::ErrorKind::OutOfMemory.into()),
+                "Failed to start the watcher thread: out of memory\n\
+                 - Check the process's thread and memory limits\n\
+                 - Close threads or applications that are no longer needed",
+            ),
+        ];
+
+        for (error, expected) in cases {
+            assert_eq!(error.help(), expected);
+            let (message, recovery) = expected.split_once('\n').unwrap();
+            assert_eq!(error.to_string(), message);
+            assert_eq!(error.recovery_text(), recovery);
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_api_error_help() {
+        let error = BroadcastError::windows_api_error(
+            "SendNotifyMessageW",
+            None,
+            windows_core::Error::empty(),
+        );
+        assert_eq!(
+            error.help(),
+            "Windows API error: SendNotifyMessageW\n\
+             - Check Windows API permissions\n\
+             - Verify system resources availability\n\
+             - Check Windows version compatibility"
+        );
+    }
+
+    #[test]
+    fn test_hresult_only_for_windows_errors() {
+        assert_eq!(
+            BroadcastError::connection_failed("sim not running").hresult(),
+            None
+        );
+        assert_eq!(BroadcastError::unknown_message_type(99).hresult(), None);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_api_error_hresult() {
+        const E_ACCESSDENIED: i32 = 0x8007_0005_u32 as i32;
+        let error = BroadcastError::windows_api_error(
+            "SendNotifyMessageW",
+            None,
+            windows_core::Error::from_hresult(windows_core::HRESULT(E_ACCESSDENIED)),
+        );
+        assert_eq!(error.hresult(), Some(E_ACCESSDENIED));
+    }
+
+    #[test]
+    fn test_with_operation_keeps_other_variants() {
+        let error = BroadcastError::connection_failed("sim not running").with_operation("Probe");
+        assert_eq!(
+            error.to_string(),
+            "Failed to connect to iRacing: sim not running"
+        );
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_with_operation_names_converted_errors() {
+        fn convert() -> Result<()> {
+            Err(windows_core::Error::empty())?
+        }
+
+        let error = convert()
+            .map_err(|e| e.with_operation("FindWindowW"))
+            .unwrap_err();
+        assert!(matches!(
+            &error,
+            BroadcastError::WindowsApi { operation, message_type: None, .. }
+                if operation == "FindWindowW"
+        ));
+        assert_eq!(error.to_string(), "Windows API error: FindWindowW");
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_api_error_io_kind() {
+        let error = BroadcastError::windows_api_error(
+            "SendNotifyMessageW",
+            None,
+            windows_core::Error::empty(),
+        );
+        assert_eq!(io::Error::from(error).kind(), io::ErrorKind::Other);
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn test_windows_api_error_carries_message_type() {
+        let error = BroadcastError::windows_api_error(
+            "SendNotifyMessageW",
+            Some(BroadcastMessageType::PitCommand),
+            windows_core::Error::empty(),
+        );
+
+        assert!(matches!(
+            error,
+            BroadcastError::WindowsApi {
+                message_type: Some(BroadcastMessageType::PitCommand),
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,212 @@
+//! A hidden window pumped on its own thread, for receiving broadcast
+//! messages.
+//!
+//! [`Listener`](crate::Listener) and the test receiver differ only in the
+//! kind of window and what they do with a message, so the unsafe Win32 code
+//! lives here once.
+
+use std::{
+    cell::RefCell,
+    ffi::c_void,
+    io,
+    sync::{Mutex, PoisonError, mpsc},
+    thread::{self, JoinHandle},
+};
+
+use windows::Win32::{
+    Foundation::{HWND, LPARAM, LRESULT, WPARAM},
+    System::LibraryLoader::GetModuleHandleW,
+    UI::WindowsAndMessaging::{
+        CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, HWND_MESSAGE, MSG,
+        PostMessageW, PostQuitMessage, RegisterClassExW, WINDOW_EX_STYLE, WINDOW_STYLE, WM_CLOSE,
+        WM_DESTROY, WNDCLASSEXW,
+    },
+};
+use windows_core::{PCWSTR, w};
+
+use crate::{BroadcastError, Result};
+
+/// Called on the pump thread with the `(WPARAM, LPARAM)` of every message
+/// with the window's registered message id.
+pub(crate) type Handler = Box<dyn FnMut(usize, isize) + Send>;
+
+const CLASS_NAME: PCWSTR = w!("IRacingBroadcastHiddenWindow");
+
+thread_local! {
+    /// Registered message id and handler of the window owned by the current
+    /// pump thread.
+    static HANDLER: RefCell<Option<(u32, Handler)>> = const { RefCell::new(None) };
+}
+
+/// Which kind of hidden window to create.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WindowKind {
+    /// A zero-sized top-level window, which `HWND_BROADCAST` reaches.
+    #[cfg_attr(not(feature = "listener"), allow(dead_code))]
+    TopLevel,
+    /// A message-only window, which only sees messages sent to its handle.
+    #[cfg_attr(not(test), allow(dead_code))]
+    MessageOnly,
+}
+
+/// A hidden window and the thread pumping its messages. Dropping it
+/// destroys the window and joins the thread.
+#[derive(Debug)]
+pub(crate) struct HiddenWindow {
+    /// The window, stored as an address since `HWND` is not `Send`.
+    window: usize,
+    pump: Option<JoinHandle<()>>,
+}
+
+impl HiddenWindow {
+    /// Create a `kind` window on a new thread named
+    /// `iracing-broadcast-{name}` and run `handler` for every message with
+    /// id `message_id`.
+    pub(crate) fn spawn(
+        name: &str,
+        kind: WindowKind,
+        message_id: u32,
+        handler: Handler,
+    ) -> Result<Self> {
+        let (ready, created) = mpsc::sync_channel(1);
+
+        let pump = thread::Builder::new()
+            .name(format!("iracing-broadcast-{name}"))
+            .spawn(move || pump(kind, message_id, handler, ready))
+            .map_err(|error| BroadcastError::thread_failed(name, error))?;
+
+        match created.recv() {
+            Ok(Ok(window)) => Ok(HiddenWindow {
+                window,
+                pump: Some(pump),
+            }),
+            Ok(Err(error)) => {
+                let _ = pump.join();
+                Err(error)
+            }
+            Err(_) => Err(BroadcastError::thread_failed(
+                name,
+                io::Error::other("the thread exited before creating its window"),
+            )),
+        }
+    }
+
+    /// The window's handle.
+    pub(crate) fn hwnd(&self) -> HWND {
+        HWND(self.window as *mut c_void)
+    }
+}
+
+impl Drop for HiddenWindow {
+    fn drop(&mut self) {
+        // Closing the window ends the pump loop; see `window_proc`.
+        let closed = unsafe { PostMessageW(Some(self.hwnd()), WM_CLOSE, WPARAM(0), LPARAM(0)) };
+        if let (Ok(()), Some(pump)) = (closed, self.pump.take()) {
+            let _ = pump.join();
+        }
+    }
+}
+
+/// Body of the pump thread: create the window, report it through `ready`,
+/// then dispatch messages until the window is destroyed.
+fn pump(
+    kind: WindowKind,
+    message_id: u32,
+    handler: Handler,
+    ready: mpsc::SyncSender<Result<usize>>,
+) {
+    let window = match create_window(kind) {
+        Ok(window) => window,
+        Err(error) => {
+            let _ = ready.send(Err(error));
+            return;
+        }
+    };
+
+    HANDLER.set(Some((message_id, handler)));
+    let _ = ready.send(Ok(window.0 as usize));
+
+    let mut msg = MSG::default();
+    // `GetMessageW` returns 0 for `WM_QUIT` and -1 on error.
+    while unsafe { GetMessageW(&mut msg, None, 0, 0) }.0 > 0 {
+        unsafe { DispatchMessageW(&msg) };
+    }
+
+    HANDLER.set(None);
+}
+
+fn create_window(kind: WindowKind) -> Result<HWND> {
+    let instance = unsafe { GetModuleHandleW(None) }
+        .map_err(|error| BroadcastError::windows_api_error("GetModuleHandleW", None, error))?;
+
+    static REGISTERED: Mutex<bool> = Mutex::new(false);
+    let mut registered = REGISTERED.lock().unwrap_or_else(PoisonError::into_inner);
+    if !*registered {
+        let class = WNDCLASSEXW {
+            cbSize: size_of::<WNDCLASSEXW>() as u32,
+            lpfnWndProc: Some(window_proc),
+            hInstance: instance.into(),
+            lpszClassName: CLASS_NAME,
+            ..Default::default()
+        };
+
+        if unsafe { RegisterClassExW(&class) } == 0 {
+            return Err(BroadcastError::windows_api_error(
+                "RegisterClassExW",
+                None,
+                windows_core::Error::from_thread(),
+            ));
+        }
+        *registered = true;
+    }
+    drop(registered);
+
+    let parent = match kind {
+        WindowKind::TopLevel => None,
+        WindowKind::MessageOnly => Some(HWND_MESSAGE),
+    };
+
+    unsafe {
+        CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            CLASS_NAME,
+            w!("iRacing broadcast receiver"),
+            WINDOW_STYLE::default(),
+            0,
+            0,
+            0,
+            0,
+            parent,
+            None,
+            Some(instance.into()),
+            None,
+        )
+    }
+    .map_err(|error| BroadcastError::windows_api_error("CreateWindowExW", None, error))
+}
+
+unsafe extern "system" fn window_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if msg == WM_DESTROY {
+        unsafe { PostQuitMessage(0) };
+        return LRESULT(0);
+    }
+
+    let handled = HANDLER.with_borrow_mut(|handler| match handler {
+        Some((message_id, handler)) if *message_id == msg => {
+            handler(wparam.0, lparam.0);
+            true
+        }
+        _ => false,
+    });
+
+    if handled {
+        LRESULT(0)
+    } else {
+        unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) }
+    }
+}
@@ -1,36 +1,132 @@
 //! iRacing broadcast message services in Rust.
 //!
 //! This crate provides a thin, documented wrapper around the iRacing broadcast
-//! window message protocol. Typical usage involves constructing a [`Client`]
-//! and sending typed [`BroadcastMessage`] values:
+//! window message protocol.
+#![cfg_attr(
+    feature = "std",
+    doc = r#"Typical usage involves constructing a [`Client`]
+and sending typed [`BroadcastMessage`] values:
+
+```no_run
+use iracing_broadcast::{BroadcastMessage, Client, PitCommandMode};
+
+let client = Client::new()?;
+client.send_message(BroadcastMessage::PitCommand(PitCommandMode::Tearoff))?;
+# Ok::<(), iracing_broadcast::BroadcastError>(())
+```
+
+The crate builds on every target so cross-platform workspaces can depend
+on it. Off Windows, message types and encoding work as usual, but
+[`Client::new`] returns [`BroadcastError::UnsupportedPlatform`]; only
+[offline](Client::offline) clients can send. APIs that take a window
+handle are Windows-only.
+"#
+)]
 //!
-//! ```no_run
-//! use iracing_broadcast::{BroadcastMessage, Client, PitCommandMode};
+//! Numeric parameters follow one policy. Enum variants take the wire
+//! field's own width, so they cannot wrap. Constructors for values held in
+//! wider integers come in pairs: `checked_*` ones, such as
+//! [`PitCommandMode::checked_fuel`], return
+//! [`BroadcastError::InvalidParameter`] for values out of range, and
+//! `saturating_*` ones, such as [`PitCommandMode::saturating_fuel`], clamp
+//! them into range instead.
 //!
-//! let client = Client::new()?;
-//! client.send_message(BroadcastMessage::PitCommand(PitCommandMode::Tearoff))?;
-//! # Ok::<(), iracing_broadcast::BroadcastError>(())
-//! ```
+//! Without the default `std` feature the crate is `no_std`, needing only
+//! `alloc`. [`BroadcastMessage`], the mode enums, [`PitServicePlan`] and
+//! the encoding helpers remain, so embedded or WASM front-ends can build
+//! messages and [`BroadcastMessage::encode`] them for a Windows agent to
+//! send. Everything that sends, starting with `Client`, needs `std`.
 //!
 //! The API is intentionally minimal and mirrors the documented iRacing SDK
 //! constants. Consult the type-level documentation for details on each message
 //! and its parameters.
 
-#[cfg(not(windows))]
-compile_error!(
-    "iracing-broadcast currently only supports Windows targets because the iRacing \
-     broadcast API is delivered via Windows messages. Please build with a Windows \
-     target triple."
-);
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+extern crate alloc;
+
+mod broadcast;
+#[cfg(feature = "std")]
+mod builder;
+#[cfg(feature = "std")]
+mod camera;
+mod car_number;
+#[cfg(feature = "std")]
+mod chat;
+#[cfg(feature = "std")]
 mod client;
+#[cfg(feature = "std")]
+mod coalesce;
+pub mod command;
 mod error;
+#[cfg(all(windows, any(feature = "listener", test)))]
+mod hidden_window;
+#[cfg(all(windows, feature = "listener"))]
+mod listener;
+mod macros;
 mod message;
+mod pit;
+#[cfg(feature = "std")]
+mod rate_limit;
+#[cfg(feature = "std")]
+mod recording;
+#[cfg(feature = "std")]
+mod replay;
+#[cfg(feature = "std")]
+mod retry;
+#[cfg(feature = "std")]
+mod scrubber;
+#[cfg(feature = "serve")]
+pub mod serve;
+#[cfg(feature = "std")]
+mod sink;
+#[cfg(feature = "telemetry")]
+mod telemetry;
+#[cfg(all(test, windows))]
+mod test_receiver;
+pub mod units;
 mod util;
+#[cfg(feature = "std")]
+mod watch;
 
-pub use client::{BroadcastMessage, Client};
+pub use broadcast::{BroadcastMessage, WireMessage};
+#[cfg(feature = "std")]
+pub use builder::{ClientBuilder, Target};
+#[cfg(feature = "std")]
+pub use camera::CameraController;
+pub use car_number::CarNumber;
+#[cfg(feature = "std")]
+pub use client::{BroadcastMessageProvider, Client, Delivery, SendMode, SendOutcome};
+#[cfg(feature = "std")]
+pub use coalesce::CoalescingSender;
 pub use error::*;
+#[cfg(all(windows, feature = "listener"))]
+pub use listener::{Listener, ReceivedMessage};
 pub use message::{
-    BroadcastMessageType, CameraState, ChatCommandMode, PitCommandMode, ReplayPositionMode,
-    ReplaySearchMode, TelemetryCommandMode, VideoCaptureMode,
+    BroadcastMessageType, CameraFocusMode, CameraGroup, CameraState, CameraStateBuilder,
+    ChatCommandMode, FfbForce, MAX_TIRE_PRESSURE_PSI, MIN_TIRE_PRESSURE_PSI, PitCommandMode,
+    ReloadTexturesMode, ReplayPositionMode, ReplaySearchMode, ReplaySpeed, TelemetryCommandMode,
+    VideoCaptureMode,
 };
+pub use pit::{PitServicePlan, PitTireSet};
+#[cfg(feature = "std")]
+pub use rate_limit::RateLimitMode;
+#[cfg(feature = "std")]
+pub use recording::{MessagePlayer, MessageRecorder, RecordedMessage};
+#[cfg(feature = "std")]
+pub use replay::ReplayController;
+#[cfg(feature = "std")]
+pub use retry::RetryPolicy;
+#[cfg(feature = "std")]
+pub use scrubber::ReplayScrubber;
+#[cfg(feature = "std")]
+pub use sink::{MessageSink, RecordingSink};
+#[cfg(feature = "telemetry")]
+pub use telemetry::Telemetry;
+pub use util::{pack, pad_car_number, try_pad_car_number, unpad_car_number};
+#[cfg(feature = "std")]
+pub use watch::SimWatcher;
+
+// Window handle types accepted by `Client::send_message_to`.
+#[cfg(all(windows, feature = "std"))]
+pub use windows::Win32::{Foundation::HWND, UI::WindowsAndMessaging::HWND_BROADCAST};
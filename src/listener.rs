@@ -0,0 +1,121 @@
+//! Receiving broadcast messages sent by other controllers.
+
+use std::{sync::mpsc, time::Duration};
+
+use crate::{
+    BroadcastMessage, BroadcastMessageType, Client, Result,
+    hidden_window::{Handler, HiddenWindow, WindowKind},
+};
+
+/// A received message: its type and three parameter words, as returned by
+/// [`BroadcastMessage::decode`].
+pub type ReceivedMessage = (BroadcastMessageType, u16, u16, u16);
+
+/// Receives broadcast messages sent by any process on this desktop,
+/// including other controllers and this process's own [`Client`]s.
+///
+/// The listener owns a hidden top-level window and a thread pumping its
+/// messages; dropping the listener destroys both. Message-only windows cannot
+/// be used here because Windows never delivers `HWND_BROADCAST` messages to
+/// them. Messages whose type is unknown are skipped.
+///
+/// Requires the `listener` feature.
+///
+/// # Examples
+///
+/// ```no_run
+/// use iracing_broadcast::Listener;
+///
+/// let listener = Listener::new()?;
+/// while let Some((message_type, var1, var2, var3)) = listener.recv() {
+///     println!("{message_type:?}: {var1} {var2} {var3}");
+/// }
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+#[derive(Debug)]
+pub struct Listener {
+    /// Pumps messages for as long as the listener lives.
+    _window: HiddenWindow,
+    receiver: mpsc::Receiver<ReceivedMessage>,
+}
+
+impl Listener {
+    /// Start listening; received messages are read with
+    /// [`Listener::recv`] and friends.
+    pub fn new() -> Result<Self> {
+        Self::with_callback(|_| {})
+    }
+
+    /// Start listening and run `callback` on the pump thread for every
+    /// received message, before it is queued for [`Listener::recv`].
+    pub fn with_callback<F>(callback: F) -> Result<Self>
+    where
+        F: FnMut(ReceivedMessage) + Send + 'static,
+    {
+        let message_id = Client::new()?.message_id();
+        let (sender, receiver) = mpsc::channel();
+
+        let mut callback = callback;
+        let handler: Handler = Box::new(move |wparam, lparam| {
+            match BroadcastMessage::decode(wparam, lparam) {
+                Ok(message) => {
+                    callback(message);
+                    // The receiver only goes away while the listener is dropped.
+                    let _ = sender.send(message);
+                }
+                #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+                Err(error) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(%error, "ignoring undecodable broadcast message");
+                }
+            }
+        });
+
+        // Message-only windows never receive `HWND_BROADCAST` messages.
+        let window = HiddenWindow::spawn("listener", WindowKind::TopLevel, message_id, handler)?;
+        Ok(Listener {
+            _window: window,
+            receiver,
+        })
+    }
+
+    /// Block until the next message arrives.
+    ///
+    /// Returns `None` if the pump thread has stopped.
+    pub fn recv(&self) -> Option<ReceivedMessage> {
+        self.receiver.recv().ok()
+    }
+
+    /// Like [`Listener::recv`], giving up after `timeout`.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<ReceivedMessage> {
+        self.receiver.recv_timeout(timeout).ok()
+    }
+
+    /// The next message if one has already arrived.
+    pub fn try_recv(&self) -> Option<ReceivedMessage> {
+        self.receiver.try_recv().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PitCommandMode;
+
+    #[test]
+    fn test_listener_observes_own_broadcast() {
+        let listener = Listener::new().unwrap();
+        let client = Client::new().unwrap();
+
+        let (var1, var2) = PitCommandMode::Fuel(40).encode();
+        client
+            .send_message(BroadcastMessage::PitCommand(PitCommandMode::Fuel(40)))
+            .unwrap();
+
+        // Other tests broadcast concurrently, so skip anything else.
+        let expected = (BroadcastMessageType::PitCommand, var1, var2, 0);
+        let received = std::iter::from_fn(|| listener.recv_timeout(Duration::from_secs(5)))
+            .find(|message| *message == expected);
+        assert_eq!(received, Some(expected));
+    }
+}
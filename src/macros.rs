@@ -0,0 +1,135 @@
+//! Declaring application-specific messages.
+
+/// Declare an enum of application-specific messages, each variant built
+/// from a [`BroadcastMessage`](crate::BroadcastMessage) expression.
+///
+/// The macro defines the enum and a `From` conversion into
+/// `BroadcastMessage`, so values can be passed straight to
+/// `Client::send_message` and anything else
+/// taking a `BroadcastMessageProvider`,
+/// and are packed by [`BroadcastMessage::encode`](crate::BroadcastMessage::encode)
+/// like any other message. This keeps the vocabulary of a tool, or an
+/// experiment with a new SDK build, in one place without forking the
+/// crate.
+///
+/// Each variant is a name, optional typed fields in parentheses, and after
+/// `=>` the message it stands for, which may use the fields by name.
+/// Attributes, including doc comments and derives, are passed through to
+/// the enum and its variants.
+///
+/// Custom variants still encode to the message types this crate knows. For
+/// a message type it does not model yet, send the words with
+/// `Client::send_raw`.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::{
+///     BroadcastMessage, CameraFocusMode, PitCommandMode, broadcast_message,
+/// };
+///
+/// broadcast_message! {
+///     /// Cues used by our race director panel.
+///     #[derive(Debug, Clone, Copy)]
+///     pub enum DirectorCue {
+///         /// Show the leader on the TV1 camera group.
+///         Leader => BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Leader, 11, 0),
+///         /// Show a car by number.
+///         Car(number: &'static str, group: u8) =>
+///             BroadcastMessage::camera_switch_number(number, group, 0),
+///         /// Ask for a pit stop with a fixed fuel load.
+///         Splash(liters: u8) => BroadcastMessage::PitCommand(PitCommandMode::Fuel(liters)),
+///     }
+/// }
+///
+/// let (_, var1, var2, var3) = BroadcastMessage::from(DirectorCue::Car("064", 11)).encode();
+/// assert_eq!((var1, var2, var3), (3064, 11, 0));
+/// ```
+#[macro_export]
+macro_rules! broadcast_message {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident $(( $($field:ident : $field_ty:ty),* $(,)? ))? => $message:expr
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant $(( $($field_ty),* ))?,
+            )*
+        }
+
+        impl ::core::convert::From<$name> for $crate::BroadcastMessage {
+            fn from(message: $name) -> Self {
+                match message {
+                    $(
+                        $name::$variant $(( $($field),* ))? => $message,
+                    )*
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BroadcastMessage, BroadcastMessageType, ChatCommandMode, ReplaySpeed};
+
+    broadcast_message! {
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        enum Cue {
+            Reply => BroadcastMessage::ChatCommand(ChatCommandMode::Reply),
+            SlowMotion(speed: i8) => BroadcastMessage::ReplaySetPlaySpeed(
+                ReplaySpeed::new(speed, true).unwrap_or(ReplaySpeed::paused()),
+            ),
+            Car(number: &'static str, group: u8, camera: u8,) =>
+                BroadcastMessage::camera_switch_number(number, group, camera),
+        }
+    }
+
+    #[test]
+    fn test_variants_convert_with_their_fields() {
+        assert_eq!(
+            BroadcastMessage::from(Cue::Reply),
+            BroadcastMessage::ChatCommand(ChatCommandMode::Reply)
+        );
+
+        let (message_type, var1, var2, var3) = BroadcastMessage::from(Cue::SlowMotion(4)).encode();
+        assert!(matches!(
+            message_type,
+            BroadcastMessageType::ReplaySetPlaySpeed
+        ));
+        assert_eq!((var1, var2, var3), (4, 1, 0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_custom_messages_send_like_builtin_ones() {
+        use crate::{Client, MessageSink, RecordingSink};
+
+        let sink = RecordingSink::new();
+        sink.send(Cue::Car("007", 2, 1).into()).unwrap();
+        assert_eq!(
+            sink.messages(),
+            [BroadcastMessage::camera_switch_number("007", 2, 1)]
+        );
+
+        let sent = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let client = Client::offline().with_observer({
+            let sent = std::sync::Arc::clone(&sent);
+            move |_, params| sent.lock().unwrap().push(params)
+        });
+        client.send_message(Cue::Reply).unwrap();
+        client
+            .send_message(BroadcastMessage::from(Cue::Reply))
+            .unwrap();
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent[0], sent[1]);
+    }
+}
@@ -1,36 +1,112 @@
+use alloc::format;
+use core::fmt;
+
 use bitflags::bitflags;
 
+use crate::{
+    BroadcastError, Result,
+    units::{gallons_to_liters, psi_to_kpa},
+    util::{checked_param, round, saturating_param},
+};
+
 /// Identifiers for broadcast messages recognized by the iRacing simulator.
+///
+/// Types order by their wire discriminant, so sorted collections keyed by
+/// message type iterate in protocol order.
+///
+/// Every discriminant is the SDK's `irsdk_BroadcastMsg` value and is part of
+/// the wire format: never renumber a variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u32)]
 pub enum BroadcastMessageType {
     /// Switch to a camera by position index.
     CameraSwitchPosition = 0,
     /// Switch to a camera by car number.
-    CameraSwitchNumber,
+    CameraSwitchNumber = 1,
     /// Update the camera state bitfield.
-    CameraSetState,
+    CameraSetState = 2,
     /// Change replay playback speed.
-    ReplaySetPlaySpeed,
+    ReplaySetPlaySpeed = 3,
     /// Move to a specific replay position.
-    ReplaySetPlayPosition,
+    ReplaySetPlayPosition = 4,
     /// Perform a replay search.
-    ReplaySearch,
-    /// Toggle the replay state.
-    ReplaySetState,
+    ReplaySearch = 5,
+    /// Change the replay tape; the only state change is erasing it.
+    ReplaySetState = 6,
     /// Reload one or more textures.
-    ReloadTextures,
+    ReloadTextures = 7,
     /// Issue a chat command.
-    ChatCommand,
+    ChatCommand = 8,
     /// Issue a pit command.
-    PitCommand,
+    PitCommand = 9,
     /// Control telemetry capture.
-    TelemetryCommand,
+    TelemetryCommand = 10,
     /// Send a force-feedback command.
-    FFBCommand,
+    FFBCommand = 11,
     /// Search to a session-relative time.
-    ReplaySearchSessionTime,
+    ReplaySearchSessionTime = 12,
     /// Control screenshot or capture recording.
-    VideoCapture,
+    VideoCapture = 13,
+}
+
+impl BroadcastMessageType {
+    /// Every variant, in protocol order.
+    pub const ALL: &'static [Self] = &[
+        BroadcastMessageType::CameraSwitchPosition,
+        BroadcastMessageType::CameraSwitchNumber,
+        BroadcastMessageType::CameraSetState,
+        BroadcastMessageType::ReplaySetPlaySpeed,
+        BroadcastMessageType::ReplaySetPlayPosition,
+        BroadcastMessageType::ReplaySearch,
+        BroadcastMessageType::ReplaySetState,
+        BroadcastMessageType::ReloadTextures,
+        BroadcastMessageType::ChatCommand,
+        BroadcastMessageType::PitCommand,
+        BroadcastMessageType::TelemetryCommand,
+        BroadcastMessageType::FFBCommand,
+        BroadcastMessageType::ReplaySearchSessionTime,
+        BroadcastMessageType::VideoCapture,
+    ];
+
+    /// The wire discriminant, usable in `const` contexts.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::BroadcastMessageType;
+    ///
+    /// const PIT_COMMAND: u32 = BroadcastMessageType::PitCommand.as_u32();
+    /// assert_eq!(PIT_COMMAND, 9);
+    /// ```
+    pub const fn as_u32(self) -> u32 {
+        self as u32
+    }
+}
+
+impl TryFrom<u32> for BroadcastMessageType {
+    type Error = BroadcastError;
+
+    /// Map a raw wire discriminant back to its message type.
+    fn try_from(value: u32) -> Result<Self> {
+        match value {
+            0 => Ok(BroadcastMessageType::CameraSwitchPosition),
+            1 => Ok(BroadcastMessageType::CameraSwitchNumber),
+            2 => Ok(BroadcastMessageType::CameraSetState),
+            3 => Ok(BroadcastMessageType::ReplaySetPlaySpeed),
+            4 => Ok(BroadcastMessageType::ReplaySetPlayPosition),
+            5 => Ok(BroadcastMessageType::ReplaySearch),
+            6 => Ok(BroadcastMessageType::ReplaySetState),
+            7 => Ok(BroadcastMessageType::ReloadTextures),
+            8 => Ok(BroadcastMessageType::ChatCommand),
+            9 => Ok(BroadcastMessageType::PitCommand),
+            10 => Ok(BroadcastMessageType::TelemetryCommand),
+            11 => Ok(BroadcastMessageType::FFBCommand),
+            12 => Ok(BroadcastMessageType::ReplaySearchSessionTime),
+            13 => Ok(BroadcastMessageType::VideoCapture),
+            _ => Err(BroadcastError::unknown_message_type(value)),
+        }
+    }
 }
 
 impl From<BroadcastMessageType> for usize {
@@ -50,7 +126,12 @@ bitflags! {
     ///
     /// let very_scenic = CameraState::UI_HIDDEN | CameraState::IS_SCENIC_ACTIVE;
     /// ```
-    #[derive(Default)]
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(
+        feature = "serde",
+        derive(serde::Serialize, serde::Deserialize),
+        serde(transparent)
+    )]
     pub struct CameraState: u32 {
         const IS_SESSION_SCREEN = 0x01;
         const IS_SCENIC_ACTIVE = 0x02;
@@ -65,7 +146,635 @@ bitflags! {
     }
 }
 
+impl CameraState {
+    /// Start a [`CameraStateBuilder`] with no flags set.
+    pub fn builder() -> CameraStateBuilder {
+        CameraStateBuilder::default()
+    }
+
+    /// Start a [`CameraStateBuilder`] from this state, so individual flags
+    /// can be flipped while keeping the rest.
+    pub fn to_builder(self) -> CameraStateBuilder {
+        CameraStateBuilder { state: self }
+    }
+
+    /// Flags that change going from `self` to `other`, as
+    /// `(added, removed)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::CameraState;
+    ///
+    /// let sent = CameraState::IS_SCENIC_ACTIVE | CameraState::CAM_TOOL_ACTIVE;
+    /// let wanted = CameraState::UI_HIDDEN | CameraState::CAM_TOOL_ACTIVE;
+    ///
+    /// let (added, removed) = sent.diff(wanted);
+    /// assert_eq!(added, CameraState::UI_HIDDEN);
+    /// assert_eq!(removed, CameraState::IS_SCENIC_ACTIVE);
+    /// ```
+    pub fn diff(&self, other: CameraState) -> (CameraState, CameraState) {
+        (other.difference(*self), self.difference(other))
+    }
+}
+
+/// Fluent builder for [`CameraState`].
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::CameraState;
+///
+/// let state = CameraState::builder().scenic(true).ui_hidden(true).build();
+/// assert_eq!(state, CameraState::IS_SCENIC_ACTIVE | CameraState::UI_HIDDEN);
+///
+/// let shown = state.to_builder().ui_hidden(false).build();
+/// assert_eq!(shown, CameraState::IS_SCENIC_ACTIVE);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CameraStateBuilder {
+    state: CameraState,
+}
+
+impl CameraStateBuilder {
+    /// Set whether the session screen is showing.
+    pub fn session_screen(self, enabled: bool) -> Self {
+        self.flag(CameraState::IS_SESSION_SCREEN, enabled)
+    }
+
+    /// Set whether the scenic camera is active.
+    pub fn scenic(self, enabled: bool) -> Self {
+        self.flag(CameraState::IS_SCENIC_ACTIVE, enabled)
+    }
+
+    /// Set whether the camera tool is open.
+    pub fn cam_tool(self, enabled: bool) -> Self {
+        self.flag(CameraState::CAM_TOOL_ACTIVE, enabled)
+    }
+
+    /// Set whether the sim UI is hidden.
+    pub fn ui_hidden(self, enabled: bool) -> Self {
+        self.flag(CameraState::UI_HIDDEN, enabled)
+    }
+
+    /// Set whether automatic shot selection is used.
+    pub fn auto_shot_selection(self, enabled: bool) -> Self {
+        self.flag(CameraState::USE_AUTO_SHOT_SELECTION, enabled)
+    }
+
+    /// Set whether camera edits are temporary.
+    pub fn temporary_edits(self, enabled: bool) -> Self {
+        self.flag(CameraState::USE_TEMPORARY_EDITS, enabled)
+    }
+
+    /// Set whether camera keys accelerate.
+    pub fn key_acceleration(self, enabled: bool) -> Self {
+        self.flag(CameraState::USE_KEY_ACCELERATION, enabled)
+    }
+
+    /// Set whether camera keys accelerate 10x.
+    pub fn key_10x_acceleration(self, enabled: bool) -> Self {
+        self.flag(CameraState::USE_KEY_10X_ACCELERATION, enabled)
+    }
+
+    /// Set whether mouse aim mode is active.
+    pub fn mouse_aim(self, enabled: bool) -> Self {
+        self.flag(CameraState::USE_MOUSE_AIM_MODE, enabled)
+    }
+
+    /// Set or clear an arbitrary set of flags.
+    pub fn flag(mut self, flag: CameraState, enabled: bool) -> Self {
+        self.state.set(flag, enabled);
+        self
+    }
+
+    /// Finish building the [`CameraState`].
+    pub fn build(self) -> CameraState {
+        self.state
+    }
+}
+
+impl From<CameraState> for CameraStateBuilder {
+    fn from(state: CameraState) -> Self {
+        state.to_builder()
+    }
+}
+
+impl fmt::Display for CameraState {
+    /// Writes the set flag names separated by `|`, or `none`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return f.write_str("none");
+        }
+        bitflags::parser::to_writer(self, f)
+    }
+}
+
+/// Automatic focus targets for camera switch messages.
+///
+/// The SDK reserves negative values in the position word so the camera system
+/// can pick a car on its own instead of following a fixed grid position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CameraFocusMode {
+    /// Focus on the most recent incident.
+    Incident,
+    /// Focus on the race leader.
+    Leader,
+    /// Focus on the most exciting on-track action.
+    Exciting,
+    /// Focus on the car in the given race position.
+    Position(u8),
+    /// Send a signed position word as-is, packed as two's complement.
+    ///
+    /// Covers sentinels this crate does not name yet; the documented ones are
+    /// `-3` ([`Incident`](CameraFocusMode::Incident)), `-2`
+    /// ([`Leader`](CameraFocusMode::Leader)) and `-1`
+    /// ([`Exciting`](CameraFocusMode::Exciting)).
+    Raw(i16),
+}
+
+impl CameraFocusMode {
+    /// Encode into the position word expected by the broadcast API.
+    pub fn encode(self) -> u16 {
+        match self {
+            CameraFocusMode::Incident => -3i16 as u16,
+            CameraFocusMode::Leader => -2i16 as u16,
+            CameraFocusMode::Exciting => -1i16 as u16,
+            CameraFocusMode::Position(position) => position as u16,
+            CameraFocusMode::Raw(position) => position as u16,
+        }
+    }
+
+    /// Decode a position word, the inverse of [`CameraFocusMode::encode`].
+    ///
+    /// Words that are neither a documented sentinel nor a `u8` position come
+    /// back as [`CameraFocusMode::Raw`].
+    pub fn decode(word: u16) -> Self {
+        match word as i16 {
+            -3 => CameraFocusMode::Incident,
+            -2 => CameraFocusMode::Leader,
+            -1 => CameraFocusMode::Exciting,
+            position => u8::try_from(position)
+                .map_or(CameraFocusMode::Raw(position), CameraFocusMode::Position),
+        }
+    }
+}
+
+impl fmt::Display for CameraFocusMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CameraFocusMode::Incident => f.write_str("incident"),
+            CameraFocusMode::Leader => f.write_str("leader"),
+            CameraFocusMode::Exciting => f.write_str("most exciting car"),
+            CameraFocusMode::Position(position) => write!(f, "position {position}"),
+            CameraFocusMode::Raw(position) => write!(f, "position word {position}"),
+        }
+    }
+}
+
+/// A camera group number, the sim's `GroupNum` from the session info.
+///
+/// Groups are numbered per session, so the same name can have a different
+/// number at another track. The named constructors and
+/// [`CameraGroup::from_name`] use the numbering of a typical road-course
+/// session; when the session's own `CameraInfo` is at hand, look names up
+/// there with [`CameraGroup::from_name_in`]. Any number can still be used
+/// with [`CameraGroup::new`], and group `0` keeps the current group.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::{BroadcastMessage, CameraGroup};
+///
+/// assert_eq!(CameraGroup::from_name("tv1"), Some(CameraGroup::tv1()));
+/// assert_eq!(
+///     BroadcastMessage::camera_switch_number("12", CameraGroup::tv1(), 0),
+///     BroadcastMessage::camera_switch_number("12", 11, 0)
+/// );
+///
+/// let session_groups = [(1, "Nose"), (2, "TV1")];
+/// assert_eq!(
+///     CameraGroup::from_name_in("TV1", session_groups),
+///     Some(CameraGroup::new(2))
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct CameraGroup(u8);
+
+impl CameraGroup {
+    /// The group names and numbers of a typical road-course session, used
+    /// by [`CameraGroup::from_name`].
+    pub const WELL_KNOWN: &'static [(u8, &'static str)] = &[
+        (1, "Nose"),
+        (2, "Gearbox"),
+        (3, "Roll Bar"),
+        (4, "LF Susp"),
+        (5, "LR Susp"),
+        (6, "Gyro"),
+        (7, "RF Susp"),
+        (8, "RR Susp"),
+        (9, "Cockpit"),
+        (10, "Scenic"),
+        (11, "TV1"),
+        (12, "TV2"),
+        (13, "TV3"),
+        (14, "Pit Lane"),
+        (15, "Pit Lane 2"),
+        (16, "Blimp"),
+        (17, "Chopper"),
+        (18, "Chase"),
+        (19, "Far Chase"),
+        (20, "Rear Chase"),
+    ];
+
+    /// The group with number `group`.
+    pub const fn new(group: u8) -> Self {
+        CameraGroup(group)
+    }
+
+    /// The group number sent to the sim.
+    pub const fn get(self) -> u8 {
+        self.0
+    }
+
+    /// Look `name` up in [`CameraGroup::WELL_KNOWN`], ignoring ASCII case
+    /// and surrounding whitespace.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::from_name_in(name, Self::WELL_KNOWN.iter().copied())
+    }
+
+    /// Look `name` up in a session's own `(GroupNum, GroupName)` pairs,
+    /// ignoring ASCII case and surrounding whitespace.
+    pub fn from_name_in<'a, I>(name: &str, groups: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = (u8, &'a str)>,
+    {
+        let name = name.trim();
+        groups
+            .into_iter()
+            .find(|(_, group_name)| group_name.trim().eq_ignore_ascii_case(name))
+            .map(|(group, _)| CameraGroup(group))
+    }
+
+    /// The nose camera group.
+    pub const fn nose() -> Self {
+        CameraGroup(1)
+    }
+
+    /// The gearbox camera group.
+    pub const fn gearbox() -> Self {
+        CameraGroup(2)
+    }
+
+    /// The roll bar camera group.
+    pub const fn roll_bar() -> Self {
+        CameraGroup(3)
+    }
+
+    /// The gyro camera group.
+    pub const fn gyro() -> Self {
+        CameraGroup(6)
+    }
+
+    /// The cockpit camera group.
+    pub const fn cockpit() -> Self {
+        CameraGroup(9)
+    }
+
+    /// The scenic camera group.
+    pub const fn scenic() -> Self {
+        CameraGroup(10)
+    }
+
+    /// The first TV camera group.
+    pub const fn tv1() -> Self {
+        CameraGroup(11)
+    }
+
+    /// The second TV camera group.
+    pub const fn tv2() -> Self {
+        CameraGroup(12)
+    }
+
+    /// The third TV camera group.
+    pub const fn tv3() -> Self {
+        CameraGroup(13)
+    }
+
+    /// The pit lane camera group.
+    pub const fn pit_lane() -> Self {
+        CameraGroup(14)
+    }
+
+    /// The blimp camera group.
+    pub const fn blimp() -> Self {
+        CameraGroup(16)
+    }
+
+    /// The chopper camera group.
+    pub const fn chopper() -> Self {
+        CameraGroup(17)
+    }
+
+    /// The chase camera group.
+    pub const fn chase() -> Self {
+        CameraGroup(18)
+    }
+
+    /// The far chase camera group.
+    pub const fn far_chase() -> Self {
+        CameraGroup(19)
+    }
+
+    /// The rear chase camera group.
+    pub const fn rear_chase() -> Self {
+        CameraGroup(20)
+    }
+}
+
+impl From<u8> for CameraGroup {
+    fn from(group: u8) -> Self {
+        CameraGroup(group)
+    }
+}
+
+impl From<CameraGroup> for u8 {
+    fn from(group: CameraGroup) -> Self {
+        group.0
+    }
+}
+
+impl fmt::Display for CameraGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "group {}", self.0)
+    }
+}
+
+/// Replay playback speed, including rewind and slow motion.
+///
+/// The SDK packs the speed as a signed word in `var1` (negative values
+/// rewind) and a slow-motion flag in `var2`. With slow motion enabled the
+/// speed is a divisor instead of a multiplier, so `2` means half speed.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::ReplaySpeed;
+///
+/// let fast_forward = ReplaySpeed::forward(4)?;
+/// let rewind = ReplaySpeed::rewind(2)?;
+/// assert_eq!(rewind.speed(), -2);
+/// assert!(ReplaySpeed::forward(32).is_err());
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "ReplaySpeedFields")
+)]
+pub struct ReplaySpeed {
+    speed: i8,
+    slow_motion: bool,
+}
+
+impl ReplaySpeed {
+    /// Fastest replay speed (or slowest slow-motion divisor) the sim offers.
+    pub const MAX: u8 = 16;
+
+    /// Build a speed from a signed value and slow-motion flag.
+    ///
+    /// Returns [`BroadcastError::InvalidParameter`] if the magnitude of
+    /// `speed` exceeds [`ReplaySpeed::MAX`].
+    pub fn new(speed: i8, slow_motion: bool) -> Result<Self> {
+        if speed.unsigned_abs() > Self::MAX {
+            return Err(BroadcastError::invalid_parameter(
+                "replay speed",
+                format!("{speed} is outside -{max}..={max}", max = Self::MAX),
+            ));
+        }
+
+        Ok(ReplaySpeed { speed, slow_motion })
+    }
+
+    /// Play forward at `speed` times normal speed.
+    pub fn forward(speed: u8) -> Result<Self> {
+        Self::new(Self::checked_magnitude(speed)?, false)
+    }
+
+    /// Play backward at `speed` times normal speed.
+    pub fn rewind(speed: u8) -> Result<Self> {
+        Self::new(-Self::checked_magnitude(speed)?, false)
+    }
+
+    /// Pause playback.
+    pub fn paused() -> Self {
+        ReplaySpeed {
+            speed: 0,
+            slow_motion: false,
+        }
+    }
+
+    /// Play forward at normal speed.
+    pub fn normal() -> Self {
+        ReplaySpeed {
+            speed: 1,
+            slow_motion: false,
+        }
+    }
+
+    /// Signed speed value; negative values rewind and `0` is paused.
+    pub fn speed(self) -> i8 {
+        self.speed
+    }
+
+    /// Whether [`ReplaySpeed::speed`] is a slow-motion divisor.
+    pub fn is_slow_motion(self) -> bool {
+        self.slow_motion
+    }
+
+    /// Encode into (var1, var2) words as expected by the broadcast API.
+    pub fn encode(self) -> (u16, u16) {
+        (self.speed as i16 as u16, self.slow_motion.into())
+    }
+
+    /// Decode (var1, var2) words, the inverse of [`ReplaySpeed::encode`].
+    ///
+    /// Fails like [`ReplaySpeed::new`] if the speed is out of range.
+    pub fn decode(var1: u16, var2: u16) -> Result<Self> {
+        let speed = var1 as i16;
+        let speed = i8::try_from(speed).map_err(|_| {
+            BroadcastError::invalid_parameter(
+                "replay speed",
+                format!("{speed} is outside -{max}..={max}", max = Self::MAX),
+            )
+        })?;
+        Self::new(speed, var2 != 0)
+    }
+
+    fn checked_magnitude(speed: u8) -> Result<i8> {
+        if speed > Self::MAX {
+            return Err(BroadcastError::invalid_parameter(
+                "replay speed",
+                format!("{speed} exceeds the maximum of {}", Self::MAX),
+            ));
+        }
+
+        Ok(speed as i8)
+    }
+}
+
+impl fmt::Display for ReplaySpeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.speed == 0 {
+            return f.write_str("paused");
+        }
+        if self.speed < 0 {
+            f.write_str("rewind ")?;
+        }
+        let magnitude = self.speed.unsigned_abs();
+        if self.slow_motion {
+            write!(f, "1/{magnitude}x slow motion")
+        } else {
+            write!(f, "{magnitude}x")
+        }
+    }
+}
+
+/// Unvalidated wire shape of [`ReplaySpeed`], checked on deserialize.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct ReplaySpeedFields {
+    speed: i8,
+    slow_motion: bool,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<ReplaySpeedFields> for ReplaySpeed {
+    type Error = BroadcastError;
+
+    fn try_from(fields: ReplaySpeedFields) -> Result<Self> {
+        ReplaySpeed::new(fields.speed, fields.slow_motion)
+    }
+}
+
+/// The sim's force-feedback "MaxForce" setting for
+/// [`BroadcastMessage::FFBCommand`].
+///
+/// MaxForce is the steering torque, in Newton meters, that maps to full
+/// wheel output. The sim scales its computed torque by it, so a **lower**
+/// value means **stronger** forces and more clipping, and `0.0` is the
+/// strongest setting of all, not "off". Raising it weakens the wheel.
+///
+/// The sim reads the value as a 16.16 fixed-point number of Newton meters
+/// (`value * 65536`). Every constructor clamps into
+/// `0.0..=`[`FfbForce::MAX_NEWTON_METERS`], and a NaN input becomes zero, so
+/// any `FfbForce` encodes to a valid fixed-point value.
+///
+/// [`BroadcastMessage::FFBCommand`]: crate::BroadcastMessage::FFBCommand
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::FfbForce;
+///
+/// assert_eq!(FfbForce::from_newton_meters(2.5).fixed_point(), 0x0002_8000);
+/// // Half of a 20 Nm wheel's torque: twice as strong as MaxForce 20 Nm.
+/// assert_eq!(FfbForce::from_percent(50.0, 20.0), FfbForce::from_newton_meters(10.0));
+/// assert_eq!(FfbForce::from_fraction(0.5, 20.0), FfbForce::from_percent(50.0, 20.0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "f32", into = "f32")
+)]
+pub struct FfbForce {
+    fixed_point: u32,
+}
+
+impl FfbForce {
+    /// Largest MaxForce this crate encodes, in Nm.
+    ///
+    /// The sim defines no upper bound; this only keeps the encoded value
+    /// sane, well above the torque of any wheel.
+    pub const MAX_NEWTON_METERS: f32 = 100.0;
+
+    /// MaxForce in Newton meters.
+    pub fn from_newton_meters(newton_meters: f32) -> Self {
+        let clamped = if newton_meters.is_nan() {
+            0.0
+        } else {
+            newton_meters.clamp(0.0, Self::MAX_NEWTON_METERS)
+        };
+
+        FfbForce {
+            fixed_point: (clamped * 65536.0) as u32,
+        }
+    }
+
+    /// MaxForce as a fraction, from `0.0` to `1.0`, of
+    /// `reference_newton_meters`, typically the wheel's own peak torque.
+    ///
+    /// Fractions outside that range are clamped. Lower fractions mean
+    /// stronger forces; see [`FfbForce`].
+    pub fn from_fraction(fraction: f32, reference_newton_meters: f32) -> Self {
+        Self::from_newton_meters(fraction.clamp(0.0, 1.0) * reference_newton_meters)
+    }
+
+    /// MaxForce as a percentage, from `0.0` to `100.0`, of
+    /// `reference_newton_meters`; see [`FfbForce::from_fraction`].
+    pub fn from_percent(percent: f32, reference_newton_meters: f32) -> Self {
+        Self::from_fraction(percent / 100.0, reference_newton_meters)
+    }
+
+    /// MaxForce in Newton meters.
+    pub fn newton_meters(self) -> f32 {
+        self.fixed_point as f32 / 65536.0
+    }
+
+    /// The 16.16 fixed-point value sent in `LPARAM`.
+    pub fn fixed_point(self) -> u32 {
+        self.fixed_point
+    }
+
+    /// A force from its 16.16 fixed-point value, clamped to
+    /// [`FfbForce::MAX_NEWTON_METERS`].
+    pub fn from_fixed_point(fixed_point: u32) -> Self {
+        FfbForce {
+            fixed_point: fixed_point.min((Self::MAX_NEWTON_METERS * 65536.0) as u32),
+        }
+    }
+}
+
+impl From<f32> for FfbForce {
+    /// Same as [`FfbForce::from_newton_meters`].
+    fn from(newton_meters: f32) -> Self {
+        Self::from_newton_meters(newton_meters)
+    }
+}
+
+impl From<FfbForce> for f32 {
+    fn from(force: FfbForce) -> Self {
+        force.newton_meters()
+    }
+}
+
+impl fmt::Display for FfbForce {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} Nm", self.newton_meters())
+    }
+}
+
 /// Replay positioning behaviors when jumping within a session recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u16)]
 pub enum ReplayPositionMode {
     /// Seek to the start of the session.
@@ -76,13 +785,43 @@ pub enum ReplayPositionMode {
     End,
 }
 
+impl ReplayPositionMode {
+    /// Every variant, in protocol order.
+    pub const ALL: &'static [Self] = &[
+        ReplayPositionMode::Begin,
+        ReplayPositionMode::Current,
+        ReplayPositionMode::End,
+    ];
+}
+
 impl From<ReplayPositionMode> for u16 {
     fn from(mode: ReplayPositionMode) -> Self {
         mode as u16
     }
 }
 
+impl TryFrom<u16> for ReplayPositionMode {
+    type Error = BroadcastError;
+
+    /// Map a raw wire word back to its mode.
+    fn try_from(value: u16) -> Result<Self> {
+        mode_from_word(Self::ALL, "replay position mode", value)
+    }
+}
+
+impl fmt::Display for ReplayPositionMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ReplayPositionMode::Begin => "session start",
+            ReplayPositionMode::Current => "current frame",
+            ReplayPositionMode::End => "session end",
+        })
+    }
+}
+
 /// High-level search controls for walking replay timelines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u16)]
 pub enum ReplaySearchMode {
     /// Jump to the beginning of the session.
@@ -107,13 +846,97 @@ pub enum ReplaySearchMode {
     NextIncident,
 }
 
+impl ReplaySearchMode {
+    /// Every variant, in protocol order.
+    pub const ALL: &'static [Self] = &[
+        ReplaySearchMode::ToStart,
+        ReplaySearchMode::ToEnd,
+        ReplaySearchMode::PreviousSession,
+        ReplaySearchMode::NextSession,
+        ReplaySearchMode::PreviousLap,
+        ReplaySearchMode::NextLap,
+        ReplaySearchMode::PreviousFrame,
+        ReplaySearchMode::NextFrame,
+        ReplaySearchMode::PreviousIncident,
+        ReplaySearchMode::NextIncident,
+    ];
+}
+
 impl From<ReplaySearchMode> for u16 {
     fn from(mode: ReplaySearchMode) -> Self {
         mode as u16
     }
 }
 
+impl TryFrom<u16> for ReplaySearchMode {
+    type Error = BroadcastError;
+
+    /// Map a raw wire word back to its mode.
+    fn try_from(value: u16) -> Result<Self> {
+        mode_from_word(Self::ALL, "replay search mode", value)
+    }
+}
+
+impl fmt::Display for ReplaySearchMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ReplaySearchMode::ToStart => "Replay to start",
+            ReplaySearchMode::ToEnd => "Replay to end",
+            ReplaySearchMode::PreviousSession => "Replay to previous session",
+            ReplaySearchMode::NextSession => "Replay to next session",
+            ReplaySearchMode::PreviousLap => "Replay to previous lap",
+            ReplaySearchMode::NextLap => "Replay to next lap",
+            ReplaySearchMode::PreviousFrame => "Replay back one frame",
+            ReplaySearchMode::NextFrame => "Replay forward one frame",
+            ReplaySearchMode::PreviousIncident => "Replay to previous incident",
+            ReplaySearchMode::NextIncident => "Replay to next incident",
+        })
+    }
+}
+
+/// Texture reload scopes for the reload-textures message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u16)]
+pub enum ReloadTexturesMode {
+    /// Reload textures for every car.
+    All = 0,
+    /// Reload textures for a single car index.
+    CarIndex,
+}
+
+impl ReloadTexturesMode {
+    /// Every variant, in protocol order.
+    pub const ALL: &'static [Self] = &[ReloadTexturesMode::All, ReloadTexturesMode::CarIndex];
+}
+
+impl From<ReloadTexturesMode> for u16 {
+    fn from(mode: ReloadTexturesMode) -> Self {
+        mode as u16
+    }
+}
+
+impl TryFrom<u16> for ReloadTexturesMode {
+    type Error = BroadcastError;
+
+    /// Map a raw wire word back to its mode.
+    fn try_from(value: u16) -> Result<Self> {
+        mode_from_word(Self::ALL, "reload textures mode", value)
+    }
+}
+
+impl fmt::Display for ReloadTexturesMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ReloadTexturesMode::All => "all cars",
+            ReloadTexturesMode::CarIndex => "single car",
+        })
+    }
+}
+
 /// Control commands for telemetry recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u16)]
 pub enum TelemetryCommandMode {
     /// Stop capturing telemetry data.
@@ -124,48 +947,129 @@ pub enum TelemetryCommandMode {
     Restart,
 }
 
+impl TelemetryCommandMode {
+    /// Every variant, in protocol order.
+    pub const ALL: &'static [Self] = &[
+        TelemetryCommandMode::Stop,
+        TelemetryCommandMode::Start,
+        TelemetryCommandMode::Restart,
+    ];
+}
+
 impl From<TelemetryCommandMode> for u16 {
     fn from(mode: TelemetryCommandMode) -> Self {
         mode as u16
     }
 }
 
+impl TryFrom<u16> for TelemetryCommandMode {
+    type Error = BroadcastError;
+
+    /// Map a raw wire word back to its mode.
+    fn try_from(value: u16) -> Result<Self> {
+        mode_from_word(Self::ALL, "telemetry command mode", value)
+    }
+}
+
+impl fmt::Display for TelemetryCommandMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TelemetryCommandMode::Stop => "Stop telemetry recording",
+            TelemetryCommandMode::Start => "Start telemetry recording",
+            TelemetryCommandMode::Restart => "Restart telemetry recording",
+        })
+    }
+}
+
 /// Chat command options exposed by the broadcast protocol.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u16)]
 pub enum ChatCommandMode {
     /// Send a numbered chat macro.
     Macro = 0,
     /// Begin a chat session.
     Begin,
-    /// Reply to an existing message.
+    /// Open the chat box addressed to whoever last sent a private message,
+    /// like typing `/r`.
+    ///
+    /// The reply takes no target: the sim only reads the sub-command word
+    /// for [`ChatCommandMode::Macro`], so a reply cannot be aimed at a
+    /// particular car. To message a given driver, type iRacing's
+    /// `/<car number> text` chat syntax with `Client::send_chat_text`.
     Reply,
     /// Cancel chat entry.
     Cancel,
 }
 
+impl ChatCommandMode {
+    /// Every variant, in protocol order.
+    pub const ALL: &'static [Self] = &[
+        ChatCommandMode::Macro,
+        ChatCommandMode::Begin,
+        ChatCommandMode::Reply,
+        ChatCommandMode::Cancel,
+    ];
+}
+
 impl From<ChatCommandMode> for u16 {
     fn from(mode: ChatCommandMode) -> Self {
         mode as u16
     }
 }
 
+impl TryFrom<u16> for ChatCommandMode {
+    type Error = BroadcastError;
+
+    /// Map a raw wire word back to its mode.
+    fn try_from(value: u16) -> Result<Self> {
+        mode_from_word(Self::ALL, "chat command mode", value)
+    }
+}
+
+impl fmt::Display for ChatCommandMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ChatCommandMode::Macro => "Chat macro",
+            ChatCommandMode::Begin => "Open chat",
+            ChatCommandMode::Reply => "Reply to last private chat",
+            ChatCommandMode::Cancel => "Close chat",
+        })
+    }
+}
+
 /// Commands that adjust pit service behavior for the player's car.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PitCommandMode {
     /// Clear all pending pit service requests.
     Clear,
     /// Request a tearoff.
     Tearoff,
-    /// Set fuel amount in gallons.
+    /// Add fuel, in whole liters. `0` keeps the amount already selected.
+    ///
+    /// The sim always interprets this value as liters; use
+    /// [`PitCommandMode::fuel_liters`] or [`PitCommandMode::fuel_gallons`] to
+    /// convert and range-check fractional amounts.
     Fuel(u8),
-    /// Set left-front tire pressure in PSI.
-    LF(u8),
-    /// Set right-front tire pressure in PSI.
-    RF(u8),
-    /// Set left-rear tire pressure in PSI.
-    LR(u8),
-    /// Set right-rear tire pressure in PSI.
-    RR(u8),
+    /// Change the left-front tire at the given pressure in kPa. `0` keeps
+    /// the current pressure.
+    ///
+    /// The sim expects kPa regardless of the display units; see
+    /// [`PitCommandMode::lf_psi`] for a range-checked PSI constructor.
+    LF(u16),
+    /// Change the right-front tire at the given pressure in kPa.
+    RF(u16),
+    /// Change the left-rear tire at the given pressure in kPa.
+    LR(u16),
+    /// Change the right-rear tire at the given pressure in kPa.
+    RR(u16),
     /// Clear all tire change requests.
+    ///
+    /// The SDK has no command to clear a single corner; its pit commands end
+    /// at [`PitCommandMode::ClearFuel`]. To drop one corner, clear all tires
+    /// and re-request the others, or send a
+    /// [`PitServicePlan`](crate::PitServicePlan) without that corner.
     ClearTires,
     /// Request a fast repair.
     FastRepair,
@@ -177,17 +1081,259 @@ pub enum PitCommandMode {
     ClearFuel,
 }
 
+/// Look up the mode whose discriminant is `value` in a protocol-ordered
+/// `ALL` table.
+fn mode_from_word<T: Copy>(modes: &[T], parameter: &str, value: u16) -> Result<T> {
+    modes.get(usize::from(value)).copied().ok_or_else(|| {
+        BroadcastError::invalid_parameter(
+            parameter,
+            format!("{value} is outside 0..{}", modes.len()),
+        )
+    })
+}
+
+/// Lowest tire pressure accepted by the checked PSI constructors.
+pub const MIN_TIRE_PRESSURE_PSI: u8 = 1;
+
+/// Highest tire pressure accepted by the checked PSI constructors.
+pub const MAX_TIRE_PRESSURE_PSI: u8 = 99;
+
+/// Validate a PSI pressure and convert it to the whole kPa the sim expects.
+pub(crate) fn tire_pressure_kpa(corner: &str, psi: u8) -> Result<u16> {
+    if !(MIN_TIRE_PRESSURE_PSI..=MAX_TIRE_PRESSURE_PSI).contains(&psi) {
+        return Err(BroadcastError::invalid_parameter(
+            format!("{corner} tire pressure"),
+            format!("{psi} PSI is outside {MIN_TIRE_PRESSURE_PSI}..={MAX_TIRE_PRESSURE_PSI} PSI"),
+        ));
+    }
+
+    Ok(round(psi_to_kpa(psi as f32)) as u16)
+}
+
+/// Validate a kPa pressure against the PSI limits and round it to the whole
+/// kPa the sim expects.
+fn checked_tire_kpa(corner: &str, kpa: f32) -> Result<u16> {
+    let min = round(psi_to_kpa(MIN_TIRE_PRESSURE_PSI as f32));
+    let max = round(psi_to_kpa(MAX_TIRE_PRESSURE_PSI as f32));
+    let rounded = round(kpa);
+    if !(min..=max).contains(&rounded) {
+        return Err(BroadcastError::invalid_parameter(
+            format!("{corner} tire pressure"),
+            format!("{kpa} kPa is outside {min}..={max} kPa"),
+        ));
+    }
+
+    Ok(rounded as u16)
+}
+
 impl PitCommandMode {
+    /// Request fuel in liters, rounded to the nearest whole liter.
+    ///
+    /// Returns [`BroadcastError::InvalidParameter`] for negative or non-finite
+    /// amounts and for amounts that do not fit the [`PitCommandMode::Fuel`]
+    /// field (more than 255 liters).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::PitCommandMode;
+    ///
+    /// assert_eq!(PitCommandMode::fuel_liters(42.6)?, PitCommandMode::Fuel(43));
+    /// assert!(PitCommandMode::fuel_liters(9000.0).is_err());
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn fuel_liters(liters: f32) -> Result<Self> {
+        if !liters.is_finite() || liters < 0.0 {
+            return Err(BroadcastError::invalid_parameter(
+                "fuel amount",
+                format!("{liters} L is not a positive amount"),
+            ));
+        }
+
+        let rounded = round(liters);
+        if rounded > u8::MAX as f32 {
+            return Err(BroadcastError::invalid_parameter(
+                "fuel amount",
+                format!("{liters} L exceeds the maximum of {} L", u8::MAX),
+            ));
+        }
+
+        Ok(PitCommandMode::Fuel(rounded as u8))
+    }
+
+    /// Request fuel in US gallons, converted to liters and rounded to the
+    /// nearest whole liter.
+    ///
+    /// The same range rules as [`PitCommandMode::fuel_liters`] apply to the
+    /// converted amount.
+    pub fn fuel_gallons(gallons: f32) -> Result<Self> {
+        Self::fuel_liters(gallons_to_liters(gallons))
+    }
+
+    /// Change the left-front tire at a pressure given in PSI.
+    ///
+    /// The pressure must be within
+    /// [`MIN_TIRE_PRESSURE_PSI`]`..=`[`MAX_TIRE_PRESSURE_PSI`]; it is
+    /// converted to the nearest whole kPa before sending.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::PitCommandMode;
+    ///
+    /// assert_eq!(PitCommandMode::lf_psi(26)?, PitCommandMode::LF(179));
+    /// assert!(PitCommandMode::lf_psi(0).is_err());
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn lf_psi(psi: u8) -> Result<Self> {
+        tire_pressure_kpa("left-front", psi).map(PitCommandMode::LF)
+    }
+
+    /// Change the right-front tire at a pressure given in PSI.
+    ///
+    /// See [`PitCommandMode::lf_psi`] for the accepted range.
+    pub fn rf_psi(psi: u8) -> Result<Self> {
+        tire_pressure_kpa("right-front", psi).map(PitCommandMode::RF)
+    }
+
+    /// Change the left-rear tire at a pressure given in PSI.
+    ///
+    /// See [`PitCommandMode::lf_psi`] for the accepted range.
+    pub fn lr_psi(psi: u8) -> Result<Self> {
+        tire_pressure_kpa("left-rear", psi).map(PitCommandMode::LR)
+    }
+
+    /// Change the right-rear tire at a pressure given in PSI.
+    ///
+    /// See [`PitCommandMode::lf_psi`] for the accepted range.
+    pub fn rr_psi(psi: u8) -> Result<Self> {
+        tire_pressure_kpa("right-rear", psi).map(PitCommandMode::RR)
+    }
+
+    /// Change the left-front tire at a pressure given in kPa, rounded to the
+    /// nearest whole kPa (the unit [`PitCommandMode::LF`] carries).
+    ///
+    /// The pressure must round to within the kPa equivalent of
+    /// [`MIN_TIRE_PRESSURE_PSI`]`..=`[`MAX_TIRE_PRESSURE_PSI`] (7 to 683 kPa);
+    /// NaN is rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::PitCommandMode;
+    ///
+    /// assert_eq!(PitCommandMode::lf_kpa(179.3)?, PitCommandMode::LF(179));
+    /// assert!(PitCommandMode::lf_kpa(1000.0).is_err());
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn lf_kpa(kpa: f32) -> Result<Self> {
+        checked_tire_kpa("left-front", kpa).map(PitCommandMode::LF)
+    }
+
+    /// Change the right-front tire at a pressure given in kPa.
+    ///
+    /// See [`PitCommandMode::lf_kpa`] for the accepted range.
+    pub fn rf_kpa(kpa: f32) -> Result<Self> {
+        checked_tire_kpa("right-front", kpa).map(PitCommandMode::RF)
+    }
+
+    /// Change the left-rear tire at a pressure given in kPa.
+    ///
+    /// See [`PitCommandMode::lf_kpa`] for the accepted range.
+    pub fn lr_kpa(kpa: f32) -> Result<Self> {
+        checked_tire_kpa("left-rear", kpa).map(PitCommandMode::LR)
+    }
+
+    /// Change the right-rear tire at a pressure given in kPa.
+    ///
+    /// See [`PitCommandMode::lf_kpa`] for the accepted range.
+    pub fn rr_kpa(kpa: f32) -> Result<Self> {
+        checked_tire_kpa("right-rear", kpa).map(PitCommandMode::RR)
+    }
+
+    /// Request `liters` of fuel, rejecting amounts above the field's
+    /// maximum of 255 L.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::PitCommandMode;
+    ///
+    /// assert_eq!(PitCommandMode::checked_fuel(40)?, PitCommandMode::Fuel(40));
+    /// assert!(PitCommandMode::checked_fuel(256).is_err());
+    /// assert_eq!(PitCommandMode::saturating_fuel(256), PitCommandMode::Fuel(255));
+    /// # Ok::<(), iracing_broadcast::BroadcastError>(())
+    /// ```
+    pub fn checked_fuel(liters: u32) -> Result<Self> {
+        checked_param("fuel amount", liters, 0..=u8::MAX).map(PitCommandMode::Fuel)
+    }
+
+    /// Request `liters` of fuel, clamped to the field's maximum of 255 L.
+    pub fn saturating_fuel(liters: u32) -> Self {
+        PitCommandMode::Fuel(saturating_param(liters, 0..=u8::MAX))
+    }
+
+    /// Change the left-front tire at `kpa`, rejecting pressures above the
+    /// field's maximum of 65535 kPa.
+    ///
+    /// Unlike [`PitCommandMode::lf_kpa`], this only checks the field width:
+    /// `0` keeps the current pressure and the sim's own limits still apply.
+    pub fn checked_lf(kpa: u32) -> Result<Self> {
+        checked_param("left-front tire pressure", kpa, 0..=u16::MAX).map(PitCommandMode::LF)
+    }
+
+    /// Change the right-front tire at `kpa`; see
+    /// [`PitCommandMode::checked_lf`].
+    pub fn checked_rf(kpa: u32) -> Result<Self> {
+        checked_param("right-front tire pressure", kpa, 0..=u16::MAX).map(PitCommandMode::RF)
+    }
+
+    /// Change the left-rear tire at `kpa`; see
+    /// [`PitCommandMode::checked_lf`].
+    pub fn checked_lr(kpa: u32) -> Result<Self> {
+        checked_param("left-rear tire pressure", kpa, 0..=u16::MAX).map(PitCommandMode::LR)
+    }
+
+    /// Change the right-rear tire at `kpa`; see
+    /// [`PitCommandMode::checked_lf`].
+    pub fn checked_rr(kpa: u32) -> Result<Self> {
+        checked_param("right-rear tire pressure", kpa, 0..=u16::MAX).map(PitCommandMode::RR)
+    }
+
+    /// Change the left-front tire at `kpa`, clamped to the field's maximum
+    /// of 65535 kPa.
+    pub fn saturating_lf(kpa: u32) -> Self {
+        PitCommandMode::LF(saturating_param(kpa, 0..=u16::MAX))
+    }
+
+    /// Change the right-front tire at `kpa`; see
+    /// [`PitCommandMode::saturating_lf`].
+    pub fn saturating_rf(kpa: u32) -> Self {
+        PitCommandMode::RF(saturating_param(kpa, 0..=u16::MAX))
+    }
+
+    /// Change the left-rear tire at `kpa`; see
+    /// [`PitCommandMode::saturating_lf`].
+    pub fn saturating_lr(kpa: u32) -> Self {
+        PitCommandMode::LR(saturating_param(kpa, 0..=u16::MAX))
+    }
+
+    /// Change the right-rear tire at `kpa`; see
+    /// [`PitCommandMode::saturating_lf`].
+    pub fn saturating_rr(kpa: u32) -> Self {
+        PitCommandMode::RR(saturating_param(kpa, 0..=u16::MAX))
+    }
+
     /// Encode into (var1, var2) words as expected by the broadcast API.
     pub fn encode(self) -> (u16, u16) {
         match self {
             PitCommandMode::Clear => (0, 0),
             PitCommandMode::Tearoff => (1, 0),
             PitCommandMode::Fuel(level) => (2, level as u16),
-            PitCommandMode::LF(pressure) => (3, pressure as u16),
-            PitCommandMode::RF(pressure) => (4, pressure as u16),
-            PitCommandMode::LR(pressure) => (5, pressure as u16),
-            PitCommandMode::RR(pressure) => (6, pressure as u16),
+            PitCommandMode::LF(pressure) => (3, pressure),
+            PitCommandMode::RF(pressure) => (4, pressure),
+            PitCommandMode::LR(pressure) => (5, pressure),
+            PitCommandMode::RR(pressure) => (6, pressure),
             PitCommandMode::ClearTires => (7, 0),
             PitCommandMode::FastRepair => (8, 0),
             PitCommandMode::ClearTearoff => (9, 0),
@@ -195,9 +1341,65 @@ impl PitCommandMode {
             PitCommandMode::ClearFuel => (11, 0),
         }
     }
+
+    /// Decode (var1, var2) words, the inverse of [`PitCommandMode::encode`].
+    ///
+    /// Returns [`BroadcastError::InvalidParameter`] for an unknown command or
+    /// a fuel amount above `255` liters.
+    pub fn decode(var1: u16, var2: u16) -> Result<Self> {
+        Ok(match var1 {
+            0 => PitCommandMode::Clear,
+            1 => PitCommandMode::Tearoff,
+            2 => PitCommandMode::Fuel(checked_param("fuel amount", var2.into(), 0..=u8::MAX)?),
+            3 => PitCommandMode::LF(var2),
+            4 => PitCommandMode::RF(var2),
+            5 => PitCommandMode::LR(var2),
+            6 => PitCommandMode::RR(var2),
+            7 => PitCommandMode::ClearTires,
+            8 => PitCommandMode::FastRepair,
+            9 => PitCommandMode::ClearTearoff,
+            10 => PitCommandMode::ClearFastRepair,
+            11 => PitCommandMode::ClearFuel,
+            _ => {
+                return Err(BroadcastError::invalid_parameter(
+                    "pit command mode",
+                    format!("{var1} is outside 0..12"),
+                ));
+            }
+        })
+    }
+}
+
+impl fmt::Display for PitCommandMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PitCommandMode::Clear => f.write_str("Clear all pit service"),
+            PitCommandMode::Tearoff => f.write_str("Tearoff"),
+            PitCommandMode::Fuel(0) => f.write_str("Fuel: current amount"),
+            PitCommandMode::Fuel(liters) => write!(f, "Fuel: {liters}L"),
+            PitCommandMode::LF(kpa) => write_tire(f, "LF", *kpa),
+            PitCommandMode::RF(kpa) => write_tire(f, "RF", *kpa),
+            PitCommandMode::LR(kpa) => write_tire(f, "LR", *kpa),
+            PitCommandMode::RR(kpa) => write_tire(f, "RR", *kpa),
+            PitCommandMode::ClearTires => f.write_str("Clear tire changes"),
+            PitCommandMode::FastRepair => f.write_str("Fast repair"),
+            PitCommandMode::ClearTearoff => f.write_str("Clear tearoff"),
+            PitCommandMode::ClearFastRepair => f.write_str("Clear fast repair"),
+            PitCommandMode::ClearFuel => f.write_str("Clear fuel"),
+        }
+    }
+}
+
+fn write_tire(f: &mut fmt::Formatter<'_>, corner: &str, kpa: u16) -> fmt::Result {
+    match kpa {
+        0 => write!(f, "{corner} tire: current pressure"),
+        kpa => write!(f, "{corner} tire: {kpa} kPa"),
+    }
 }
 
 /// Control video capture and screenshot functionality.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(u16)]
 pub enum VideoCaptureMode {
     /// Trigger a single screenshot.
@@ -214,8 +1416,514 @@ pub enum VideoCaptureMode {
     HideTimer,
 }
 
+impl VideoCaptureMode {
+    /// Every variant, in protocol order.
+    pub const ALL: &'static [Self] = &[
+        VideoCaptureMode::ScreenShot,
+        VideoCaptureMode::StartCapture,
+        VideoCaptureMode::EndCapture,
+        VideoCaptureMode::ToggleCapture,
+        VideoCaptureMode::ShowTimer,
+        VideoCaptureMode::HideTimer,
+    ];
+}
+
 impl From<VideoCaptureMode> for u16 {
     fn from(mode: VideoCaptureMode) -> Self {
         mode as u16
     }
 }
+
+impl TryFrom<u16> for VideoCaptureMode {
+    type Error = BroadcastError;
+
+    /// Map a raw wire word back to its mode.
+    fn try_from(value: u16) -> Result<Self> {
+        mode_from_word(Self::ALL, "video capture mode", value)
+    }
+}
+
+impl fmt::Display for VideoCaptureMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            VideoCaptureMode::ScreenShot => "Take screenshot",
+            VideoCaptureMode::StartCapture => "Start video capture",
+            VideoCaptureMode::EndCapture => "Stop video capture",
+            VideoCaptureMode::ToggleCapture => "Toggle video capture",
+            VideoCaptureMode::ShowTimer => "Show video timer",
+            VideoCaptureMode::HideTimer => "Hide video timer",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BroadcastMessage;
+
+    #[test]
+    fn test_all_variants_in_protocol_order() {
+        assert_eq!(BroadcastMessageType::ALL.len(), 14);
+        assert_eq!(ReplaySearchMode::ALL.len(), 10);
+        assert_eq!(VideoCaptureMode::ALL.len(), 6);
+
+        for (index, mode) in ReplaySearchMode::ALL.iter().enumerate() {
+            assert_eq!(u16::from(*mode) as usize, index);
+        }
+        for (index, mode) in VideoCaptureMode::ALL.iter().enumerate() {
+            assert_eq!(u16::from(*mode) as usize, index);
+        }
+        for (index, mode) in ChatCommandMode::ALL.iter().enumerate() {
+            assert_eq!(u16::from(*mode) as usize, index);
+        }
+    }
+
+    #[test]
+    fn test_message_type_matches_sdk_constants() {
+        // irsdk_BroadcastMsg from the iRacing SDK's irsdk_defines.h.
+        let sdk = [
+            (BroadcastMessageType::CameraSwitchPosition, 0),
+            (BroadcastMessageType::CameraSwitchNumber, 1),
+            (BroadcastMessageType::CameraSetState, 2),
+            (BroadcastMessageType::ReplaySetPlaySpeed, 3),
+            (BroadcastMessageType::ReplaySetPlayPosition, 4),
+            (BroadcastMessageType::ReplaySearch, 5),
+            (BroadcastMessageType::ReplaySetState, 6),
+            (BroadcastMessageType::ReloadTextures, 7),
+            (BroadcastMessageType::ChatCommand, 8),
+            (BroadcastMessageType::PitCommand, 9),
+            (BroadcastMessageType::TelemetryCommand, 10),
+            (BroadcastMessageType::FFBCommand, 11),
+            (BroadcastMessageType::ReplaySearchSessionTime, 12),
+            (BroadcastMessageType::VideoCapture, 13),
+        ];
+
+        assert_eq!(sdk.len(), BroadcastMessageType::ALL.len());
+        for (message_type, value) in sdk {
+            assert_eq!(message_type as u32, value, "{message_type:?}");
+            assert_eq!(BroadcastMessageType::try_from(value).unwrap(), message_type);
+        }
+    }
+
+    #[test]
+    fn test_pit_command_matches_sdk_constants() {
+        // irsdk_PitCommandMode from the iRacing SDK's irsdk_defines.h, which
+        // has no per-corner tire clears.
+        let sdk = [
+            (PitCommandMode::Clear, 0),
+            (PitCommandMode::Tearoff, 1),
+            (PitCommandMode::Fuel(0), 2),
+            (PitCommandMode::LF(0), 3),
+            (PitCommandMode::RF(0), 4),
+            (PitCommandMode::LR(0), 5),
+            (PitCommandMode::RR(0), 6),
+            (PitCommandMode::ClearTires, 7),
+            (PitCommandMode::FastRepair, 8),
+            (PitCommandMode::ClearTearoff, 9),
+            (PitCommandMode::ClearFastRepair, 10),
+            (PitCommandMode::ClearFuel, 11),
+        ];
+
+        for (mode, value) in sdk {
+            assert_eq!(mode.encode(), (value, 0), "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn test_mode_word_round_trip() {
+        fn round_trip<T>(modes: &[T])
+        where
+            T: Copy + PartialEq + fmt::Debug + TryFrom<u16, Error = BroadcastError>,
+            u16: From<T>,
+        {
+            for &mode in modes {
+                assert_eq!(T::try_from(u16::from(mode)).unwrap(), mode);
+            }
+            let past_end = modes.len() as u16;
+            assert!(matches!(
+                T::try_from(past_end),
+                Err(BroadcastError::InvalidParameter { .. })
+            ));
+            assert!(T::try_from(u16::MAX).is_err());
+        }
+
+        round_trip(ReplayPositionMode::ALL);
+        round_trip(ReplaySearchMode::ALL);
+        round_trip(ReloadTexturesMode::ALL);
+        round_trip(TelemetryCommandMode::ALL);
+        round_trip(ChatCommandMode::ALL);
+        round_trip(VideoCaptureMode::ALL);
+    }
+
+    #[test]
+    fn test_as_u32_in_const_context() {
+        const IDS: [u32; 14] = [
+            BroadcastMessageType::CameraSwitchPosition.as_u32(),
+            BroadcastMessageType::CameraSwitchNumber.as_u32(),
+            BroadcastMessageType::CameraSetState.as_u32(),
+            BroadcastMessageType::ReplaySetPlaySpeed.as_u32(),
+            BroadcastMessageType::ReplaySetPlayPosition.as_u32(),
+            BroadcastMessageType::ReplaySearch.as_u32(),
+            BroadcastMessageType::ReplaySetState.as_u32(),
+            BroadcastMessageType::ReloadTextures.as_u32(),
+            BroadcastMessageType::ChatCommand.as_u32(),
+            BroadcastMessageType::PitCommand.as_u32(),
+            BroadcastMessageType::TelemetryCommand.as_u32(),
+            BroadcastMessageType::FFBCommand.as_u32(),
+            BroadcastMessageType::ReplaySearchSessionTime.as_u32(),
+            BroadcastMessageType::VideoCapture.as_u32(),
+        ];
+        const _: () = assert!(BroadcastMessageType::VideoCapture.as_u32() == 13);
+
+        assert_eq!(IDS, std::array::from_fn(|index| index as u32));
+    }
+
+    #[test]
+    fn test_message_type_ordering() {
+        use std::collections::BTreeSet;
+
+        let set: BTreeSet<_> = BroadcastMessageType::ALL.iter().rev().copied().collect();
+        assert!(set.iter().eq(BroadcastMessageType::ALL));
+        assert!(BroadcastMessageType::CameraSwitchPosition < BroadcastMessageType::VideoCapture);
+    }
+
+    #[test]
+    fn test_camera_state_builder_matches_manual_flags() {
+        let built = CameraState::builder()
+            .scenic(true)
+            .ui_hidden(true)
+            .mouse_aim(true)
+            .build();
+        assert_eq!(
+            built,
+            CameraState::IS_SCENIC_ACTIVE
+                | CameraState::UI_HIDDEN
+                | CameraState::USE_MOUSE_AIM_MODE
+        );
+        assert_eq!(CameraState::builder().build(), CameraState::empty());
+    }
+
+    #[test]
+    fn test_camera_state_diff() {
+        let scenic_tool = CameraState::IS_SCENIC_ACTIVE | CameraState::CAM_TOOL_ACTIVE;
+        let hidden_tool = CameraState::UI_HIDDEN | CameraState::CAM_TOOL_ACTIVE;
+        assert_eq!(
+            scenic_tool.diff(hidden_tool),
+            (CameraState::UI_HIDDEN, CameraState::IS_SCENIC_ACTIVE)
+        );
+
+        let session = CameraState::IS_SESSION_SCREEN;
+        let hidden = CameraState::UI_HIDDEN | CameraState::USE_MOUSE_AIM_MODE;
+        assert_eq!(session.diff(hidden), (hidden, session));
+        assert_eq!(hidden.diff(session), (session, hidden));
+
+        let empty = CameraState::empty();
+        assert_eq!(hidden_tool.diff(hidden_tool), (empty, empty));
+        assert_eq!(empty.diff(empty), (empty, empty));
+    }
+
+    #[test]
+    fn test_camera_state_builder_from_existing() {
+        let current = CameraState::CAM_TOOL_ACTIVE | CameraState::UI_HIDDEN;
+        let updated = current
+            .to_builder()
+            .ui_hidden(false)
+            .auto_shot_selection(true)
+            .build();
+        assert_eq!(
+            updated,
+            CameraState::CAM_TOOL_ACTIVE | CameraState::USE_AUTO_SHOT_SELECTION
+        );
+        assert_eq!(CameraStateBuilder::from(current).build(), current);
+    }
+
+    #[test]
+    fn test_mode_display() {
+        assert_eq!(PitCommandMode::Fuel(65).to_string(), "Fuel: 65L");
+        assert_eq!(PitCommandMode::LF(179).to_string(), "LF tire: 179 kPa");
+        assert_eq!(
+            PitCommandMode::RR(0).to_string(),
+            "RR tire: current pressure"
+        );
+        assert_eq!(
+            ReplaySearchMode::NextIncident.to_string(),
+            "Replay to next incident"
+        );
+        assert_eq!(VideoCaptureMode::ScreenShot.to_string(), "Take screenshot");
+        assert_eq!(ReplaySpeed::rewind(2).unwrap().to_string(), "rewind 2x");
+        assert_eq!(
+            ReplaySpeed::new(4, true).unwrap().to_string(),
+            "1/4x slow motion"
+        );
+        assert_eq!(ReplaySpeed::paused().to_string(), "paused");
+        assert_eq!(
+            (CameraState::IS_SCENIC_ACTIVE | CameraState::UI_HIDDEN).to_string(),
+            "IS_SCENIC_ACTIVE | UI_HIDDEN"
+        );
+        assert_eq!(CameraState::empty().to_string(), "none");
+    }
+
+    #[test]
+    fn test_fuel_liters_rounding() {
+        assert_eq!(
+            PitCommandMode::fuel_liters(0.0).unwrap(),
+            PitCommandMode::Fuel(0)
+        );
+        assert_eq!(
+            PitCommandMode::fuel_liters(42.4).unwrap(),
+            PitCommandMode::Fuel(42)
+        );
+        assert_eq!(
+            PitCommandMode::fuel_liters(42.5).unwrap(),
+            PitCommandMode::Fuel(43)
+        );
+        assert_eq!(
+            PitCommandMode::fuel_liters(255.4).unwrap(),
+            PitCommandMode::Fuel(255)
+        );
+    }
+
+    #[test]
+    fn test_fuel_gallons_conversion() {
+        // 10 gal == 37.85 L
+        assert_eq!(
+            PitCommandMode::fuel_gallons(10.0).unwrap(),
+            PitCommandMode::Fuel(38)
+        );
+        assert_eq!(
+            PitCommandMode::fuel_gallons(1.0).unwrap(),
+            PitCommandMode::Fuel(4)
+        );
+    }
+
+    #[test]
+    fn test_fuel_out_of_range() {
+        for liters in [-1.0, 255.5, 9000.0, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                PitCommandMode::fuel_liters(liters),
+                Err(BroadcastError::InvalidParameter { .. })
+            ));
+        }
+        assert!(PitCommandMode::fuel_gallons(100.0).is_err());
+    }
+
+    #[test]
+    fn test_replay_speed_encoding() {
+        assert_eq!(ReplaySpeed::forward(1).unwrap().encode(), (1, 0));
+        assert_eq!(ReplaySpeed::forward(16).unwrap().encode(), (16, 0));
+        assert_eq!(ReplaySpeed::rewind(1).unwrap().encode(), (0xFFFF, 0));
+        assert_eq!(ReplaySpeed::rewind(16).unwrap().encode(), (0xFFF0, 0));
+        assert_eq!(ReplaySpeed::paused().encode(), (0, 0));
+        assert_eq!(ReplaySpeed::new(4, true).unwrap().encode(), (4, 1));
+        assert_eq!(ReplaySpeed::new(-2, true).unwrap().encode(), (0xFFFE, 1));
+    }
+
+    #[test]
+    fn test_replay_speed_bounds() {
+        assert!(ReplaySpeed::forward(17).is_err());
+        assert!(ReplaySpeed::rewind(17).is_err());
+        assert!(ReplaySpeed::new(-17, false).is_err());
+        assert!(ReplaySpeed::new(i8::MIN, false).is_err());
+    }
+
+    #[test]
+    fn test_ffb_force_percentages() {
+        // Of a 20 Nm wheel.
+        assert_eq!(FfbForce::from_percent(0.0, 20.0).fixed_point(), 0);
+        assert_eq!(FfbForce::from_percent(50.0, 20.0).fixed_point(), 655_360);
+        assert_eq!(FfbForce::from_percent(100.0, 20.0).fixed_point(), 1_310_720);
+
+        assert_eq!(
+            FfbForce::from_fraction(0.0, 20.0),
+            FfbForce::from_percent(0.0, 20.0)
+        );
+        assert_eq!(
+            FfbForce::from_fraction(0.5, 20.0),
+            FfbForce::from_percent(50.0, 20.0)
+        );
+        assert_eq!(
+            FfbForce::from_fraction(1.0, 20.0),
+            FfbForce::from_percent(100.0, 20.0)
+        );
+    }
+
+    #[test]
+    fn test_ffb_force_clamps() {
+        assert_eq!(FfbForce::from_newton_meters(-3.0).fixed_point(), 0);
+        assert_eq!(FfbForce::from_newton_meters(f32::NAN).fixed_point(), 0);
+        assert_eq!(
+            FfbForce::from_newton_meters(250.0).newton_meters(),
+            FfbForce::MAX_NEWTON_METERS
+        );
+        // Never beyond the reference torque, nor below zero.
+        assert_eq!(
+            FfbForce::from_fraction(2.0, 20.0),
+            FfbForce::from_newton_meters(20.0)
+        );
+        assert_eq!(FfbForce::from_percent(-10.0, 20.0).fixed_point(), 0);
+    }
+
+    #[test]
+    fn test_tire_pressure_psi_boundaries() {
+        assert_eq!(PitCommandMode::lf_psi(1).unwrap(), PitCommandMode::LF(7));
+        assert_eq!(PitCommandMode::rf_psi(26).unwrap(), PitCommandMode::RF(179));
+        assert_eq!(PitCommandMode::lr_psi(99).unwrap(), PitCommandMode::LR(683));
+        assert_eq!(PitCommandMode::rr_psi(30).unwrap(), PitCommandMode::RR(207));
+
+        for psi in [0, 100, u8::MAX] {
+            assert!(matches!(
+                PitCommandMode::lf_psi(psi),
+                Err(BroadcastError::InvalidParameter { .. })
+            ));
+            assert!(PitCommandMode::rr_psi(psi).is_err());
+        }
+    }
+
+    #[test]
+    fn test_checked_and_saturating_fuel() {
+        for (liters, checked, saturated) in [
+            (0, Some(0), 0),
+            (1, Some(1), 1),
+            (254, Some(254), 254),
+            (255, Some(255), 255),
+            (256, None, 255),
+            (u32::MAX, None, 255),
+        ] {
+            assert_eq!(
+                PitCommandMode::checked_fuel(liters).ok(),
+                checked.map(PitCommandMode::Fuel),
+                "{liters}"
+            );
+            assert_eq!(
+                PitCommandMode::saturating_fuel(liters),
+                PitCommandMode::Fuel(saturated)
+            );
+        }
+    }
+
+    #[test]
+    fn test_checked_and_saturating_tires() {
+        type Corner = (
+            fn(u32) -> Result<PitCommandMode>,
+            fn(u32) -> PitCommandMode,
+            fn(u16) -> PitCommandMode,
+        );
+        let corners: [Corner; 4] = [
+            (
+                PitCommandMode::checked_lf,
+                PitCommandMode::saturating_lf,
+                PitCommandMode::LF,
+            ),
+            (
+                PitCommandMode::checked_rf,
+                PitCommandMode::saturating_rf,
+                PitCommandMode::RF,
+            ),
+            (
+                PitCommandMode::checked_lr,
+                PitCommandMode::saturating_lr,
+                PitCommandMode::LR,
+            ),
+            (
+                PitCommandMode::checked_rr,
+                PitCommandMode::saturating_rr,
+                PitCommandMode::RR,
+            ),
+        ];
+
+        for (checked, saturating, variant) in corners {
+            for (kpa, fits, saturated) in [
+                (0, Some(0), 0),
+                (179, Some(179), 179),
+                (65_535, Some(65_535), 65_535),
+                (65_536, None, 65_535),
+                (u32::MAX, None, 65_535),
+            ] {
+                assert_eq!(checked(kpa).ok(), fits.map(variant), "{kpa}");
+                assert_eq!(saturating(kpa), variant(saturated), "{kpa}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_tire_pressure_kpa() {
+        assert_eq!(
+            PitCommandMode::lf_kpa(179.3).unwrap(),
+            PitCommandMode::LF(179)
+        );
+        assert_eq!(
+            PitCommandMode::rf_kpa(206.5).unwrap(),
+            PitCommandMode::RF(207)
+        );
+        assert_eq!(PitCommandMode::lr_kpa(6.6).unwrap(), PitCommandMode::LR(7));
+        assert_eq!(
+            PitCommandMode::rr_kpa(683.0).unwrap(),
+            PitCommandMode::RR(683)
+        );
+
+        for kpa in [0.0, 6.4, 683.6, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                PitCommandMode::lf_kpa(kpa),
+                Err(BroadcastError::InvalidParameter { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_camera_group_names() {
+        for (name, group) in [
+            ("Nose", CameraGroup::nose()),
+            ("cockpit", CameraGroup::cockpit()),
+            ("Scenic", CameraGroup::scenic()),
+            ("TV1", CameraGroup::tv1()),
+            ("tv2", CameraGroup::tv2()),
+            (" TV3 ", CameraGroup::tv3()),
+            ("Pit Lane", CameraGroup::pit_lane()),
+            ("BLIMP", CameraGroup::blimp()),
+            ("Chopper", CameraGroup::chopper()),
+            ("Far Chase", CameraGroup::far_chase()),
+        ] {
+            assert_eq!(CameraGroup::from_name(name), Some(group), "{name}");
+        }
+        assert_eq!(CameraGroup::from_name("Pit Lane 2").unwrap().get(), 15);
+        assert_eq!(CameraGroup::from_name("TV4"), None);
+        assert_eq!(CameraGroup::from_name(""), None);
+
+        for &(group, name) in CameraGroup::WELL_KNOWN {
+            assert_eq!(CameraGroup::from_name(name), Some(CameraGroup::new(group)));
+        }
+    }
+
+    #[test]
+    fn test_camera_group_session_lookup() {
+        let session = [(1, "Nose"), (5, "TV1"), (9, "TV Static")];
+
+        assert_eq!(
+            CameraGroup::from_name_in("tv1", session),
+            Some(CameraGroup::new(5))
+        );
+        assert_eq!(
+            CameraGroup::from_name_in("TV Static", session),
+            Some(CameraGroup::new(9))
+        );
+        assert_eq!(CameraGroup::from_name_in("TV2", session), None);
+    }
+
+    #[test]
+    fn test_camera_group_raw_passthrough() {
+        for raw in [0, 7, 42, u8::MAX] {
+            let group = CameraGroup::from(raw);
+            assert_eq!(group, CameraGroup::new(raw));
+            assert_eq!(u8::from(group), raw);
+        }
+
+        assert_eq!(
+            BroadcastMessage::camera_switch_number("12", CameraGroup::tv1(), 0),
+            BroadcastMessage::CameraSwitchNumber("12".into(), 11, 0)
+        );
+        assert_eq!(
+            BroadcastMessage::camera_switch_number("12", 42, 3),
+            BroadcastMessage::CameraSwitchNumber("12".into(), 42, 3)
+        );
+    }
+}
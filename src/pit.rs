@@ -0,0 +1,234 @@
+//! Declarative pit service plans.
+
+use alloc::vec::Vec;
+
+use crate::{BroadcastMessage, PitCommandMode, Result, message::tire_pressure_kpa};
+
+/// Everything the crew should do at the next stop, expressed as one value.
+///
+/// A plan replaces whatever service is currently selected: its messages always
+/// start with [`PitCommandMode::Clear`], so fields left unset are deselected
+/// rather than inherited from an earlier request. The default plan therefore
+/// clears all service.
+///
+/// Fuel is in whole liters and tire pressures are in kPa, exactly like the
+/// matching [`PitCommandMode`] variants; a pressure of `0` changes the tire
+/// at its current pressure.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::{BroadcastMessage, PitCommandMode, PitServicePlan};
+///
+/// let plan = PitServicePlan {
+///     fuel: Some(40),
+///     left_front: Some(179),
+///     tearoff: true,
+///     ..PitServicePlan::default()
+/// };
+///
+/// assert_eq!(
+///     plan.into_messages(),
+///     [
+///         PitCommandMode::Clear,
+///         PitCommandMode::Fuel(40),
+///         PitCommandMode::LF(179),
+///         PitCommandMode::Tearoff,
+///     ]
+///     .map(BroadcastMessage::PitCommand)
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PitServicePlan {
+    /// Fuel to add, in liters.
+    pub fuel: Option<u8>,
+    /// Left-front tire pressure, in kPa.
+    pub left_front: Option<u16>,
+    /// Right-front tire pressure, in kPa.
+    pub right_front: Option<u16>,
+    /// Left-rear tire pressure, in kPa.
+    pub left_rear: Option<u16>,
+    /// Right-rear tire pressure, in kPa.
+    pub right_rear: Option<u16>,
+    /// Take a windscreen tearoff.
+    pub tearoff: bool,
+    /// Use a fast repair.
+    pub fast_repair: bool,
+}
+
+impl PitServicePlan {
+    /// The ordered `PitCommand` messages that select exactly this plan.
+    ///
+    /// The sequence is a clear, then fuel, the tires from left-front to
+    /// right-rear, tearoff and fast repair, skipping anything not requested.
+    pub fn into_messages(self) -> Vec<BroadcastMessage> {
+        let tires = [
+            self.left_front.map(PitCommandMode::LF),
+            self.right_front.map(PitCommandMode::RF),
+            self.left_rear.map(PitCommandMode::LR),
+            self.right_rear.map(PitCommandMode::RR),
+        ];
+
+        core::iter::once(Some(PitCommandMode::Clear))
+            .chain([self.fuel.map(PitCommandMode::Fuel)])
+            .chain(tires)
+            .chain([
+                self.tearoff.then_some(PitCommandMode::Tearoff),
+                self.fast_repair.then_some(PitCommandMode::FastRepair),
+            ])
+            .flatten()
+            .map(BroadcastMessage::PitCommand)
+            .collect()
+    }
+}
+
+/// Pressures for all four tires, in kPa like [`PitCommandMode::LF`].
+///
+/// Unlike [`PitServicePlan`], this only selects tire changes and leaves the
+/// rest of the selected service alone. A pressure of 0 changes that tire at
+/// its current pressure, so the default set changes all four without
+/// touching the pressures.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::{BroadcastMessage, PitCommandMode, PitTireSet};
+///
+/// assert_eq!(PitTireSet::all_psi(26)?, PitTireSet::all(179));
+/// assert_eq!(
+///     PitTireSet::all(179).into_messages(),
+///     [
+///         PitCommandMode::LF(179),
+///         PitCommandMode::RF(179),
+///         PitCommandMode::LR(179),
+///         PitCommandMode::RR(179),
+///     ]
+///     .map(BroadcastMessage::PitCommand)
+/// );
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PitTireSet {
+    /// Left-front tire pressure, in kPa.
+    pub lf: u16,
+    /// Right-front tire pressure, in kPa.
+    pub rf: u16,
+    /// Left-rear tire pressure, in kPa.
+    pub lr: u16,
+    /// Right-rear tire pressure, in kPa.
+    pub rr: u16,
+}
+
+impl PitTireSet {
+    /// The same pressure, in kPa, on every tire.
+    pub fn all(kpa: u16) -> Self {
+        PitTireSet {
+            lf: kpa,
+            rf: kpa,
+            lr: kpa,
+            rr: kpa,
+        }
+    }
+
+    /// Pressures given in PSI, each checked and converted like
+    /// [`PitCommandMode::lf_psi`].
+    pub fn from_psi(lf: u8, rf: u8, lr: u8, rr: u8) -> Result<Self> {
+        Ok(PitTireSet {
+            lf: tire_pressure_kpa("left-front", lf)?,
+            rf: tire_pressure_kpa("right-front", rf)?,
+            lr: tire_pressure_kpa("left-rear", lr)?,
+            rr: tire_pressure_kpa("right-rear", rr)?,
+        })
+    }
+
+    /// The same pressure, in PSI, on every tire.
+    pub fn all_psi(psi: u8) -> Result<Self> {
+        Self::from_psi(psi, psi, psi, psi)
+    }
+
+    /// The four `PitCommand` messages, from left-front to right-rear.
+    pub fn into_messages(self) -> Vec<BroadcastMessage> {
+        [
+            PitCommandMode::LF(self.lf),
+            PitCommandMode::RF(self.rf),
+            PitCommandMode::LR(self.lr),
+            PitCommandMode::RR(self.rr),
+        ]
+        .into_iter()
+        .map(BroadcastMessage::PitCommand)
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BroadcastError;
+
+    #[test]
+    fn test_default_plan_only_clears() {
+        assert_eq!(
+            PitServicePlan::default().into_messages(),
+            vec![BroadcastMessage::PitCommand(PitCommandMode::Clear)]
+        );
+    }
+
+    #[test]
+    fn test_fuel_and_two_tires() {
+        let plan = PitServicePlan {
+            fuel: Some(65),
+            left_rear: Some(172),
+            left_front: Some(179),
+            ..PitServicePlan::default()
+        };
+
+        assert_eq!(
+            plan.into_messages(),
+            vec![
+                BroadcastMessage::PitCommand(PitCommandMode::Clear),
+                BroadcastMessage::PitCommand(PitCommandMode::Fuel(65)),
+                BroadcastMessage::PitCommand(PitCommandMode::LF(179)),
+                BroadcastMessage::PitCommand(PitCommandMode::LR(172)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tire_set_order() {
+        let set = PitTireSet::from_psi(26, 27, 24, 25).unwrap();
+
+        assert_eq!(
+            set.into_messages(),
+            vec![
+                BroadcastMessage::PitCommand(PitCommandMode::lf_psi(26).unwrap()),
+                BroadcastMessage::PitCommand(PitCommandMode::rf_psi(27).unwrap()),
+                BroadcastMessage::PitCommand(PitCommandMode::lr_psi(24).unwrap()),
+                BroadcastMessage::PitCommand(PitCommandMode::rr_psi(25).unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_default_tire_set_keeps_pressures() {
+        assert_eq!(
+            PitTireSet::default().into_messages(),
+            [
+                PitCommandMode::LF(0),
+                PitCommandMode::RF(0),
+                PitCommandMode::LR(0),
+                PitCommandMode::RR(0),
+            ]
+            .map(BroadcastMessage::PitCommand)
+        );
+    }
+
+    #[test]
+    fn test_tire_set_rejects_out_of_range_psi() {
+        assert!(matches!(
+            PitTireSet::from_psi(26, 26, 26, 0),
+            Err(BroadcastError::InvalidParameter { .. })
+        ));
+    }
+}
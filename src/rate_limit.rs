@@ -0,0 +1,110 @@
+//! Spacing out sends so automation cannot flood the simulator.
+
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{BroadcastError, Result};
+
+/// What a rate-limited [`Client`](crate::Client) does with a send that comes
+/// too soon after the previous one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitMode {
+    /// Sleep until the minimum interval has passed, then send.
+    #[default]
+    Wait,
+    /// Fail with [`BroadcastError::RateLimited`] without sending.
+    Reject,
+}
+
+/// Minimum gap between sends, shared by a client and its clones.
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimiter {
+    min_interval: Duration,
+    mode: RateLimitMode,
+    last_send: Arc<Mutex<Option<Instant>>>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(min_interval: Duration, mode: RateLimitMode) -> Self {
+        Self {
+            min_interval,
+            mode,
+            last_send: Arc::default(),
+        }
+    }
+
+    pub(crate) fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+
+    pub(crate) fn mode(&self) -> RateLimitMode {
+        self.mode
+    }
+
+    /// Claim the next send slot, waiting for it or rejecting the send
+    /// according to the mode.
+    pub(crate) fn acquire(&self) -> Result<()> {
+        // Holding the lock while sleeping queues concurrent senders behind
+        // each other instead of letting them all wake at the same instant.
+        let mut last_send = self
+            .last_send
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(last) = *last_send {
+            let wait = self.min_interval.saturating_sub(last.elapsed());
+            if !wait.is_zero() {
+                match self.mode {
+                    RateLimitMode::Wait => thread::sleep(wait),
+                    RateLimitMode::Reject => return Err(BroadcastError::rate_limited(wait)),
+                }
+            }
+        }
+
+        *last_send = Some(Instant::now());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTERVAL: Duration = Duration::from_millis(50);
+
+    #[test]
+    fn test_reject_within_interval() {
+        let limiter = RateLimiter::new(INTERVAL, RateLimitMode::Reject);
+        limiter.acquire().unwrap();
+
+        let error = limiter.acquire().unwrap_err();
+        assert!(matches!(
+            error,
+            BroadcastError::RateLimited { retry_after } if retry_after <= INTERVAL
+        ));
+
+        thread::sleep(INTERVAL);
+        limiter.acquire().unwrap();
+    }
+
+    #[test]
+    fn test_wait_spaces_sends() {
+        let limiter = RateLimiter::new(INTERVAL, RateLimitMode::Wait);
+        let start = Instant::now();
+        limiter.acquire().unwrap();
+        limiter.acquire().unwrap();
+
+        assert!(start.elapsed() >= INTERVAL);
+    }
+
+    #[test]
+    fn test_clones_share_the_interval() {
+        let limiter = RateLimiter::new(INTERVAL, RateLimitMode::Reject);
+        limiter.clone().acquire().unwrap();
+
+        assert!(limiter.acquire().is_err());
+    }
+}
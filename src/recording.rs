@@ -0,0 +1,278 @@
+//! Capturing sent messages and playing them back later.
+
+use std::{
+    sync::{Mutex, MutexGuard, PoisonError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{BroadcastMessage, Client, MessageSink, Result};
+
+/// One message of a recording, with the time since the message before it.
+///
+/// Produced by [`MessageRecorder::recording`] and consumed by
+/// [`MessagePlayer::play`]. With the `serde` feature a recording can be
+/// saved and loaded as a `Vec<RecordedMessage>`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordedMessage {
+    /// Time since the previous message; zero for the first one.
+    pub delay: Duration,
+    /// The message as it was sent.
+    pub message: BroadcastMessage,
+}
+
+/// A [`MessageSink`] that forwards to another sink and logs every message
+/// it delivered, with the instant it was sent.
+///
+/// Failed sends are not logged, so playing the recording back repeats what
+/// actually reached the sim.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::{
+///     MessagePlayer, MessageRecorder, MessageSink, PitCommandMode, RecordingSink,
+/// };
+///
+/// let recorder = MessageRecorder::new(RecordingSink::new());
+/// recorder.send(PitCommandMode::Fuel(40).into())?;
+/// recorder.send(PitCommandMode::Tearoff.into())?;
+///
+/// let replay = RecordingSink::new();
+/// MessagePlayer::new(&replay).play(&recorder.recording())?;
+/// assert_eq!(replay.messages(), recorder.sink().messages());
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+#[derive(Debug)]
+pub struct MessageRecorder<S = Client> {
+    sink: S,
+    entries: Mutex<Vec<(Instant, BroadcastMessage)>>,
+}
+
+impl<S: MessageSink> MessageRecorder<S> {
+    /// Record everything sent through `sink`, usually a [`Client`].
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink,
+            entries: Mutex::default(),
+        }
+    }
+
+    /// Snapshot of every delivered message and when it was sent, in send
+    /// order.
+    pub fn entries(&self) -> Vec<(Instant, BroadcastMessage)> {
+        self.lock().clone()
+    }
+
+    /// The log as relative delays, ready for [`MessagePlayer::play`] or
+    /// serialization.
+    pub fn recording(&self) -> Vec<RecordedMessage> {
+        let entries = self.lock();
+        let mut previous = entries.first().map(|(sent_at, _)| *sent_at);
+        entries
+            .iter()
+            .map(|(sent_at, message)| RecordedMessage {
+                delay: previous
+                    .replace(*sent_at)
+                    .map_or(Duration::ZERO, |previous| {
+                        sent_at.saturating_duration_since(previous)
+                    }),
+                message: *message,
+            })
+            .collect()
+    }
+
+    /// Discard the log.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// The sink messages are forwarded to.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Unwrap the sink, discarding the log.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<(Instant, BroadcastMessage)>> {
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<S: MessageSink> MessageSink for MessageRecorder<S> {
+    fn send(&self, message: BroadcastMessage) -> Result<()> {
+        let sent_at = Instant::now();
+        self.sink.send(message)?;
+        self.lock().push((sent_at, message));
+        Ok(())
+    }
+}
+
+/// Re-sends a recording, waiting out the original gaps between messages.
+///
+/// The gaps can be scaled with [`MessagePlayer::with_time_scale`], e.g. to
+/// run a regression recording without waiting.
+#[derive(Debug, Clone)]
+pub struct MessagePlayer<S = Client> {
+    sink: S,
+    time_scale: f64,
+}
+
+impl<S: MessageSink> MessagePlayer<S> {
+    /// Play recordings through `sink`, usually a [`Client`], in real time.
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink,
+            time_scale: 1.0,
+        }
+    }
+
+    /// Multiply every recorded delay by `time_scale`: `0.5` plays twice as
+    /// fast and `0.0` sends without waiting. Negative and NaN scales are
+    /// treated as `0.0`.
+    pub fn with_time_scale(mut self, time_scale: f64) -> Self {
+        self.time_scale = if time_scale > 0.0 { time_scale } else { 0.0 };
+        self
+    }
+
+    /// The factor applied to recorded delays.
+    pub fn time_scale(&self) -> f64 {
+        self.time_scale
+    }
+
+    /// Send every message of `recording` in order, sleeping for its scaled
+    /// delay first.
+    ///
+    /// Stops at the first failed send and returns its error.
+    pub fn play(&self, recording: &[RecordedMessage]) -> Result<()> {
+        for RecordedMessage { delay, message } in recording {
+            let delay = self.scaled(*delay);
+            if !delay.is_zero() {
+                thread::sleep(delay);
+            }
+            self.sink.send(*message)?;
+        }
+        Ok(())
+    }
+
+    /// The sink messages are sent through.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Unwrap the sink.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+
+    fn scaled(&self, delay: Duration) -> Duration {
+        Duration::try_from_secs_f64(delay.as_secs_f64() * self.time_scale).unwrap_or(Duration::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{PitCommandMode, RecordingSink, ReplaySearchMode, sink::FailingSink};
+
+    const GAP: Duration = Duration::from_millis(30);
+
+    fn record() -> MessageRecorder<RecordingSink> {
+        let recorder = MessageRecorder::new(RecordingSink::new());
+        recorder
+            .send(BroadcastMessage::ReplaySearch(ReplaySearchMode::ToStart))
+            .unwrap();
+        thread::sleep(GAP);
+        recorder.send(PitCommandMode::Fuel(40).into()).unwrap();
+        recorder.send(PitCommandMode::Tearoff.into()).unwrap();
+        recorder
+    }
+
+    #[test]
+    fn test_recording_delays() {
+        let recording = record().recording();
+
+        assert_eq!(recording.len(), 3);
+        assert_eq!(recording[0].delay, Duration::ZERO);
+        assert!(recording[1].delay >= GAP);
+        assert!(recording[2].delay < GAP);
+    }
+
+    #[test]
+    fn test_failed_sends_are_not_recorded() {
+        let recorder = MessageRecorder::new(FailingSink::new(|_, message| {
+            matches!(message, BroadcastMessage::PitCommand(_))
+        }));
+        recorder.send(BroadcastMessage::ReplaySetState).unwrap();
+        assert!(recorder.send(PitCommandMode::Tearoff.into()).is_err());
+
+        let entries = recorder.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].1, BroadcastMessage::ReplaySetState);
+    }
+
+    #[test]
+    fn test_play_honors_delays() {
+        let recorder = record();
+        let replay = RecordingSink::new();
+
+        let start = Instant::now();
+        MessagePlayer::new(&replay)
+            .play(&recorder.recording())
+            .unwrap();
+        assert!(start.elapsed() >= GAP);
+        assert_eq!(replay.messages(), recorder.sink().messages());
+
+        let start = Instant::now();
+        MessagePlayer::new(&replay)
+            .with_time_scale(0.0)
+            .play(&recorder.recording())
+            .unwrap();
+        assert!(start.elapsed() < GAP);
+        assert_eq!(replay.messages().len(), 6);
+    }
+
+    #[test]
+    fn test_time_scale() {
+        let player = MessagePlayer::new(RecordingSink::new()).with_time_scale(0.5);
+        assert_eq!(
+            player.scaled(Duration::from_secs(4)),
+            Duration::from_secs(2)
+        );
+
+        assert_eq!(
+            MessagePlayer::new(RecordingSink::new())
+                .with_time_scale(f64::NAN)
+                .time_scale(),
+            0.0
+        );
+        assert_eq!(
+            MessagePlayer::new(RecordingSink::new())
+                .with_time_scale(f64::INFINITY)
+                .scaled(Duration::from_secs(1)),
+            Duration::MAX
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip_then_play() {
+        let recorder = record();
+        let recording = recorder.recording();
+
+        let json = serde_json::to_string(&recording).unwrap();
+        let loaded: Vec<RecordedMessage> = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, recording);
+
+        let replay = RecordingSink::new();
+        MessagePlayer::new(&replay)
+            .with_time_scale(0.0)
+            .play(&loaded)
+            .unwrap();
+        assert_eq!(replay.messages(), recorder.sink().messages());
+    }
+}
@@ -0,0 +1,190 @@
+//! Stateful replay playback for director UIs.
+
+use crate::{BroadcastMessage, Client, MessageSink, ReplaySpeed, Result};
+
+/// Tracks the replay speed this controller last commanded, so a UI can
+/// show whether the replay is playing and how fast, and pause and resume
+/// at the same rate.
+///
+/// The sim does not report replay state over the broadcast channel, so the
+/// tracked speed is only what this controller last sent successfully. It
+/// starts at normal speed, like a live session; a failed send leaves it
+/// unchanged. If the replay may have been changed elsewhere, read
+/// `ReplayPlaySpeed` and `ReplayPlaySlowMotion` from telemetry and pass
+/// them to [`ReplayController::sync`].
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::{BroadcastMessage, RecordingSink, ReplayController, ReplaySpeed};
+///
+/// let mut replay = ReplayController::new(RecordingSink::new());
+/// replay.set_speed(ReplaySpeed::new(4, true)?)?;
+/// replay.toggle_pause()?;
+/// assert!(replay.is_paused());
+/// replay.toggle_pause()?;
+///
+/// assert_eq!(
+///     replay.sink().messages().last(),
+///     Some(&BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::new(4, true)?))
+/// );
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReplayController<S = Client> {
+    sink: S,
+    speed: ReplaySpeed,
+    /// The last speed that was not paused, which resuming restores.
+    resume_speed: ReplaySpeed,
+}
+
+impl<S: MessageSink> ReplayController<S> {
+    /// Control the replay through `sink`, usually a [`Client`].
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink,
+            speed: ReplaySpeed::normal(),
+            resume_speed: ReplaySpeed::normal(),
+        }
+    }
+
+    /// Play at `speed`; a speed of `0` pauses.
+    pub fn set_speed(&mut self, speed: ReplaySpeed) -> Result<()> {
+        self.sink
+            .send(BroadcastMessage::ReplaySetPlaySpeed(speed))?;
+        self.sync(speed);
+        Ok(())
+    }
+
+    /// Play forward at normal speed.
+    pub fn play(&mut self) -> Result<()> {
+        self.set_speed(ReplaySpeed::normal())
+    }
+
+    /// Freeze the replay on the current frame, remembering the speed to
+    /// resume at.
+    pub fn pause(&mut self) -> Result<()> {
+        self.set_speed(ReplaySpeed::paused())
+    }
+
+    /// Play again at the speed the replay had before it was paused.
+    pub fn resume(&mut self) -> Result<()> {
+        self.set_speed(self.resume_speed)
+    }
+
+    /// Pause if playing, or [resume](ReplayController::resume) at the prior
+    /// speed, slow motion and direction included, if paused.
+    pub fn toggle_pause(&mut self) -> Result<()> {
+        if self.is_paused() {
+            self.resume()
+        } else {
+            self.pause()
+        }
+    }
+
+    /// Record `speed` as the current one without sending anything, e.g.
+    /// after reading it from telemetry.
+    pub fn sync(&mut self, speed: ReplaySpeed) {
+        self.speed = speed;
+        if speed.speed() != 0 {
+            self.resume_speed = speed;
+        }
+    }
+
+    /// The last commanded speed.
+    pub fn speed(&self) -> ReplaySpeed {
+        self.speed
+    }
+
+    /// The speed [`ReplayController::resume`] plays at.
+    pub fn resume_speed(&self) -> ReplaySpeed {
+        self.resume_speed
+    }
+
+    /// Whether the replay was last paused.
+    pub fn is_paused(&self) -> bool {
+        self.speed.speed() == 0
+    }
+
+    /// Whether the replay was last set to slow motion.
+    pub fn is_slow_motion(&self) -> bool {
+        self.speed.is_slow_motion()
+    }
+
+    /// The sink messages are sent through.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Unwrap the sink.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RecordingSink, sink::FailingSink};
+
+    fn speed(message: &BroadcastMessage) -> ReplaySpeed {
+        match message {
+            BroadcastMessage::ReplaySetPlaySpeed(speed) => *speed,
+            other => panic!("not a speed change: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_pause_then_resume_restores_speed() {
+        let mut replay = ReplayController::new(RecordingSink::new());
+        let rewind = ReplaySpeed::rewind(8).unwrap();
+
+        replay.set_speed(rewind).unwrap();
+        replay.toggle_pause().unwrap();
+        assert!(replay.is_paused());
+        assert_eq!(replay.resume_speed(), rewind);
+
+        replay.toggle_pause().unwrap();
+        assert_eq!(replay.speed(), rewind);
+
+        let sent: Vec<_> = replay.sink().messages().iter().map(speed).collect();
+        assert_eq!(sent, [rewind, ReplaySpeed::paused(), rewind]);
+    }
+
+    #[test]
+    fn test_starts_playing_at_normal_speed() {
+        let mut replay = ReplayController::new(RecordingSink::new());
+        assert!(!replay.is_paused());
+
+        replay.pause().unwrap();
+        replay.resume().unwrap();
+        assert_eq!(
+            replay.sink().messages(),
+            [
+                BroadcastMessage::replay_pause(),
+                BroadcastMessage::replay_play()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slow_motion_and_sync() {
+        let mut replay = ReplayController::new(RecordingSink::new());
+
+        replay.sync(ReplaySpeed::new(2, true).unwrap());
+        assert!(replay.is_slow_motion());
+        replay.sync(ReplaySpeed::paused());
+        assert!(replay.sink().messages().is_empty());
+
+        replay.toggle_pause().unwrap();
+        assert_eq!(replay.speed(), ReplaySpeed::new(2, true).unwrap());
+    }
+
+    #[test]
+    fn test_failed_send_keeps_state() {
+        let mut replay = ReplayController::new(FailingSink::always());
+        assert!(replay.pause().is_err());
+        assert!(!replay.is_paused());
+        assert_eq!(replay.speed(), ReplaySpeed::normal());
+    }
+}
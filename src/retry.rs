@@ -0,0 +1,83 @@
+//! Retrying sends that fail transiently.
+
+use std::{thread, time::Duration};
+
+use crate::Result;
+
+/// How many times to attempt a send and how long to wait in between.
+///
+/// Only errors whose [`is_retryable`](crate::BroadcastError::is_retryable) is
+/// `true` are retried; anything else, such as
+/// [`UnsupportedPlatform`](crate::BroadcastError::UnsupportedPlatform), is returned
+/// after the first attempt. The wait starts at the initial backoff and doubles
+/// after every failed attempt.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use iracing_broadcast::RetryPolicy;
+///
+/// let policy = RetryPolicy::new(4, Duration::from_millis(10));
+/// assert_eq!(policy.max_attempts(), 4);
+/// assert_eq!(policy.backoff(2), Duration::from_millis(40));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Allow up to `max_attempts` sends in total, waiting `initial_backoff`
+    /// after the first failure. A `max_attempts` of `0` is treated as `1`.
+    pub fn new(max_attempts: u32, initial_backoff: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            initial_backoff,
+        }
+    }
+
+    /// Total number of attempts, including the first.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Wait after the failed attempt numbered `retry` (starting at `0`).
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+    }
+
+    /// Run `send` until it succeeds, fails with a non-retryable error, or the
+    /// attempts run out, returning the last error in the latter cases.
+    pub(crate) fn run<F>(&self, mut send: F) -> Result<()>
+    where
+        F: FnMut() -> Result<()>,
+    {
+        let mut retry = 0;
+        loop {
+            let error = match send() {
+                Ok(()) => return Ok(()),
+                Err(error) => error,
+            };
+
+            if !error.is_retryable() || retry + 1 >= self.max_attempts {
+                return Err(error);
+            }
+
+            #[cfg(feature = "tracing")]
+            tracing::debug!(retry, %error, "retrying broadcast send");
+
+            thread::sleep(self.backoff(retry));
+            retry += 1;
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Three attempts, starting with a 50 ms wait.
+    fn default() -> Self {
+        Self::new(3, Duration::from_millis(50))
+    }
+}
@@ -0,0 +1,211 @@
+//! Common replay workflows as single calls.
+
+use std::{thread, time::Duration};
+
+use crate::{BroadcastMessage, Client, MessageSink, ReplaySearchMode, Result};
+
+/// Drives the replay with the message sequences a director uses most.
+///
+/// Each method sends its messages in order and stops at the first failure.
+/// Searches are always preceded by a pause so the replay does not run on
+/// from wherever it was before the jump landed.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::{
+///     BroadcastMessage, RecordingSink, ReplayScrubber, ReplaySearchMode, ReplaySpeed,
+/// };
+///
+/// let scrubber = ReplayScrubber::new(RecordingSink::new());
+/// scrubber.to_next_incident_and_play()?;
+///
+/// assert_eq!(
+///     scrubber.sink().messages(),
+///     [
+///         BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::paused()),
+///         BroadcastMessage::ReplaySearch(ReplaySearchMode::NextIncident),
+///         BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::normal()),
+///     ]
+/// );
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReplayScrubber<S = Client> {
+    sink: S,
+    step_interval: Duration,
+}
+
+impl<S: MessageSink> ReplayScrubber<S> {
+    /// Scrub the replay through `sink`, usually a [`Client`].
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink,
+            step_interval: Duration::ZERO,
+        }
+    }
+
+    /// Wait `interval` before each lap step of
+    /// [`jump_to_lap`](ReplayScrubber::jump_to_lap), so a long jump does
+    /// not flood the sim's message queue. Steps are sent back to back by
+    /// default.
+    pub fn with_step_interval(mut self, interval: Duration) -> Self {
+        self.step_interval = interval;
+        self
+    }
+
+    /// Freeze the replay on the current frame.
+    pub fn pause(&self) -> Result<()> {
+        self.sink.send(BroadcastMessage::replay_pause())
+    }
+
+    /// Play forward at normal speed.
+    pub fn play(&self) -> Result<()> {
+        self.sink.send(BroadcastMessage::replay_play())
+    }
+
+    /// Pause, jump to the next recorded incident and play it at 1x.
+    pub fn to_next_incident_and_play(&self) -> Result<()> {
+        self.search_and_play(ReplaySearchMode::NextIncident)
+    }
+
+    /// Pause, jump to the previous recorded incident and play it at 1x.
+    pub fn to_previous_incident_and_play(&self) -> Result<()> {
+        self.search_and_play(ReplaySearchMode::PreviousIncident)
+    }
+
+    /// Pause and jump to the start of lap `lap`, leaving the replay paused.
+    ///
+    /// The sim has no absolute lap search, so this rewinds to the start of
+    /// the replay and then steps forward one lap `lap` times; lap `0` is the
+    /// start of the replay.
+    ///
+    /// These are the messages of [`BroadcastMessage::replay_to_lap`] after
+    /// its focus change, with the
+    /// [step interval](ReplayScrubber::with_step_interval) before each lap
+    /// step. Returns [`BroadcastError::InvalidParameter`](crate::BroadcastError::InvalidParameter)
+    /// without sending anything if `lap` is above
+    /// [`BroadcastMessage::MAX_REPLAY_LAP`].
+    pub fn jump_to_lap(&self, lap: u16) -> Result<()> {
+        let (rewind, mut steps) = BroadcastMessage::replay_lap_search(lap)?;
+        rewind
+            .into_iter()
+            .try_for_each(|message| self.sink.send(message))?;
+        steps.try_for_each(|message| {
+            if !self.step_interval.is_zero() {
+                thread::sleep(self.step_interval);
+            }
+            self.sink.send(message)
+        })
+    }
+
+    /// The sink messages are sent through.
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    /// Unwrap the sink.
+    pub fn into_inner(self) -> S {
+        self.sink
+    }
+
+    fn search(&self, mode: ReplaySearchMode) -> Result<()> {
+        self.sink.send(BroadcastMessage::ReplaySearch(mode))
+    }
+
+    fn search_and_play(&self, mode: ReplaySearchMode) -> Result<()> {
+        self.pause()?;
+        self.search(mode)?;
+        self.play()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RecordingSink, ReplaySpeed, sink::FailingSink};
+
+    fn paused() -> BroadcastMessage {
+        BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::paused())
+    }
+
+    #[test]
+    fn test_previous_incident_and_play() {
+        let scrubber = ReplayScrubber::new(RecordingSink::new());
+        scrubber.to_previous_incident_and_play().unwrap();
+
+        assert_eq!(
+            scrubber.into_inner().messages(),
+            vec![
+                paused(),
+                BroadcastMessage::ReplaySearch(ReplaySearchMode::PreviousIncident),
+                BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::normal()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_jump_to_lap() {
+        let scrubber = ReplayScrubber::new(RecordingSink::new());
+        scrubber.jump_to_lap(3).unwrap();
+
+        let mut expected = vec![
+            paused(),
+            BroadcastMessage::ReplaySearch(ReplaySearchMode::ToStart),
+        ];
+        expected.extend(std::iter::repeat_n(
+            BroadcastMessage::ReplaySearch(ReplaySearchMode::NextLap),
+            3,
+        ));
+        assert_eq!(scrubber.sink().messages(), expected);
+        assert_eq!(
+            BroadcastMessage::replay_to_lap("7", 3).unwrap()[1..],
+            expected
+        );
+
+        scrubber.sink().clear();
+        scrubber.jump_to_lap(0).unwrap();
+        assert_eq!(
+            scrubber.sink().messages(),
+            vec![
+                paused(),
+                BroadcastMessage::ReplaySearch(ReplaySearchMode::ToStart)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_jump_to_lap_rejects_laps_above_max() {
+        let scrubber = ReplayScrubber::new(RecordingSink::new());
+        scrubber
+            .jump_to_lap(BroadcastMessage::MAX_REPLAY_LAP)
+            .unwrap();
+        scrubber.sink().clear();
+
+        assert!(matches!(
+            scrubber.jump_to_lap(BroadcastMessage::MAX_REPLAY_LAP + 1),
+            Err(crate::BroadcastError::InvalidParameter { .. })
+        ));
+        assert!(scrubber.sink().messages().is_empty());
+    }
+
+    #[test]
+    fn test_step_interval_paces_lap_steps() {
+        let interval = Duration::from_millis(5);
+        let scrubber = ReplayScrubber::new(RecordingSink::new()).with_step_interval(interval);
+
+        let started = std::time::Instant::now();
+        scrubber.jump_to_lap(3).unwrap();
+        assert!(started.elapsed() >= interval * 3);
+        assert_eq!(scrubber.sink().messages().len(), 5);
+    }
+
+    #[test]
+    fn test_stops_at_first_failure() {
+        let scrubber = ReplayScrubber::new(FailingSink::new(|_, message| {
+            matches!(message, BroadcastMessage::ReplaySearch(_))
+        }));
+        assert!(scrubber.to_next_incident_and_play().is_err());
+        assert_eq!(scrubber.sink().messages(), vec![paused()]);
+    }
+}
@@ -0,0 +1,225 @@
+//! Turning JSON commands into broadcasts, for local control servers.
+//!
+//! Browser-based director UIs usually talk to a small local server that
+//! relays their commands to the sim. This module is that server's core,
+//! independent of the transport: feed it each incoming text frame or line
+//! and send back the JSON it returns.
+//!
+//! A command is a [`BroadcastMessage`] in its `serde` form, e.g.
+//! `{"PitCommand":{"Fuel":65}}` or `"ReplaySetState"`. Replies are
+//! [`Reply`] values:
+//!
+//! ```json
+//! {"status":"sent","message":{"PitCommand":{"Fuel":65}}}
+//! {"status":"error","error":{"kind":"invalid_parameter","message":"...","retryable":false,"suggestions":["..."]}}
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BroadcastError, BroadcastMessage, MessageSink, Result};
+
+/// The answer to one command.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum Reply {
+    /// The command parsed and was sent.
+    Sent { message: BroadcastMessage },
+    /// The command did not parse, or sending it failed.
+    Error { error: ErrorReply },
+}
+
+/// A [`BroadcastError`] in a form a UI can act on without parsing text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ErrorReply {
+    /// The error variant in `snake_case`, e.g. `"invalid_parameter"`.
+    pub kind: String,
+    /// The error's `Display` text.
+    pub message: String,
+    /// See [`BroadcastError::is_retryable`].
+    pub retryable: bool,
+    /// See [`BroadcastError::recovery_suggestions`].
+    pub suggestions: Vec<String>,
+}
+
+impl From<&BroadcastError> for ErrorReply {
+    fn from(error: &BroadcastError) -> Self {
+        ErrorReply {
+            kind: error_kind(error).to_owned(),
+            message: error.to_string(),
+            retryable: error.is_retryable(),
+            suggestions: error
+                .recovery_suggestions()
+                .into_iter()
+                .map(str::to_owned)
+                .collect(),
+        }
+    }
+}
+
+impl From<Result<BroadcastMessage>> for Reply {
+    fn from(result: Result<BroadcastMessage>) -> Self {
+        match result {
+            Ok(message) => Reply::Sent { message },
+            Err(error) => Reply::Error {
+                error: ErrorReply::from(&error),
+            },
+        }
+    }
+}
+
+/// Parse one JSON command.
+///
+/// Malformed JSON and unknown or out-of-range fields are reported as
+/// [`BroadcastError::InvalidParameter`] for the `command` parameter.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::{BroadcastMessage, PitCommandMode, serve};
+///
+/// assert_eq!(
+///     serve::parse_command(r#"{"PitCommand":{"Fuel":65}}"#)?,
+///     BroadcastMessage::PitCommand(PitCommandMode::Fuel(65))
+/// );
+/// assert!(serve::parse_command(r#"{"PitCommand":"Refuel"}"#).is_err());
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+pub fn parse_command(json: &str) -> Result<BroadcastMessage> {
+    serde_json::from_str(json)
+        .map_err(|error| BroadcastError::invalid_parameter("command", error.to_string()))
+}
+
+/// Parse one JSON command and send it through `sink`, usually a
+/// [`Client`](crate::Client), returning the message that was sent.
+pub fn dispatch<S: MessageSink + ?Sized>(sink: &S, json: &str) -> Result<BroadcastMessage> {
+    let message = parse_command(json)?;
+    sink.send(message)?;
+    Ok(message)
+}
+
+/// Like [`dispatch`], but always answers with a serialized [`Reply`], ready
+/// to write back to the caller.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::{RecordingSink, serve};
+///
+/// let sink = RecordingSink::new();
+/// assert_eq!(
+///     serve::handle(&sink, r#""ReplaySetState""#),
+///     r#"{"status":"sent","message":"ReplaySetState"}"#
+/// );
+/// assert!(serve::handle(&sink, "not json").starts_with(r#"{"status":"error""#));
+/// ```
+pub fn handle<S: MessageSink + ?Sized>(sink: &S, json: &str) -> String {
+    let reply = Reply::from(dispatch(sink, json));
+    serde_json::to_string(&reply).expect("replies serialize to JSON")
+}
+
+/// The `snake_case` name of `error`'s variant.
+fn error_kind(error: &BroadcastError) -> &'static str {
+    match error {
+        BroadcastError::Connection { .. } => "connection",
+        BroadcastError::UnsupportedPlatform { .. } => "unsupported_platform",
+        BroadcastError::UnknownMessageType { .. } => "unknown_message_type",
+        BroadcastError::InvalidCarNumber { .. } => "invalid_car_number",
+        BroadcastError::InvalidParameter { .. } => "invalid_parameter",
+        BroadcastError::RateLimited { .. } => "rate_limited",
+        BroadcastError::Unverified { .. } => "unverified",
+        BroadcastError::Thread { .. } => "thread",
+        #[cfg(windows)]
+        BroadcastError::WindowsApi { .. } => "windows_api",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::{Value, json};
+
+    use super::*;
+    use crate::{CameraFocusMode, Client, PitCommandMode, RateLimitMode, RecordingSink};
+
+    fn reply(sink: &impl MessageSink, json: &str) -> Value {
+        serde_json::from_str(&handle(sink, json)).unwrap()
+    }
+
+    #[test]
+    fn test_dispatches_parsed_commands() {
+        let sink = RecordingSink::new();
+        dispatch(&sink, r#"{"PitCommand":{"Fuel":65}}"#).unwrap();
+        dispatch(&sink, r#"{"CameraSwitchNumber":["064",4,0]}"#).unwrap();
+        dispatch(&sink, r#"{"CameraSwitchFocus":["Leader",1,1]}"#).unwrap();
+        dispatch(&sink, r#" "ReplaySetState" "#).unwrap();
+
+        assert_eq!(
+            sink.messages(),
+            vec![
+                BroadcastMessage::PitCommand(PitCommandMode::Fuel(65)),
+                BroadcastMessage::camera_switch_number("064", 4, 0),
+                BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Leader, 1, 1),
+                BroadcastMessage::ReplaySetState,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sent_reply() {
+        let sink = RecordingSink::new();
+
+        assert_eq!(
+            reply(&sink, r#"{"PitCommand":"Tearoff"}"#),
+            json!({ "status": "sent", "message": { "PitCommand": "Tearoff" } })
+        );
+    }
+
+    #[test]
+    fn test_malformed_commands_are_not_sent() {
+        let sink = RecordingSink::new();
+
+        for command in [
+            "",
+            "{",
+            r#"{"PitCommand":"Refuel"}"#,
+            r#"{"ChatCommandMacro":300}"#,
+        ] {
+            let reply = reply(&sink, command);
+            assert_eq!(reply["status"], "error", "{command}");
+            assert_eq!(reply["error"]["kind"], "invalid_parameter", "{command}");
+            assert_eq!(reply["error"]["retryable"], false, "{command}");
+        }
+        assert!(sink.messages().is_empty());
+    }
+
+    #[test]
+    fn test_send_errors_become_replies() {
+        let mut client = Client::offline();
+        client.set_dry_run(true);
+
+        let Reply::Error { error } = Reply::from(dispatch(&client, r#"{"ChatCommandMacro":40}"#))
+        else {
+            panic!("out-of-range macro was sent");
+        };
+        assert_eq!(error.kind, "invalid_parameter");
+        assert!(!error.suggestions.is_empty());
+
+        let mut client = Client::offline();
+        client.set_rate_limit(Duration::from_secs(60), RateLimitMode::Reject);
+        dispatch(&client, r#"{"ChatCommandMacro":4}"#).unwrap();
+        let reply = reply(&client, r#"{"ChatCommandMacro":4}"#);
+        assert_eq!(reply["error"]["kind"], "rate_limited");
+        assert_eq!(reply["error"]["retryable"], true);
+    }
+
+    #[test]
+    fn test_reply_round_trip() {
+        let sink = RecordingSink::new();
+
+        for command in [r#""ReplaySetState""#, "nonsense"] {
+            let parsed: Reply = serde_json::from_str(&handle(&sink, command)).unwrap();
+            assert_eq!(parsed, Reply::from(parse_command(command)));
+        }
+    }
+}
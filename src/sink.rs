@@ -0,0 +1,312 @@
+//! Platform-independent message sinks.
+//!
+//! [`MessageSink`] abstracts over "something that accepts broadcast messages"
+//! so control logic can be written once and exercised against a
+//! [`RecordingSink`] in tests, then pointed at a real [`Client`] in
+//! production.
+//!
+//! ```
+//! use iracing_broadcast::{BroadcastMessage, MessageSink, PitCommandMode, RecordingSink};
+//!
+//! fn request_tearoff(sink: &impl MessageSink) -> iracing_broadcast::Result<()> {
+//!     sink.send(BroadcastMessage::PitCommand(PitCommandMode::Tearoff))
+//! }
+//!
+//! let sink = RecordingSink::new();
+//! request_tearoff(&sink)?;
+//! assert_eq!(
+//!     sink.messages(),
+//!     vec![BroadcastMessage::PitCommand(PitCommandMode::Tearoff)]
+//! );
+//! # Ok::<(), iracing_broadcast::BroadcastError>(())
+//! ```
+
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::{BroadcastError, BroadcastMessage, Client, Result, RetryPolicy};
+
+/// A destination for broadcast messages.
+pub trait MessageSink {
+    /// Deliver a single message.
+    fn send(&self, message: BroadcastMessage) -> Result<()>;
+
+    /// Deliver a sequence of messages in order.
+    ///
+    /// Every message is attempted even if an earlier one fails; failures are
+    /// collected as `(index, error)` pairs, where `index` is the message's
+    /// position in `messages`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use iracing_broadcast::{BroadcastMessage, MessageSink, PitCommandMode, RecordingSink};
+    ///
+    /// let sink = RecordingSink::new();
+    /// let pit_stop = [
+    ///     PitCommandMode::Clear,
+    ///     PitCommandMode::Fuel(40),
+    ///     PitCommandMode::Tearoff,
+    /// ];
+    ///
+    /// sink.send_all(pit_stop.map(BroadcastMessage::PitCommand))
+    ///     .expect("recording never fails");
+    /// assert_eq!(sink.messages().len(), 3);
+    /// ```
+    fn send_all<I>(&self, messages: I) -> Result<(), Vec<(usize, BroadcastError)>>
+    where
+        I: IntoIterator<Item = BroadcastMessage>,
+        Self: Sized,
+    {
+        let failures: Vec<_> = messages
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, message)| self.send(message).err().map(|error| (index, error)))
+            .collect();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures)
+        }
+    }
+
+    /// Deliver a message, retrying transient failures according to `policy`.
+    ///
+    /// Returns the last error if every attempt fails, or the first
+    /// non-retryable error.
+    fn send_with_retry(&self, message: BroadcastMessage, policy: &RetryPolicy) -> Result<()>
+    where
+        Self: Sized,
+    {
+        policy.run(|| self.send(message))
+    }
+}
+
+impl MessageSink for Client {
+    fn send(&self, message: BroadcastMessage) -> Result<()> {
+        self.send_message(message)
+    }
+}
+
+/// Lets wrappers such as [`ReplayScrubber`](crate::ReplayScrubber) borrow a
+/// sink instead of owning it.
+impl<S: MessageSink + ?Sized> MessageSink for &S {
+    fn send(&self, message: BroadcastMessage) -> Result<()> {
+        (**self).send(message)
+    }
+}
+
+/// A [`MessageSink`] that stores every message it receives.
+///
+/// Available on all platforms; intended for unit-testing code that drives a
+/// [`Client`] without a running simulator.
+#[derive(Debug, Default)]
+pub struct RecordingSink {
+    messages: Mutex<Vec<BroadcastMessage>>,
+}
+
+impl RecordingSink {
+    /// Create an empty recording sink.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Snapshot of every message sent so far, in send order.
+    pub fn messages(&self) -> Vec<BroadcastMessage> {
+        self.lock().clone()
+    }
+
+    /// Discard all recorded messages.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<BroadcastMessage>> {
+        // A panic while holding the lock cannot leave the Vec half-written,
+        // so recover the data rather than propagating the poison.
+        self.messages.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl MessageSink for RecordingSink {
+    fn send(&self, message: BroadcastMessage) -> Result<()> {
+        self.lock().push(message);
+        Ok(())
+    }
+}
+
+/// Decides, from the attempt number and message, whether a
+/// [`FailingSink`] rejects a send.
+#[cfg(test)]
+type Rejects = dyn Fn(usize, &BroadcastMessage) -> bool + Send + Sync;
+
+/// A test double that records the messages it accepts and fails the sends
+/// its predicate rejects, with a transient error unless told otherwise.
+#[cfg(test)]
+pub(crate) struct FailingSink {
+    accepted: RecordingSink,
+    rejects: Box<Rejects>,
+    error: fn() -> BroadcastError,
+    attempts: Mutex<usize>,
+}
+
+#[cfg(test)]
+impl FailingSink {
+    /// Reject the sends for which `rejects` returns `true`, given the
+    /// zero-based attempt number and the message.
+    pub(crate) fn new<F>(rejects: F) -> Self
+    where
+        F: Fn(usize, &BroadcastMessage) -> bool + Send + Sync + 'static,
+    {
+        FailingSink {
+            accepted: RecordingSink::new(),
+            rejects: Box::new(rejects),
+            error: || BroadcastError::connection_failed("injected failure"),
+            attempts: Mutex::new(0),
+        }
+    }
+
+    /// Reject every send.
+    pub(crate) fn always() -> Self {
+        Self::new(|_, _| true)
+    }
+
+    /// Fail with `error` instead of a connection error.
+    pub(crate) fn with_error(mut self, error: fn() -> BroadcastError) -> Self {
+        self.error = error;
+        self
+    }
+
+    /// The messages accepted so far, in send order.
+    pub(crate) fn messages(&self) -> Vec<BroadcastMessage> {
+        self.accepted.messages()
+    }
+
+    /// How many sends were attempted, failed ones included.
+    pub(crate) fn attempts(&self) -> usize {
+        *self.attempts.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+impl MessageSink for FailingSink {
+    fn send(&self, message: BroadcastMessage) -> Result<()> {
+        let attempt = {
+            let mut attempts = self.attempts.lock().unwrap_or_else(PoisonError::into_inner);
+            *attempts += 1;
+            *attempts - 1
+        };
+
+        if (self.rejects)(attempt, &message) {
+            return Err((self.error)());
+        }
+        self.accepted.send(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use crate::{PitCommandMode, ReplaySearchMode};
+
+    fn unsupported() -> BroadcastError {
+        BroadcastError::unsupported_platform("Broadcast Client", "Windows")
+    }
+
+    fn pit_sequence() -> Vec<BroadcastMessage> {
+        [
+            PitCommandMode::Clear,
+            PitCommandMode::Fuel(40),
+            PitCommandMode::LF(179),
+            PitCommandMode::Tearoff,
+        ]
+        .into_iter()
+        .map(BroadcastMessage::PitCommand)
+        .collect()
+    }
+
+    #[test]
+    fn test_recording_sink_records_in_order() {
+        let sink = RecordingSink::new();
+        sink.send(BroadcastMessage::PitCommand(PitCommandMode::Clear))
+            .unwrap();
+        sink.send(BroadcastMessage::ReplaySearch(ReplaySearchMode::ToEnd))
+            .unwrap();
+
+        assert_eq!(
+            sink.messages(),
+            vec![
+                BroadcastMessage::PitCommand(PitCommandMode::Clear),
+                BroadcastMessage::ReplaySearch(ReplaySearchMode::ToEnd),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recording_sink_clear() {
+        let sink = RecordingSink::new();
+        sink.send(BroadcastMessage::ReplaySetState).unwrap();
+        sink.clear();
+
+        assert!(sink.messages().is_empty());
+    }
+
+    #[test]
+    fn test_send_all_preserves_order() {
+        let sink = RecordingSink::new();
+        sink.send_all(pit_sequence()).unwrap();
+
+        assert_eq!(sink.messages(), pit_sequence());
+    }
+
+    #[test]
+    fn test_send_all_reports_failed_index() {
+        let sink = FailingSink::new(|attempt, _| attempt == 2);
+
+        let failures = sink.send_all(pit_sequence()).unwrap_err();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, 2);
+        assert!(matches!(failures[0].1, BroadcastError::Connection { .. }));
+
+        // The messages after the failure were still sent.
+        let mut expected = pit_sequence();
+        expected.remove(2);
+        assert_eq!(sink.messages(), expected);
+    }
+
+    #[test]
+    fn test_retry_until_success() {
+        let sink = FailingSink::new(|attempt, _| attempt < 2);
+        let policy = RetryPolicy::new(3, Duration::ZERO);
+
+        sink.send_with_retry(BroadcastMessage::ReplaySetState, &policy)
+            .unwrap();
+        assert_eq!(sink.attempts(), 3);
+    }
+
+    #[test]
+    fn test_retry_gives_up_with_last_error() {
+        let sink = FailingSink::new(|attempt, _| attempt < 5);
+        let policy = RetryPolicy::new(3, Duration::ZERO);
+
+        let error = sink
+            .send_with_retry(BroadcastMessage::ReplaySetState, &policy)
+            .unwrap_err();
+        assert!(matches!(error, BroadcastError::Connection { .. }));
+        assert_eq!(sink.attempts(), 3);
+    }
+
+    #[test]
+    fn test_retry_skips_non_retryable_errors() {
+        let sink = FailingSink::new(|attempt, _| attempt < 1).with_error(unsupported);
+        let policy = RetryPolicy::new(3, Duration::ZERO);
+
+        let error = sink
+            .send_with_retry(BroadcastMessage::ReplaySetState, &policy)
+            .unwrap_err();
+        assert!(matches!(error, BroadcastError::UnsupportedPlatform { .. }));
+        assert_eq!(sink.attempts(), 1);
+    }
+}
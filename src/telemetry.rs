@@ -0,0 +1,597 @@
+//! Confirming sends against the sim's shared-memory telemetry.
+//!
+//! The sim publishes its live state in a memory-mapped file: a header, a
+//! table of variable descriptions, and a ring of buffers holding the latest
+//! values. Only the header, the variable table and single values are read;
+//! the session info YAML is never parsed.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use self::memory::Memory;
+use crate::{
+    BroadcastError, BroadcastMessage, Client, PitCommandMode, ReplayPositionMode, Result,
+    client::BroadcastMessageProvider,
+};
+
+/// Size of `irsdk_header`, including the four `irsdk_varBuf` slots.
+const HEADER_LEN: usize = 112;
+/// Size of one `irsdk_varHeader`.
+const VAR_HEADER_LEN: usize = 144;
+/// Longest variable name, including the terminating NUL.
+const VAR_NAME_LEN: usize = 32;
+/// `irsdk_stConnected` in the header's status field.
+const STATUS_CONNECTED: i32 = 1;
+/// How often [`Telemetry::wait_until`] polls; the sim updates at 60 Hz.
+const POLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// `irsdk_PitSvFlags` bits in the `PitSvFlags` variable.
+mod pit_flags {
+    pub const TIRES: i32 = 0x0F;
+    pub const LF: i32 = 0x01;
+    pub const RF: i32 = 0x02;
+    pub const LR: i32 = 0x04;
+    pub const RR: i32 = 0x08;
+    pub const FUEL: i32 = 0x10;
+    pub const TEAROFF: i32 = 0x20;
+    pub const FAST_REPAIR: i32 = 0x40;
+    pub const ALL: i32 = 0x7F;
+}
+
+/// Read access to the sim's live telemetry.
+///
+/// Use [`Client::send_verified`] to send a message and wait until its effect
+/// shows up, or [`Telemetry::read_i32`] to inspect variables directly.
+///
+/// Requires the `telemetry` feature. Off Windows, [`Telemetry::open`]
+/// returns [`BroadcastError::UnsupportedPlatform`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use iracing_broadcast::{BroadcastMessage, Client, Telemetry};
+///
+/// let client = Client::new()?;
+/// let telemetry = Telemetry::open()?;
+///
+/// client.send_verified(
+///     BroadcastMessage::camera_switch_number("064", 4, 1),
+///     &telemetry,
+///     Duration::from_millis(500),
+/// )?;
+/// assert_eq!(telemetry.read_i32("CamGroupNumber"), Some(4));
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+#[derive(Debug)]
+pub struct Telemetry {
+    memory: Memory,
+}
+
+impl Telemetry {
+    /// Whether the sim is running a session and updating telemetry.
+    pub fn is_connected(&self) -> bool {
+        let mut header = [0; HEADER_LEN];
+        self.memory.read(0, &mut header) && read_i32_at(&header, 4) & STATUS_CONNECTED != 0
+    }
+
+    /// The latest value of the integer, bit field or boolean variable
+    /// `name`, e.g. `"CamCarIdx"` or `"ReplayFrameNum"`.
+    ///
+    /// Returns `None` if there is no such variable or it holds another type.
+    /// Array variables yield their first element.
+    pub fn read_i32(&self, name: &str) -> Option<i32> {
+        lookup_i32(|offset, buf| self.memory.read(offset, buf), name)
+    }
+
+    /// Poll until `condition` holds or `timeout` passes, returning whether
+    /// it held.
+    ///
+    /// The condition is always checked at least once.
+    pub fn wait_until<F>(&self, timeout: Duration, mut condition: F) -> bool
+    where
+        F: FnMut(&Self) -> bool,
+    {
+        let start = Instant::now();
+        loop {
+            if condition(self) {
+                return true;
+            }
+            if start.elapsed() >= timeout {
+                return false;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+impl Client {
+    /// Send a message, then wait up to `timeout` for its effect to show up
+    /// in `telemetry`.
+    ///
+    /// Checked effects are the camera group and camera after a camera
+    /// switch, the play speed after [`BroadcastMessage::ReplaySetPlaySpeed`],
+    /// the frame after seeking to an absolute frame, and the pit service
+    /// flags after pit commands. Camera switches that keep the group or
+    /// camera (`0`) are checked on the other number only. Other messages are
+    /// sent without checking.
+    ///
+    /// Returns [`BroadcastError::Connection`] without sending if the sim is
+    /// not updating telemetry, and [`BroadcastError::Unverified`] if the
+    /// effect did not appear in time.
+    ///
+    /// Requires the `telemetry` feature.
+    pub fn send_verified(
+        &self,
+        message: impl Into<BroadcastMessage>,
+        telemetry: &Telemetry,
+        timeout: Duration,
+    ) -> Result<()> {
+        let message = message.into();
+        let expected = expectations(&message);
+        if expected.is_empty() {
+            return self.send_message(message);
+        }
+        if !telemetry.is_connected() {
+            return Err(BroadcastError::connection_failed(
+                "iRacing is not updating telemetry",
+            ));
+        }
+
+        self.send_message(message)?;
+        let holds = |telemetry: &Telemetry| {
+            expected
+                .iter()
+                .all(|(name, expected)| telemetry.read_i32(name).is_some_and(|v| expected.holds(v)))
+        };
+        if telemetry.wait_until(timeout, holds) {
+            return Ok(());
+        }
+
+        let mismatches: Vec<_> = expected
+            .iter()
+            .filter_map(|(name, expected)| match telemetry.read_i32(name) {
+                Some(value) if expected.holds(value) => None,
+                Some(value) => Some(format!("{name} is {value:#x}, expected {expected}")),
+                None => Some(format!("{name} is not in telemetry")),
+            })
+            .collect();
+        let (message_type, ..) = (&message).to_message();
+        Err(BroadcastError::unverified(
+            message_type,
+            format!("after {timeout:?}, {}", mismatches.join("; ")),
+        ))
+    }
+}
+
+/// The value a telemetry variable should have once a message took effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expected {
+    Equals(i32),
+    BitsSet(i32),
+    BitsClear(i32),
+}
+
+impl Expected {
+    fn holds(self, value: i32) -> bool {
+        match self {
+            Expected::Equals(expected) => value == expected,
+            Expected::BitsSet(bits) => value & bits == bits,
+            Expected::BitsClear(bits) => value & bits == 0,
+        }
+    }
+}
+
+impl std::fmt::Display for Expected {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expected::Equals(value) => write!(f, "{value:#x}"),
+            Expected::BitsSet(bits) => write!(f, "bits {bits:#x} set"),
+            Expected::BitsClear(bits) => write!(f, "bits {bits:#x} clear"),
+        }
+    }
+}
+
+/// The telemetry variables `message` changes, and to what.
+fn expectations(message: &BroadcastMessage) -> Vec<(&'static str, Expected)> {
+    let camera = |group: u8, camera: u8| {
+        [("CamGroupNumber", group), ("CamCameraNumber", camera)]
+            .into_iter()
+            .filter(|(_, number)| *number != 0)
+            .map(|(name, number)| (name, Expected::Equals(number.into())))
+            .collect()
+    };
+
+    match message {
+        BroadcastMessage::CameraSwitchPosition(_, group, number)
+        | BroadcastMessage::CameraSwitchNumber(_, group, number)
+        | BroadcastMessage::CameraSwitchFocus(_, group, number) => camera(*group, *number),
+        BroadcastMessage::ReplaySetPlaySpeed(speed) => vec![
+            ("ReplayPlaySpeed", Expected::Equals(speed.speed().into())),
+            (
+                "ReplayPlaySlowMotion",
+                Expected::Equals(speed.is_slow_motion().into()),
+            ),
+        ],
+        BroadcastMessage::ReplaySetPlayPosition(ReplayPositionMode::Begin, frame) => {
+            match i32::try_from(*frame) {
+                Ok(frame) => vec![("ReplayFrameNum", Expected::Equals(frame))],
+                Err(_) => Vec::new(),
+            }
+        }
+        BroadcastMessage::PitCommand(mode) => {
+            let expected = match mode {
+                PitCommandMode::Clear => Expected::BitsClear(pit_flags::ALL),
+                PitCommandMode::Tearoff => Expected::BitsSet(pit_flags::TEAROFF),
+                PitCommandMode::Fuel(_) => Expected::BitsSet(pit_flags::FUEL),
+                PitCommandMode::LF(_) => Expected::BitsSet(pit_flags::LF),
+                PitCommandMode::RF(_) => Expected::BitsSet(pit_flags::RF),
+                PitCommandMode::LR(_) => Expected::BitsSet(pit_flags::LR),
+                PitCommandMode::RR(_) => Expected::BitsSet(pit_flags::RR),
+                PitCommandMode::ClearTires => Expected::BitsClear(pit_flags::TIRES),
+                PitCommandMode::FastRepair => Expected::BitsSet(pit_flags::FAST_REPAIR),
+                PitCommandMode::ClearTearoff => Expected::BitsClear(pit_flags::TEAROFF),
+                PitCommandMode::ClearFastRepair => Expected::BitsClear(pit_flags::FAST_REPAIR),
+                PitCommandMode::ClearFuel => Expected::BitsClear(pit_flags::FUEL),
+            };
+            vec![("PitSvFlags", expected)]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Find variable `name` and read its latest value, using `read` to copy
+/// bytes out of the shared memory at an offset.
+fn lookup_i32(read: impl Fn(usize, &mut [u8]) -> bool, name: &str) -> Option<i32> {
+    let mut header = [0; HEADER_LEN];
+    if !read(0, &mut header) {
+        return None;
+    }
+
+    let num_vars = usize::try_from(read_i32_at(&header, 24)).ok()?;
+    let var_header_offset = usize::try_from(read_i32_at(&header, 28)).ok()?;
+    let num_buf = usize::try_from(read_i32_at(&header, 32)).ok()?.min(4);
+    // The buffer the sim finished writing last has the highest tick count.
+    let buffer_offset = (0..num_buf)
+        .map(|slot| 48 + slot * 16)
+        .max_by_key(|&slot| read_i32_at(&header, slot))
+        .and_then(|slot| usize::try_from(read_i32_at(&header, slot + 4)).ok())?;
+
+    let mut var_headers = vec![0; num_vars.checked_mul(VAR_HEADER_LEN)?];
+    if !read(var_header_offset, &mut var_headers) {
+        return None;
+    }
+    let var = var_headers
+        .chunks_exact(VAR_HEADER_LEN)
+        .find(|var| var_name(var) == name.as_bytes())?;
+
+    let offset = buffer_offset.checked_add(usize::try_from(read_i32_at(var, 4)).ok()?)?;
+    match read_i32_at(var, 0) {
+        // irsdk_bool
+        1 => {
+            let mut value = [0; 1];
+            read(offset, &mut value).then_some(value[0].into())
+        }
+        // irsdk_int, irsdk_bitField
+        2 | 3 => {
+            let mut value = [0; 4];
+            read(offset, &mut value).then_some(i32::from_le_bytes(value))
+        }
+        _ => None,
+    }
+}
+
+/// The NUL-terminated name of an `irsdk_varHeader`.
+fn var_name(var: &[u8]) -> &[u8] {
+    let name = &var[16..16 + VAR_NAME_LEN];
+    let len = name.iter().position(|&b| b == 0).unwrap_or(VAR_NAME_LEN);
+    &name[..len]
+}
+
+fn read_i32_at(bytes: &[u8], offset: usize) -> i32 {
+    let mut word = [0; 4];
+    word.copy_from_slice(&bytes[offset..offset + 4]);
+    i32::from_le_bytes(word)
+}
+
+#[cfg(windows)]
+mod memory {
+    use std::ffi::c_void;
+
+    use windows::Win32::{
+        Foundation::{CloseHandle, HANDLE},
+        System::Memory::{
+            FILE_MAP_READ, MEMORY_MAPPED_VIEW_ADDRESS, MapViewOfFile, OpenFileMappingW,
+            UnmapViewOfFile,
+        },
+    };
+    use windows_core::w;
+
+    use super::{HEADER_LEN, Telemetry, read_i32_at};
+    use crate::{BroadcastError, Result};
+
+    /// The mapped telemetry file, stored as addresses since handles are
+    /// not `Send`.
+    #[derive(Debug)]
+    pub(super) struct Memory {
+        mapping: usize,
+        view: usize,
+    }
+
+    impl Telemetry {
+        /// Map the sim's telemetry.
+        ///
+        /// Returns [`BroadcastError::Connection`] if the sim has not been
+        /// started since boot; the mapping outlives the sim, so use
+        /// [`Telemetry::is_connected`] to check it is still running.
+        pub fn open() -> Result<Self> {
+            let mapping = unsafe {
+                OpenFileMappingW(FILE_MAP_READ.0, false, w!("Local\\IRSDKMemMapFileName"))
+            }
+            .map_err(|error| {
+                BroadcastError::connection_failed(format!("iRacing telemetry not found: {error}"))
+            })?;
+
+            let view = unsafe { MapViewOfFile(mapping, FILE_MAP_READ, 0, 0, 0) };
+            if view.Value.is_null() {
+                let error = windows_core::Error::from_thread();
+                let _ = unsafe { CloseHandle(mapping) };
+                return Err(BroadcastError::windows_api_error(
+                    "MapViewOfFile",
+                    None,
+                    error,
+                ));
+            }
+
+            Ok(Telemetry {
+                memory: Memory {
+                    mapping: mapping.0 as usize,
+                    view: view.Value as usize,
+                },
+            })
+        }
+    }
+
+    impl Memory {
+        /// Copy `buf.len()` bytes at `offset`, refusing reads past the
+        /// regions the header describes.
+        pub(super) fn read(&self, offset: usize, buf: &mut [u8]) -> bool {
+            let Some(end) = offset.checked_add(buf.len()) else {
+                return false;
+            };
+            if end > self.len() {
+                return false;
+            }
+            // The sim rewrites values while we read; a torn value is no
+            // worse than one from the previous tick.
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    (self.view as *const u8).add(offset),
+                    buf.as_mut_ptr(),
+                    buf.len(),
+                );
+            }
+            true
+        }
+
+        /// End of the furthest region named by the header.
+        fn len(&self) -> usize {
+            let mut header = [0; HEADER_LEN];
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    self.view as *const u8,
+                    header.as_mut_ptr(),
+                    HEADER_LEN,
+                );
+            }
+            let field = |offset| usize::try_from(read_i32_at(&header, offset)).unwrap_or(0);
+
+            let session_info = field(20) + field(16);
+            let var_headers = field(28) + field(24) * super::VAR_HEADER_LEN;
+            let buffers = (0..field(32).min(4))
+                .map(|slot| field(48 + slot * 16 + 4) + field(36))
+                .max()
+                .unwrap_or(0);
+            HEADER_LEN.max(session_info).max(var_headers).max(buffers)
+        }
+    }
+
+    impl Drop for Memory {
+        fn drop(&mut self) {
+            unsafe {
+                let _ = UnmapViewOfFile(MEMORY_MAPPED_VIEW_ADDRESS {
+                    Value: self.view as *mut c_void,
+                });
+                let _ = CloseHandle(HANDLE(self.mapping as *mut c_void));
+            }
+        }
+    }
+}
+
+#[cfg(not(windows))]
+mod memory {
+    use super::Telemetry;
+    use crate::{BroadcastError, Result};
+
+    /// Telemetry cannot be opened off Windows.
+    #[derive(Debug)]
+    pub(super) enum Memory {}
+
+    impl Telemetry {
+        /// Always fails off Windows.
+        pub fn open() -> Result<Self> {
+            Err(BroadcastError::unsupported_platform("Telemetry", "Windows"))
+        }
+    }
+
+    impl Memory {
+        pub(super) fn read(&self, _offset: usize, _buf: &mut [u8]) -> bool {
+            match *self {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CameraFocusMode, ReplaySpeed};
+
+    /// A telemetry image with two buffers, the second being the latest.
+    fn image(vars: &[(&str, i32, i32)]) -> Vec<u8> {
+        let var_header_offset = HEADER_LEN;
+        let buffer_len = 4 * vars.len();
+        let buffers = var_header_offset + vars.len() * VAR_HEADER_LEN;
+        let mut image = vec![0; buffers + 2 * buffer_len];
+
+        let mut put = |offset: usize, value: i32| {
+            image[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+        };
+        put(4, STATUS_CONNECTED);
+        put(24, vars.len() as i32);
+        put(28, var_header_offset as i32);
+        put(32, 2);
+        put(36, buffer_len as i32);
+        // Slot 0 is stale: lower tick count, and every value is -1.
+        put(48, 10);
+        put(52, buffers as i32);
+        put(64, 11);
+        put(68, (buffers + buffer_len) as i32);
+        for (index, (_, var_type, value)) in vars.iter().enumerate() {
+            let header = var_header_offset + index * VAR_HEADER_LEN;
+            put(header, *var_type);
+            put(header + 4, 4 * index as i32);
+            put(buffers + 4 * index, -1);
+            put(buffers + buffer_len + 4 * index, *value);
+        }
+        for (index, (name, ..)) in vars.iter().enumerate() {
+            let name_offset = var_header_offset + index * VAR_HEADER_LEN + 16;
+            image[name_offset..name_offset + name.len()].copy_from_slice(name.as_bytes());
+        }
+        image
+    }
+
+    fn lookup(image: &[u8], name: &str) -> Option<i32> {
+        lookup_i32(
+            |offset, buf| match image.get(offset..offset + buf.len()) {
+                Some(bytes) => {
+                    buf.copy_from_slice(bytes);
+                    true
+                }
+                None => false,
+            },
+            name,
+        )
+    }
+
+    #[test]
+    fn test_lookup_reads_latest_buffer() {
+        let image = image(&[
+            ("CamCarIdx", 2, 7),
+            ("PitSvFlags", 3, 0x30),
+            ("IsReplayPlaying", 1, 1),
+            ("SessionTime", 5, 0),
+        ]);
+
+        assert_eq!(lookup(&image, "CamCarIdx"), Some(7));
+        assert_eq!(lookup(&image, "PitSvFlags"), Some(0x30));
+        assert_eq!(lookup(&image, "IsReplayPlaying"), Some(1));
+        assert_eq!(lookup(&image, "SessionTime"), None);
+        assert_eq!(lookup(&image, "CamCar"), None);
+        assert_eq!(lookup(&image[..HEADER_LEN], "CamCarIdx"), None);
+    }
+
+    #[test]
+    fn test_camera_expectations_skip_kept_numbers() {
+        assert_eq!(
+            expectations(&BroadcastMessage::camera_switch_number("064", 4, 2)),
+            [
+                ("CamGroupNumber", Expected::Equals(4)),
+                ("CamCameraNumber", Expected::Equals(2)),
+            ]
+        );
+        assert_eq!(
+            expectations(&BroadcastMessage::CameraSwitchFocus(
+                CameraFocusMode::Leader,
+                0,
+                3
+            )),
+            [("CamCameraNumber", Expected::Equals(3))]
+        );
+    }
+
+    #[test]
+    fn test_replay_and_pit_expectations() {
+        assert_eq!(
+            expectations(&BroadcastMessage::ReplaySetPlaySpeed(
+                ReplaySpeed::new(-2, false).unwrap()
+            )),
+            [
+                ("ReplayPlaySpeed", Expected::Equals(-2)),
+                ("ReplayPlaySlowMotion", Expected::Equals(0)),
+            ]
+        );
+        assert_eq!(
+            expectations(&BroadcastMessage::ReplaySetPlayPosition(
+                ReplayPositionMode::Begin,
+                600
+            )),
+            [("ReplayFrameNum", Expected::Equals(600))]
+        );
+        assert!(
+            expectations(&BroadcastMessage::ReplaySetPlayPosition(
+                ReplayPositionMode::Current,
+                600
+            ))
+            .is_empty()
+        );
+
+        let [(name, tearoff)] = expectations(&PitCommandMode::Tearoff.into())[..] else {
+            panic!("expected one pit flag check");
+        };
+        assert_eq!(name, "PitSvFlags");
+        assert!(tearoff.holds(0x21) && !tearoff.holds(0x01));
+
+        let [(_, clear_tires)] = expectations(&PitCommandMode::ClearTires.into())[..] else {
+            panic!("expected one pit flag check");
+        };
+        assert!(clear_tires.holds(0x30) && !clear_tires.holds(0x31));
+
+        assert!(expectations(&BroadcastMessage::ReplaySetState).is_empty());
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_open_unsupported_off_windows() {
+        assert!(matches!(
+            Telemetry::open(),
+            Err(BroadcastError::UnsupportedPlatform { .. })
+        ));
+    }
+
+    /// Only runs its checks while the sim is running a session.
+    #[cfg(windows)]
+    #[test]
+    fn test_send_verified_with_sim() {
+        let Ok(telemetry) = Telemetry::open() else {
+            return;
+        };
+        if !telemetry.is_connected() {
+            return;
+        }
+
+        let client = Client::new().unwrap();
+        let camera = telemetry.read_i32("CamCameraNumber").unwrap();
+        let group = telemetry.read_i32("CamGroupNumber").unwrap();
+        let message = BroadcastMessage::CameraSwitchFocus(
+            CameraFocusMode::Exciting,
+            group as u8,
+            camera as u8,
+        );
+
+        client
+            .send_verified(message, &telemetry, Duration::from_secs(2))
+            .unwrap();
+    }
+}
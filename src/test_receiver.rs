@@ -0,0 +1,206 @@
+//! A hidden window that records the raw broadcast messages sent to it, for
+//! end-to-end tests of the send path on Windows.
+//!
+//! Unlike [`Listener`](crate::Listener), the receiver keeps the packed
+//! `WPARAM`/`LPARAM` exactly as Windows delivered them, so tests can check
+//! the wire format rather than this crate's own decoding. It is a
+//! message-only window: `HWND_BROADCAST` never reaches it, so it only sees
+//! messages sent to [`Receiver::hwnd`] and is unaffected by tests
+//! broadcasting concurrently.
+
+use std::{sync::mpsc, time::Duration};
+
+use windows::Win32::Foundation::HWND;
+
+use crate::{
+    Client,
+    hidden_window::{HiddenWindow, WindowKind},
+};
+
+/// A received message's `(WPARAM, LPARAM)`.
+type Packed = (usize, isize);
+
+/// How long [`Receiver::recv`] waits for a message.
+const RECV_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A message-only window registered for `IRSDK_BROADCASTMSG`, pumped on its
+/// own thread. Dropping it destroys the window and joins the thread.
+pub(crate) struct Receiver {
+    window: HiddenWindow,
+    messages: mpsc::Receiver<Packed>,
+}
+
+impl Receiver {
+    /// Create the window, panicking if Windows refuses.
+    pub(crate) fn new() -> Self {
+        let message_id = Client::new().unwrap().message_id();
+        let (sender, messages) = mpsc::channel();
+
+        let window = HiddenWindow::spawn(
+            "test-receiver",
+            WindowKind::MessageOnly,
+            message_id,
+            Box::new(move |wparam, lparam| {
+                // The test may have given up waiting; nothing to report then.
+                let _ = sender.send((wparam, lparam));
+            }),
+        )
+        .unwrap();
+
+        Receiver { window, messages }
+    }
+
+    /// The window to send to, e.g. with [`Client::send_message_to`].
+    pub(crate) fn hwnd(&self) -> HWND {
+        self.window.hwnd()
+    }
+
+    /// The packed `(WPARAM, LPARAM)` of the next broadcast message, or
+    /// `None` if none arrives within a few seconds.
+    pub(crate) fn recv(&self) -> Option<Packed> {
+        self.messages.recv_timeout(RECV_TIMEOUT).ok()
+    }
+
+    /// The next broadcast message if one has already arrived.
+    pub(crate) fn try_recv(&self) -> Option<Packed> {
+        self.messages.try_recv().ok()
+    }
+}
+
+mod tests {
+    use super::*;
+    use crate::{
+        BroadcastMessage, CameraFocusMode, CameraState, ChatCommandMode, Delivery, FfbForce,
+        PitCommandMode, ReplayPositionMode, ReplaySearchMode, ReplaySpeed, TelemetryCommandMode,
+        VideoCaptureMode,
+    };
+
+    /// One message of every variant with the `(WPARAM, LPARAM)` the SDK's
+    /// `irsdk_broadcastMsg` would send for it.
+    fn expected_wire() -> Vec<(BroadcastMessage, usize, isize)> {
+        vec![
+            (
+                BroadcastMessage::CameraSwitchPosition(3, 11, 2),
+                0x3_0000,
+                0x2_000b,
+            ),
+            (
+                BroadcastMessage::camera_switch_number("064", 11, 0),
+                0x0bf8_0001,
+                0xb,
+            ),
+            (
+                BroadcastMessage::CameraSwitchFocus(CameraFocusMode::Leader, 11, 0),
+                0xfffe_0000,
+                0xb,
+            ),
+            (
+                BroadcastMessage::CameraSetState(
+                    CameraState::CAM_TOOL_ACTIVE | CameraState::UI_HIDDEN,
+                ),
+                0xc_0002,
+                0,
+            ),
+            (
+                BroadcastMessage::ReplaySetPlaySpeed(ReplaySpeed::rewind(2).unwrap()),
+                0xfffe_0003,
+                0,
+            ),
+            (
+                BroadcastMessage::ReplaySetPlayPosition(ReplayPositionMode::Begin, 200_000),
+                0x4,
+                0x3_0d40,
+            ),
+            (
+                BroadcastMessage::replay_seek_from_end(60),
+                0x2_0004,
+                0xffff_ffc4,
+            ),
+            (
+                BroadcastMessage::ReplaySearch(ReplaySearchMode::NextIncident),
+                0x9_0005,
+                0,
+            ),
+            (BroadcastMessage::ReplaySetState, 0x6, 0),
+            (BroadcastMessage::ReloadAllTextures, 0x7, 0),
+            (BroadcastMessage::ReloadTextures(7), 0x1_0007, 0x7),
+            (
+                BroadcastMessage::ChatCommand(ChatCommandMode::Reply),
+                0x2_0008,
+                0,
+            ),
+            (BroadcastMessage::ChatCommandMacro(3), 0x8, 0x3),
+            (
+                BroadcastMessage::PitCommand(PitCommandMode::Fuel(40)),
+                0x2_0009,
+                0x28,
+            ),
+            (
+                BroadcastMessage::PitCommand(PitCommandMode::LF(179)),
+                0x3_0009,
+                0xb3,
+            ),
+            (
+                BroadcastMessage::TelemetryCommand(TelemetryCommandMode::Restart),
+                0x2_000a,
+                0,
+            ),
+            // 12.5 Nm as 16.16 fixed point.
+            (
+                BroadcastMessage::FFBCommand(FfbForce::from_newton_meters(12.5)),
+                0xb,
+                0xc_8000,
+            ),
+            // One hour into session 2, in milliseconds.
+            (
+                BroadcastMessage::ReplaySearchSessionTime(2, 3_600_000),
+                0x2_000c,
+                0x36_ee80,
+            ),
+            (
+                BroadcastMessage::VideoCapture(VideoCaptureMode::ToggleCapture),
+                0x3_000d,
+                0,
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_every_variant_arrives_packed() {
+        let receiver = Receiver::new();
+        let client = Client::new().unwrap();
+
+        for (message, wparam, lparam) in expected_wire() {
+            client.send_message_to(receiver.hwnd(), message).unwrap();
+            assert_eq!(receiver.recv(), Some((wparam, lparam)), "{message:?}");
+        }
+        assert_eq!(receiver.try_recv(), None);
+    }
+
+    #[test]
+    fn test_each_delivery_arrives() {
+        let receiver = Receiver::new();
+        let message = BroadcastMessage::PitCommand(PitCommandMode::Fuel(40));
+
+        for delivery in [Delivery::Notify, Delivery::Post, Delivery::Send] {
+            let mut client = Client::new().unwrap();
+            client.set_delivery(delivery);
+
+            client.send_message_to(receiver.hwnd(), message).unwrap();
+            assert_eq!(receiver.recv(), Some((0x2_0009, 0x28)), "{delivery:?}");
+        }
+    }
+
+    #[test]
+    fn test_send_delivery_is_synchronous() {
+        let receiver = Receiver::new();
+        let mut client = Client::new().unwrap();
+        client.set_delivery(Delivery::Send);
+
+        client
+            .send_message_to(receiver.hwnd(), BroadcastMessage::ReplaySetState)
+            .unwrap();
+        // `SendMessageW` returns only once the window has processed it.
+        assert_eq!(receiver.try_recv(), Some((0x6, 0)));
+    }
+}
@@ -0,0 +1,62 @@
+//! Unit conversions for pit service values.
+//!
+//! The simulator always reads tire pressures in kPa and fuel in liters,
+//! whatever units the driver's display uses. These helpers are the single
+//! place the conversion factors live; the checked [`PitCommandMode`]
+//! constructors use them before rounding to the whole units the broadcast
+//! parameters carry.
+//!
+//! ```
+//! use iracing_broadcast::units::{kpa_to_psi, psi_to_kpa};
+//!
+//! assert_eq!(psi_to_kpa(26.0).round(), 179.0);
+//! assert_eq!(kpa_to_psi(179.0).round(), 26.0);
+//! ```
+//!
+//! [`PitCommandMode`]: crate::PitCommandMode
+
+/// Kilopascals per pound-force per square inch.
+pub const KPA_PER_PSI: f32 = 6.894_757;
+
+/// Liters per US gallon.
+pub const LITERS_PER_GALLON: f32 = 3.785_411_8;
+
+/// Convert a pressure in PSI to kPa.
+pub fn psi_to_kpa(psi: f32) -> f32 {
+    psi * KPA_PER_PSI
+}
+
+/// Convert a pressure in kPa to PSI.
+pub fn kpa_to_psi(kpa: f32) -> f32 {
+    kpa / KPA_PER_PSI
+}
+
+/// Convert a volume in US gallons to liters.
+pub fn gallons_to_liters(gallons: f32) -> f32 {
+    gallons * LITERS_PER_GALLON
+}
+
+/// Convert a volume in liters to US gallons.
+pub fn liters_to_gallons(liters: f32) -> f32 {
+    liters / LITERS_PER_GALLON
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversion_factors() {
+        assert_eq!(psi_to_kpa(1.0), 6.894_757);
+        assert_eq!(gallons_to_liters(1.0), 3.785_411_8);
+        assert!((kpa_to_psi(psi_to_kpa(32.5)) - 32.5).abs() < 1e-4);
+        assert!((liters_to_gallons(gallons_to_liters(18.0)) - 18.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_rounded_pressures() {
+        assert_eq!(psi_to_kpa(26.0).round(), 179.0);
+        assert_eq!(psi_to_kpa(30.0).round(), 207.0);
+        assert_eq!(kpa_to_psi(200.0).round(), 29.0);
+    }
+}
@@ -1,41 +1,296 @@
-/// Encodes a car number string into a sortable `u16`, keeping leading zeros
-/// distinct by folding them into the thousands place (`1` -> `1`, `01` -> `2001`,
-/// `001` -> `3001`). All-zero strings treat one zero as the number itself so we
-/// only count the extra zeros.
+use alloc::{format, string::String};
+use core::{fmt::Display, num::IntErrorKind, ops::RangeInclusive};
+
+use crate::{BroadcastError, CarNumber, Result};
+
+/// Encodes a car number string into the `u16` the SDK expects, keeping
+/// leading zeros distinct (`1` -> `1`, `01` -> `2001`, `001` -> `3001`).
+///
+/// The scheme matches the SDK's `padCarNum`: a number without leading zeros
+/// (including a lone `0`) is sent as-is, and one with leading zeros is sent
+/// as its value plus `1000` times its total digit count. For the one to
+/// three digit numbers iRacing allows, every string gets a distinct value
+/// between `0` and `3999`.
+///
+/// Longer strings are outside the SDK's scheme: they still encode, but can
+/// collide (`0999` and `4999` both give `4999`), and results above
+/// `u16::MAX` saturate to `u16::MAX`. Malformed input is treated as the
+/// number `0`. Use [`try_pad_car_number`] to reject both instead.
 pub fn pad_car_number(s: &str) -> u16 {
-    let bytes = s.as_bytes();
-    let len = bytes.len();
-
-    // Count leading zeros without allocating
-    let mut zeros = 0usize;
-    for &b in bytes {
-        if b == b'0' {
-            zeros += 1;
-        } else {
-            break;
-        }
+    // Fall back to zero for malformed car numbers so we avoid panicking the
+    // caller, but keep oversized ones oversized so they saturate below.
+    let num = match s.parse::<u64>() {
+        Ok(num) => num,
+        Err(error) if *error.kind() == IntErrorKind::PosOverflow => u64::MAX,
+        Err(_) => 0,
+    };
+
+    let padded = if s.len() > 1 && s.starts_with('0') {
+        num.saturating_add(1000 * s.len() as u64)
+    } else {
+        num
+    };
+
+    u16::try_from(padded).unwrap_or(u16::MAX)
+}
+
+/// Decodes a [`pad_car_number`] value back into the car number string,
+/// restoring its leading zeros, e.g. to display `"064"` given `3064`.
+///
+/// For the one to three digit numbers iRacing allows this is the exact
+/// inverse of [`pad_car_number`]. Values no such number encodes to, like
+/// `2064` (two digits, but `64` has no leading zero) or anything above
+/// `3999`, decode to car `"0"`, as [`CarNumber`](crate::CarNumber)'s
+/// lenient conversions do; [`CarNumber::from_padded`](crate::CarNumber::from_padded)
+/// rejects them instead.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::{pad_car_number, unpad_car_number};
+///
+/// assert_eq!(unpad_car_number(3064), "064");
+/// assert_eq!(unpad_car_number(pad_car_number("01")), "01");
+/// assert_eq!(unpad_car_number(2064), "0");
+/// ```
+pub fn unpad_car_number(encoded: u16) -> String {
+    CarNumber::from_padded(encoded)
+        .unwrap_or_default()
+        .as_str()
+        .into()
+}
+
+/// Validating counterpart to [`pad_car_number`].
+///
+/// Accepts one to three ASCII digits (leading zeros included) and returns the
+/// same encoding as [`pad_car_number`]. Anything else is rejected with
+/// [`BroadcastError::InvalidCarNumber`] instead of silently becoming car `0`.
+///
+/// # Examples
+///
+/// ```
+/// use iracing_broadcast::try_pad_car_number;
+///
+/// assert_eq!(try_pad_car_number("12")?, 12);
+/// assert_eq!(try_pad_car_number("012")?, 3012);
+/// assert!(try_pad_car_number("1a").is_err());
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+pub fn try_pad_car_number(s: &str) -> Result<u16> {
+    if s.is_empty() {
+        return Err(BroadcastError::invalid_car_number(s, "car number is empty"));
+    }
+
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(BroadcastError::invalid_car_number(
+            s,
+            "car number may only contain digits",
+        ));
+    }
+
+    if s.len() > CarNumber::MAX_DIGITS {
+        return Err(BroadcastError::invalid_car_number(
+            s,
+            format!("car number is longer than {} digits", CarNumber::MAX_DIGITS),
+        ));
     }
 
-    // If the entire string was zeros, subtract 1
-    if zeros > 0 && zeros == len {
-        zeros -= 1;
+    Ok(pad_car_number(s))
+}
+
+/// Packs a message type and its three parameter words into the
+/// `(WPARAM, LPARAM)` pair broadcast to the simulator.
+///
+/// This is the SDK's `MAKELONG(msg_type, var1)` and `MAKELONG(var2, var3)`:
+///
+/// | Parameter | Bits 0-15  | Bits 16-31 |
+/// |-----------|------------|------------|
+/// | `WPARAM`  | `msg_type` | `var1`     |
+/// | `LPARAM`  | `var2`     | `var3`     |
+///
+/// Higher bits are zero. [`BroadcastMessage::decode`] is the inverse.
+///
+/// [`BroadcastMessage::decode`]: crate::BroadcastMessage::decode
+///
+/// # Examples
+///
+/// ```
+/// // PitCommand (9), Fuel (2), 65 liters
+/// assert_eq!(iracing_broadcast::pack(9, 2, 65, 0), (0x0002_0009, 0x0041));
+/// ```
+pub fn pack(msg_type: u16, var1: u16, var2: u16, var3: u16) -> (usize, isize) {
+    let wparam = msg_type as usize | ((var1 as usize) << 16);
+    let lparam = var2 as isize | ((var3 as isize) << 16);
+    (wparam, lparam)
+}
+
+/// Splits a 32-bit value into its `(low, high)` 16-bit words, matching the
+/// `MAKELONG(low, high)` layout the SDK uses for wide parameters.
+pub fn split_words(value: u32) -> (u16, u16) {
+    (value as u16, (value >> 16) as u16)
+}
+
+/// Narrows `value` to a parameter accepting `range`, naming `parameter` in
+/// the error if it does not fit.
+pub(crate) fn checked_param<T>(parameter: &str, value: u32, range: RangeInclusive<T>) -> Result<T>
+where
+    T: Copy + Display + Into<u32> + TryFrom<u32>,
+{
+    let (min, max) = (*range.start(), *range.end());
+    if (min.into()..=max.into()).contains(&value)
+        && let Ok(value) = T::try_from(value)
+    {
+        return Ok(value);
     }
 
-    // Parse the numeric value (leading zeros are fine). Fall back to zero for
-    // any malformed car numbers so we avoid panicking the caller.
-    let num: u16 = s.parse().unwrap_or(0);
+    Err(BroadcastError::invalid_parameter(
+        parameter,
+        format!("{value} is outside {min}..={max}"),
+    ))
+}
 
-    if zeros > 0 {
-        let num_place = if num > 99 {
-            3
-        } else if num > 9 {
-            2
-        } else {
-            1
-        };
+/// [`f32::round`], which needs `std`: rounds to the nearest whole number,
+/// with half-way cases away from zero.
+pub(crate) fn round(value: f32) -> f32 {
+    // From 2^23 up every `f32` is already whole; NaN passes through too.
+    if value.is_nan() || value.abs() >= 8_388_608.0 {
+        return value;
+    }
 
-        num + 1000 * (num_place + zeros as u16)
+    let truncated = value as i32 as f32;
+    if (value - truncated).abs() >= 0.5 {
+        truncated + value.signum()
     } else {
-        num
+        truncated
+    }
+}
+
+/// Clamps `value` into `range`.
+pub(crate) fn saturating_param<T>(value: u32, range: RangeInclusive<T>) -> T
+where
+    T: Copy + Into<u32> + TryFrom<u32>,
+{
+    let (min, max) = (*range.start(), *range.end());
+    T::try_from(value.clamp(min.into(), max.into())).unwrap_or(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_try_pad_car_number_valid() {
+        assert_eq!(try_pad_car_number("12").unwrap(), 12);
+        assert_eq!(try_pad_car_number("012").unwrap(), 3012);
+        assert_eq!(try_pad_car_number("000").unwrap(), 3000);
+    }
+
+    #[test]
+    fn test_pad_car_number_is_unique_for_valid_numbers() {
+        let mut seen = std::collections::HashSet::new();
+        for digits in 1..=3 {
+            for value in 0..10u32.pow(digits) {
+                let car_number = format!("{value:0width$}", width = digits as usize);
+                let padded = try_pad_car_number(&car_number).unwrap();
+                assert!(padded < 4000, "{car_number} -> {padded}");
+                assert!(seen.insert(padded), "{car_number} collides at {padded}");
+                assert_eq!(unpad_car_number(padded), car_number);
+            }
+        }
+        assert_eq!(seen.len(), 1110);
+    }
+
+    #[test]
+    fn test_unpad_car_number_restores_leading_zeros() {
+        for (car_number, padded) in [
+            ("0", 0),
+            ("1", 1),
+            ("01", 2001),
+            ("001", 3001),
+            ("064", 3064),
+        ] {
+            assert_eq!(pad_car_number(car_number), padded);
+            assert_eq!(unpad_car_number(padded), car_number);
+        }
+
+        for invalid in [1064, 2064, 3100, 4000, u16::MAX] {
+            assert_eq!(unpad_car_number(invalid), "0", "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_pad_car_number_beyond_three_digits() {
+        assert_eq!(pad_car_number("0999"), 4999);
+        assert_eq!(pad_car_number("00999"), 5999);
+        assert_eq!(pad_car_number("0000"), 4000);
+        // Previously `num_place` stopped at 3, so these both gave 5000.
+        assert_eq!(pad_car_number("01000"), 6000);
+        assert_ne!(pad_car_number("01000"), pad_car_number("5000"));
+    }
+
+    #[test]
+    fn test_pad_car_number_saturates() {
+        assert_eq!(pad_car_number("065000"), u16::MAX);
+        assert_eq!(pad_car_number("99999"), u16::MAX);
+        assert_eq!(pad_car_number(&format!("{}1", "0".repeat(100))), u16::MAX);
+        assert_eq!(pad_car_number(&"9".repeat(40)), u16::MAX);
+
+        for car_number in ["0999", "065000", "99999"] {
+            assert!(try_pad_car_number(car_number).is_err());
+        }
+    }
+
+    #[test]
+    fn test_pack_bit_layout() {
+        assert_eq!(pack(0, 0, 0, 0), (0, 0));
+        assert_eq!(
+            pack(0x000D, 0xABCD, 0x1234, 0x5678),
+            (0xABCD_000D, 0x5678_1234)
+        );
+        // High words keep their top bit instead of sign-extending.
+        let (wparam, lparam) = pack(0xFFFF, 0xFFFF, 0xFFFF, 0xFFFF);
+        assert_eq!((wparam, lparam as usize), (0xFFFF_FFFF, 0xFFFF_FFFF));
+    }
+
+    #[test]
+    fn test_pack_wide_values() {
+        // FFB 2.5 Nm as 16.16 fixed point, and one hour of session time.
+        let (low, high) = split_words(0x0002_8000);
+        assert_eq!(pack(11, 0, low, high).1, 0x0002_8000);
+
+        let (low, high) = split_words(3_600_000);
+        assert_eq!(pack(12, 2, low, high), (0x0002_000C, 3_600_000));
+    }
+
+    #[test]
+    fn test_try_pad_car_number_invalid() {
+        for car_number in ["", "1a", "1000"] {
+            assert!(matches!(
+                try_pad_car_number(car_number),
+                Err(BroadcastError::InvalidCarNumber { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_round_matches_std() {
+        for value in [
+            0.0,
+            0.49,
+            0.5,
+            1.5,
+            2.5,
+            -0.5,
+            -2.5,
+            42.6,
+            179.26,
+            8_388_607.5,
+            1e10,
+            -1e10,
+        ] {
+            assert_eq!(round(value), value.round(), "{value}");
+        }
+        assert!(round(f32::NAN).is_nan());
+        assert_eq!(round(f32::INFINITY), f32::INFINITY);
     }
 }
@@ -0,0 +1,173 @@
+//! Noticing the simulator starting and quitting.
+
+use std::{
+    sync::mpsc::{self, RecvTimeoutError},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{BroadcastError, Client, Result};
+
+/// A background thread polling whether the simulator is running and
+/// reporting each change to a callback.
+///
+/// The sim counts as not running until the first poll, so a sim that is
+/// already up is reported as `true` right away; after that, the callback
+/// only runs when the state changes. Dropping the watcher (or calling
+/// [`SimWatcher::stop`]) stops the thread without waiting out the poll
+/// interval.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use iracing_broadcast::Client;
+///
+/// let _watcher = Client::watch_iracing(Duration::from_secs(1), |running| {
+///     println!("iRacing {}", if running { "started" } else { "quit" });
+/// })?;
+/// # Ok::<(), iracing_broadcast::BroadcastError>(())
+/// ```
+#[derive(Debug)]
+pub struct SimWatcher {
+    /// Dropped to wake and stop the thread.
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SimWatcher {
+    /// Call `detect` every `poll_interval` and `callback` with its result
+    /// whenever that differs from the previous one.
+    ///
+    /// [`Client::watch_iracing`] detects with
+    /// [`Client::is_iracing_running`]; any other check, e.g. one based on
+    /// telemetry, works the same way. Both closures run on the watcher's
+    /// thread.
+    pub fn new<D, F>(poll_interval: Duration, detect: D, callback: F) -> Result<Self>
+    where
+        D: Fn() -> bool + Send + 'static,
+        F: Fn(bool) + Send + 'static,
+    {
+        let (stop, stopped) = mpsc::channel::<()>();
+
+        let thread = thread::Builder::new()
+            .name("iracing-broadcast-watcher".into())
+            .spawn(move || {
+                let mut running = false;
+                loop {
+                    let now = detect();
+                    if now != running {
+                        running = now;
+                        callback(now);
+                    }
+
+                    // Nothing is ever sent, so only the timeout keeps going.
+                    if stopped.recv_timeout(poll_interval) != Err(RecvTimeoutError::Timeout) {
+                        break;
+                    }
+                }
+            })
+            .map_err(|error| BroadcastError::thread_failed("watcher", error))?;
+
+        Ok(SimWatcher {
+            stop: Some(stop),
+            thread: Some(thread),
+        })
+    }
+
+    /// Stop polling and wait for a callback in progress to finish.
+    pub fn stop(self) {
+        drop(self);
+    }
+}
+
+impl Drop for SimWatcher {
+    fn drop(&mut self) {
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Client {
+    /// Poll [`Client::is_iracing_running`] every `poll_interval` on a
+    /// background thread and call `callback` with `true` when the sim
+    /// appears and `false` when it goes away, e.g. to connect and
+    /// disconnect a broadcast tool automatically.
+    ///
+    /// Off Windows the sim is never running, so the callback never runs.
+    /// See [`SimWatcher`].
+    pub fn watch_iracing<F>(poll_interval: Duration, callback: F) -> Result<SimWatcher>
+    where
+        F: Fn(bool) + Send + 'static,
+    {
+        SimWatcher::new(poll_interval, Client::is_iracing_running, callback)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    };
+
+    use super::*;
+
+    const POLL: Duration = Duration::from_millis(5);
+    const WAIT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn test_reports_transitions() {
+        let running = Arc::new(AtomicBool::new(false));
+        let (sender, changes) = mpsc::channel();
+
+        let watcher = SimWatcher::new(
+            POLL,
+            {
+                let running = Arc::clone(&running);
+                move || running.load(Ordering::SeqCst)
+            },
+            move |now| sender.send(now).unwrap(),
+        )
+        .unwrap();
+
+        running.store(true, Ordering::SeqCst);
+        assert_eq!(changes.recv_timeout(WAIT), Ok(true));
+        running.store(false, Ordering::SeqCst);
+        assert_eq!(changes.recv_timeout(WAIT), Ok(false));
+
+        watcher.stop();
+        assert_eq!(changes.recv(), Err(mpsc::RecvError));
+    }
+
+    #[test]
+    fn test_already_running_is_reported_at_once() {
+        let (sender, changes) = mpsc::channel();
+        let watcher = SimWatcher::new(
+            Duration::from_secs(60),
+            || true,
+            move |now| sender.send(now).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(changes.recv_timeout(WAIT), Ok(true));
+        // Stopping does not wait out the long poll interval.
+        drop(watcher);
+        assert_eq!(changes.try_recv(), Err(mpsc::TryRecvError::Disconnected));
+    }
+
+    #[cfg(not(windows))]
+    #[test]
+    fn test_never_running_off_windows() {
+        let (sender, changes) = mpsc::channel();
+        let watcher = Client::watch_iracing(POLL, move |now| sender.send(now).unwrap()).unwrap();
+
+        assert_eq!(
+            changes.recv_timeout(POLL * 4),
+            Err(RecvTimeoutError::Timeout)
+        );
+        watcher.stop();
+    }
+}
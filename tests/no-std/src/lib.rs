@@ -0,0 +1,47 @@
+//! Builds `iracing-broadcast` without its `std` feature and encodes messages
+//! the way a front-end with no client would before shipping them to a
+//! Windows agent.
+
+#![no_std]
+
+use iracing_broadcast::{BroadcastMessage, PitCommandMode, pack};
+
+/// The `WPARAM`/`LPARAM` pair for `message`, ready to forward.
+pub fn encode(message: &BroadcastMessage) -> (usize, isize) {
+    let (message_type, var1, var2, var3) = message.encode();
+    pack(message_type as u16, var1, var2, var3)
+}
+
+/// Requests `liters` of fuel at the next stop.
+pub fn fuel(liters: u8) -> (usize, isize) {
+    encode(&BroadcastMessage::PitCommand(PitCommandMode::Fuel(liters)))
+}
+
+#[cfg(test)]
+mod tests {
+    use iracing_broadcast::{BroadcastMessageType, ReplaySearchMode};
+
+    use super::*;
+
+    #[test]
+    fn test_encode_fuel() {
+        // PitCommand (9), Fuel (2), 65 liters
+        assert_eq!(fuel(65), (0x0002_0009, 0x0041));
+    }
+
+    #[test]
+    fn test_encode_round_trips() {
+        let message = BroadcastMessage::ReplaySearch(ReplaySearchMode::NextLap);
+        let (wparam, lparam) = encode(&message);
+
+        assert_eq!(
+            BroadcastMessage::decode(wparam, lparam).unwrap(),
+            (
+                BroadcastMessageType::ReplaySearch,
+                u16::from(ReplaySearchMode::NextLap),
+                0,
+                0
+            )
+        );
+    }
+}